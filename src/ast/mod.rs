@@ -12,7 +12,7 @@ use pat::{NamePat, Pat};
 use std::{
     ffi::OsStr,
     fmt::{self, Display},
-    ops::Deref,
+    ops::{Deref, Range},
     path::{Path, PathBuf},
 };
 use ustr::{ustr, Ustr};
@@ -24,6 +24,7 @@ pub struct Module {
     pub file_id: FileId,
     pub bindings: Vec<Binding>,
     pub comptime_blocks: Vec<Comptime>,
+    pub static_ifs: Vec<StaticIf>,
 }
 
 impl Module {
@@ -34,14 +35,45 @@ impl Module {
             info: module_info,
             bindings: vec![],
             comptime_blocks: vec![],
+            static_ifs: vec![],
         }
     }
 
-    pub fn find_binding(&self, name: Ustr) -> Option<(usize, &Binding)> {
+    // Returns whether the binding at `index` is in the taken branch of every `static if` it is nested in.
+    // `condition` returns the evaluated condition of the `static if` at an index, if it is known.
+    // A binding in a `static if` whose condition isn't known isn't considered to be declared
+    pub fn is_binding_in_taken_branch(&self, index: usize, condition: impl Fn(usize) -> Option<bool>) -> bool {
+        self.static_ifs.iter().enumerate().all(|(static_if_index, static_if)| {
+            let in_then = static_if.then_bindings.contains(&index);
+            let in_else = static_if.else_bindings.contains(&index);
+
+            !(in_then || in_else) || condition(static_if_index) == Some(in_then)
+        })
+    }
+
+    // Returns the names of all bindings declared in this module, skipping untaken branches of `static if`s
+    pub fn declared_binding_names(&self, condition: impl Fn(usize) -> Option<bool>) -> Vec<Ustr> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.is_binding_in_taken_branch(*index, &condition))
+            .flat_map(|(_, binding)| match &binding.kind {
+                BindingKind::Let { pat, .. } => pat.iter().map(|pat| pat.name).collect::<Vec<_>>(),
+                BindingKind::Function { name, .. }
+                | BindingKind::ExternFunction { name, .. }
+                | BindingKind::ExternVariable { name, .. }
+                | BindingKind::Type { name, .. } => vec![name.name],
+            })
+            .collect()
+    }
+
+    // Returns all bindings with the given name.
+    // There can be more than one when a name is declared in different branches of a `static if`
+    pub fn find_bindings(&self, name: Ustr) -> impl Iterator<Item = (usize, &Binding)> {
         self.bindings
             .iter()
             .enumerate()
-            .find(|(_, binding)| match &binding.kind {
+            .filter(move |(_, binding)| match &binding.kind {
                 BindingKind::Let { pat, .. } => pat.iter().any(|pat| pat.name == name),
                 BindingKind::Function {
                     name: NameAndSpan { name: binding_name, .. },
@@ -63,6 +95,16 @@ impl Module {
     }
 }
 
+// A module-level `static if`. Only the bindings in the taken branch are declared.
+// Branches are stored as ranges of indices into the module's bindings
+#[derive(Debug, PartialEq, Clone)]
+pub struct StaticIf {
+    pub condition: Box<Ast>,
+    pub then_bindings: Range<usize>,
+    pub else_bindings: Range<usize>,
+    pub span: Span,
+}
+
 define_id_type!(FunctionId);
 
 #[derive(Debug, PartialEq, Clone)]
@@ -76,8 +118,8 @@ pub enum Ast {
     Loop(Loop),
     While(While),
     For(For),
-    Break(Empty),
-    Continue(Empty),
+    Break(LoopControl),
+    Continue(LoopControl),
     Return(Return),
     If(If),
     Block(Block),
@@ -189,6 +231,13 @@ pub struct Empty {
     pub span: Span,
 }
 
+// A `break` or `continue`, which can optionally specify the depth of the loop it targets, i.e: `break 2`
+#[derive(Debug, PartialEq, Clone)]
+pub struct LoopControl {
+    pub depth: Option<usize>,
+    pub span: Span,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ArrayLiteral {
     pub kind: ArrayLiteralKind,
@@ -283,6 +332,7 @@ pub struct StructType {
 pub struct StructTypeField {
     pub name: Ustr,
     pub ty: Ast,
+    pub vis: Vis,
     pub span: Span,
 }
 
@@ -342,6 +392,7 @@ pub struct Literal {
 #[derive(strum_macros::IntoStaticStr, Debug, PartialEq, Clone, Copy)]
 pub enum LiteralKind {
     Nil,
+    Null,
     Bool(bool),
     Int(i128),
     Float(f64),
@@ -371,6 +422,27 @@ pub struct Comptime {
 pub enum BuiltinKind {
     SizeOf(Box<Ast>),
     AlignOf(Box<Ast>),
+    SizeOfVal(Box<Ast>),
+    AlignOfVal(Box<Ast>),
+    TypeId(Box<Ast>),
+    TypeEq(Box<Ast>, Box<Ast>),
+    FieldType(Box<Ast>, Box<Ast>),
+    FieldCount(Box<Ast>),
+    IncludeStr(Box<Ast>),
+    AddWithOverflow(Box<Ast>, Box<Ast>),
+    IsAligned(Box<Ast>, Box<Ast>),
+    MemCmp(Box<Ast>, Box<Ast>, Box<Ast>),
+    Splat(Box<Ast>, Box<Ast>, Box<Ast>),
+    Asm(Box<Ast>, Box<Ast>, Box<Ast>, Vec<Ast>),
+    Assume(Box<Ast>),
+    Breakpoint,
+    Src,
+    Abort(Box<Ast>),
+    Zeroed(Box<Ast>),
+    Panic(Box<Ast>),
+    Ref(Box<Ast>, bool),
+    Deref(Box<Ast>),
+    Call(Box<Ast>, Box<Ast>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -534,7 +606,6 @@ pub struct Binding {
 }
 
 impl Binding {
-    #[allow(unused)]
     pub fn debug_name(&self) -> String {
         match &self.kind {
             BindingKind::Let { pat, .. } => pat.to_string(),
@@ -869,6 +940,7 @@ impl fmt::Display for LiteralKind {
             "{}",
             match self {
                 LiteralKind::Nil => "nil".to_string(),
+                LiteralKind::Null => "null".to_string(),
                 LiteralKind::Bool(v) => v.to_string(),
                 LiteralKind::Int(v) => v.to_string(),
                 LiteralKind::Float(v) => v.to_string(),
@@ -10,6 +10,7 @@ use std::{
     sync::{mpsc::channel, Arc},
 };
 use threadpool::ThreadPool;
+use ustr::ustr;
 
 #[derive(Debug, Clone, Copy)]
 pub struct AstGenerationStats {
@@ -28,6 +29,12 @@ pub fn generate_ast(workspace: &mut Workspace) -> (Vec<ast::Module>, AstGenerati
         diagnostics: workspace.diagnostics.clone(),
         parsed_files: HashSet::new(),
         total_lines: 0,
+        max_expr_depth: workspace.build_options.max_expr_depth,
+        root_source: workspace
+            .build_options
+            .source_contents
+            .clone()
+            .map(|source| (ustr(&workspace_root_file), source)),
     }));
 
     let thread_pool = ThreadPool::new(num_cpus::get());
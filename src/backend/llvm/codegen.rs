@@ -1,13 +1,14 @@
 use super::{
     abi::{align_of, size_of},
     traits::IsALoadInst,
+    ty::contains_over_aligned_type,
 };
 use crate::{
     ast::ExternLibrary,
     common::{build_options, scopes::Scopes, target::TargetMetrics},
     hir,
-    infer::type_ctx::TypeCtx,
-    types::*,
+    infer::{normalize::Normalize, type_ctx::TypeCtx},
+    types::{align_of::AlignOf, *},
     workspace::{BindingId, BindingInfo, ModuleId, ModuleInfo, Workspace},
 };
 use inkwell::{
@@ -137,7 +138,15 @@ pub(super) struct LoopBlock<'ctx> {
 
 impl<'g, 'ctx> Generator<'g, 'ctx> {
     pub(super) fn start(&mut self) {
-        self.gen_start_function();
+        if self.workspace.build_options.no_entry {
+            self.gen_exported_bindings();
+        } else if self.workspace.build_options.test_runner {
+            self.gen_test_runner_function();
+        } else {
+            self.gen_start_function();
+        }
+
+        self.gen_exported_symbols();
     }
 
     pub(super) fn optimize(&mut self) {
@@ -181,6 +190,15 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
         let binding_info = self.workspace.binding_infos.get(id).unwrap();
         let global_value = self.module.add_global(ty, None, &binding_info.name);
         global_value.set_linkage(linkage);
+
+        let ty = binding_info.ty.normalize(self.tcx);
+        let min_align = self.cache.binding_aligns.get(&id).copied();
+
+        if contains_over_aligned_type(&ty) || min_align.is_some() {
+            let align = ty.align_of(self.target_metrics.word_size).max(min_align.unwrap_or(1));
+            global_value.set_alignment(align as u32);
+        }
+
         global_value
     }
 
@@ -230,10 +248,16 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
 
         ptr.set_name(&binding_info.name);
 
-        let align = align_of(
-            ptr.get_type().get_element_type().try_into().unwrap(),
-            self.target_metrics.word_size,
-        );
+        let align = match binding_info.ty.normalize(self.tcx) {
+            // over-aligned structs don't carry their alignment in their llvm type
+            ty if contains_over_aligned_type(&ty) => ty.align_of(self.target_metrics.word_size),
+            _ => align_of(
+                ptr.get_type().get_element_type().try_into().unwrap(),
+                self.target_metrics.word_size,
+            ),
+        };
+
+        let align = align.max(self.cache.binding_aligns.get(&id).copied().unwrap_or(1));
 
         ptr.as_instruction_value().unwrap().set_alignment(align as u32).unwrap();
 
@@ -1,4 +1,5 @@
 use super::{
+    abi::size_of,
     codegen::{Codegen, FunctionState, Generator},
     ty::IntoLlvmType,
 };
@@ -9,8 +10,11 @@ use crate::{
     types::*,
 };
 use inkwell::{
-    types::IntType,
-    values::{BasicValue, BasicValueEnum, FunctionValue, InstructionOpcode, IntValue},
+    types::{ArrayType, BasicMetadataTypeEnum, BasicType, BasicTypeEnum, IntType},
+    values::{
+        ArrayValue, BasicMetadataValueEnum, BasicValue, BasicValueEnum, CallableValue, FunctionValue,
+        InstructionOpcode, IntValue, PointerValue, VectorValue,
+    },
     FloatPredicate, IntPredicate,
 };
 
@@ -37,6 +41,27 @@ impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::Builtin {
                 let (lhs, rhs, ty) = gen_binary(binary, generator, state);
                 generator.gen_rem(state, lhs, rhs, ty, binary.span)
             }
+            hir::Builtin::AddWithOverflow(binary) => {
+                let (lhs, rhs, ty) = gen_binary(binary, generator, state);
+                let (lhs, rhs) = (lhs.into_int_value(), rhs.into_int_value());
+
+                let overflow_fn = generator.get_overflow_fn(ast::BinaryOp::Add, ty, lhs.get_type());
+                let call_value = generator.builder.build_call(overflow_fn, &[lhs.into(), rhs.into()], "");
+                let return_value = call_value.try_as_basic_value().left().unwrap();
+
+                let result = generator.gep_struct(return_value, 0, "result", false);
+                let overflow_bit = generator.gep_struct(return_value, 1, "overflow_bit", false);
+
+                let tuple_type = binary.ty.llvm_type(generator);
+                let tuple_ptr = generator.build_alloca(state, tuple_type);
+
+                for (index, value) in [result, overflow_bit].into_iter().enumerate() {
+                    let ptr = generator.builder.build_struct_gep(tuple_ptr, index as u32, "").unwrap();
+                    generator.build_store(ptr, value);
+                }
+
+                generator.build_load(tuple_ptr, "load_tuple")
+            }
             hir::Builtin::Shl(binary) => {
                 let (lhs, rhs, _) = gen_binary(binary, generator, state);
                 generator.gen_shl(lhs, rhs)
@@ -161,6 +186,101 @@ impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::Builtin {
             hir::Builtin::Ref(ref_) => ref_.codegen(generator, state),
             hir::Builtin::Offset(offset) => offset.codegen(generator, state),
             hir::Builtin::Slice(slice) => slice.codegen(generator, state),
+            hir::Builtin::MemCmp(memcmp) => {
+                let lhs = memcmp.lhs.codegen(generator, state).into_pointer_value();
+                let rhs = memcmp.rhs.codegen(generator, state).into_pointer_value();
+                let len = memcmp.len.codegen(generator, state).into_int_value();
+
+                gen_memcmp(generator, lhs, rhs, len).into()
+            }
+            hir::Builtin::Asm(asm) => {
+                let inputs: Vec<BasicValueEnum> =
+                    asm.inputs.iter().map(|input| input.codegen(generator, state)).collect();
+
+                let input_types: Vec<BasicMetadataTypeEnum> =
+                    inputs.iter().map(|input| input.get_type().into()).collect();
+
+                let ty = asm.ty.normalize(generator.tcx);
+
+                let fn_type = if ty.is_unit() {
+                    generator.context.void_type().fn_type(&input_types, false)
+                } else {
+                    asm.ty.llvm_type(generator).fn_type(&input_types, false)
+                };
+
+                let inline_asm = generator.context.create_inline_asm(
+                    fn_type,
+                    asm.template.to_string(),
+                    asm.constraints.to_string(),
+                    true,
+                    false,
+                    None,
+                );
+
+                let callable: CallableValue = inline_asm.try_into().unwrap();
+
+                let args: Vec<BasicMetadataValueEnum> = inputs.into_iter().map(|input| input.into()).collect();
+
+                let result = generator.builder.build_call(callable, &args, "asm");
+
+                if ty.is_unit() {
+                    generator.const_unit()
+                } else {
+                    result.try_as_basic_value().left().unwrap()
+                }
+            }
+            hir::Builtin::Assume(unary) => {
+                let condition = unary.value.codegen(generator, state).into_int_value();
+
+                let assume = generator.get_or_add_function(
+                    "llvm.assume",
+                    generator
+                        .context
+                        .void_type()
+                        .fn_type(&[generator.context.bool_type().into()], false),
+                    None,
+                );
+
+                generator.builder.build_call(assume, &[condition.into()], "");
+
+                generator.const_unit()
+            }
+            hir::Builtin::Breakpoint(_) => {
+                if generator.workspace.build_options.optimization_level.is_debug() {
+                    let debugtrap = generator.get_or_add_function(
+                        "llvm.debugtrap",
+                        generator.context.void_type().fn_type(&[], false),
+                        None,
+                    );
+
+                    generator.builder.build_call(debugtrap, &[], "");
+                }
+
+                generator.const_unit()
+            }
+            hir::Builtin::Abort(unary) => {
+                let code = unary.value.codegen(generator, state).into_int_value();
+
+                let exit = generator.get_or_add_function(
+                    "exit",
+                    generator
+                        .context
+                        .void_type()
+                        .fn_type(&[generator.context.i32_type().into()], false),
+                    None,
+                );
+
+                generator.builder.build_call(exit, &[code.into()], "");
+                generator.build_unreachable();
+
+                generator.const_unit()
+            }
+            hir::Builtin::Zeroed(x) => x.ty.llvm_type(generator).const_zero(),
+            hir::Builtin::Panic(unary) => {
+                let message = unary.value.codegen(generator, state);
+                generator.gen_panic(state, message, unary.span);
+                generator.const_unit()
+            }
         }
     }
 }
@@ -217,10 +337,59 @@ fn gen_cmp<'g, 'ctx>(
 
             generator.builder.build_int_compare(int_predicate, lhs, rhs, "").into()
         }
+        Type::Array(..) if ty.is_bytewise_comparable() => {
+            // Arrays are compared by their bytes, so `lhs == rhs` is lowered to `memcmp(&lhs, &rhs, size) == 0`
+            let size = size_of(lhs.get_type(), generator.target_metrics.word_size);
+            let size = generator.ptr_sized_int_type.const_int(size as u64, false);
+
+            let lhs = generator.build_alloca_or_load_addr(state, lhs);
+            let rhs = generator.build_alloca_or_load_addr(state, rhs);
+
+            let result = gen_memcmp(generator, lhs, rhs, size);
+            let zero = result.get_type().const_zero();
+
+            generator
+                .builder
+                .build_int_compare(int_predicate, result, zero, "")
+                .into()
+        }
         ty => panic!("unexpected type: {}", ty.display(generator.tcx)),
     }
 }
 
+fn gen_memcmp<'g, 'ctx>(
+    generator: &mut Generator<'g, 'ctx>,
+    lhs: PointerValue<'ctx>,
+    rhs: PointerValue<'ctx>,
+    len: IntValue<'ctx>,
+) -> IntValue<'ctx> {
+    let raw_pointer_type = generator.raw_pointer_type();
+
+    let memcmp = generator.get_or_add_function(
+        "memcmp",
+        generator.context.i32_type().fn_type(
+            &[
+                raw_pointer_type.into(),
+                raw_pointer_type.into(),
+                generator.ptr_sized_int_type.into(),
+            ],
+            false,
+        ),
+        None,
+    );
+
+    let lhs = generator.builder.build_pointer_cast(lhs, raw_pointer_type, "");
+    let rhs = generator.builder.build_pointer_cast(rhs, raw_pointer_type, "");
+
+    generator
+        .builder
+        .build_call(memcmp, &[lhs.into(), rhs.into(), len.into()], "")
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value()
+}
+
 impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::Slice {
     fn codegen(&self, generator: &mut Generator<'g, 'ctx>, state: &mut FunctionState<'ctx>) -> BasicValueEnum<'ctx> {
         let value = self.value.codegen(generator, state);
@@ -299,6 +468,9 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
                     .build_gep(lhs.into_pointer_value(), &[rhs.into_int_value()], "padd")
                     .into()
             },
+            Type::Array(element_type, len) => {
+                self.gen_element_wise(state, lhs, rhs, element_type, *len, ast::BinaryOp::Add, span)
+            }
             _ => panic!("unexpected type `{}`", ty.display(self.tcx)),
         }
     }
@@ -333,6 +505,9 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
                     .build_gep(lhs.into_pointer_value(), &[rhs.into_int_value()], "psub")
                     .into()
             },
+            Type::Array(element_type, len) => {
+                self.gen_element_wise(state, lhs, rhs, element_type, *len, ast::BinaryOp::Sub, span)
+            }
             _ => panic!("unexpected type `{}`", ty.display(self.tcx)),
         }
     }
@@ -362,10 +537,109 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
                 .builder
                 .build_float_mul(lhs.into_float_value(), rhs.into_float_value(), "fmul")
                 .into(),
+            Type::Array(element_type, len) => {
+                self.gen_element_wise(state, lhs, rhs, element_type, *len, ast::BinaryOp::Mul, span)
+            }
             _ => panic!("unexpected type `{}`", ty.display(self.tcx)),
         }
     }
 
+    // Arrays of numbers are added, subtracted and multiplied element-wise.
+    // In release builds the elements are packed into an llvm vector, so the operation compiles to SIMD instructions.
+    // In debug builds each pair of elements is operated on separately, so integer overflow is still checked
+    fn gen_element_wise(
+        &mut self,
+        state: &mut FunctionState<'ctx>,
+        lhs: BasicValueEnum<'ctx>,
+        rhs: BasicValueEnum<'ctx>,
+        element_type: &Type,
+        len: usize,
+        op: ast::BinaryOp,
+        span: Span,
+    ) -> BasicValueEnum<'ctx> {
+        let lhs = lhs.into_array_value();
+        let rhs = rhs.into_array_value();
+        let array_type = lhs.get_type();
+
+        if self.workspace.build_options.optimization_level.is_release() {
+            let lhs = self.build_array_to_vector(lhs, len);
+            let rhs = self.build_array_to_vector(rhs, len);
+
+            let result = match (element_type, op) {
+                (Type::Float(_), ast::BinaryOp::Add) => self.builder.build_float_add(lhs, rhs, "vfadd"),
+                (Type::Float(_), ast::BinaryOp::Sub) => self.builder.build_float_sub(lhs, rhs, "vfsub"),
+                (Type::Float(_), ast::BinaryOp::Mul) => self.builder.build_float_mul(lhs, rhs, "vfmul"),
+                (_, ast::BinaryOp::Add) => self.builder.build_int_add(lhs, rhs, "vadd"),
+                (_, ast::BinaryOp::Sub) => self.builder.build_int_sub(lhs, rhs, "vsub"),
+                (_, ast::BinaryOp::Mul) => self.builder.build_int_mul(lhs, rhs, "vmul"),
+                _ => unreachable!("{}", op),
+            };
+
+            self.build_vector_to_array(result, array_type, len).into()
+        } else {
+            let mut result = array_type.get_undef();
+
+            for index in 0..len as u32 {
+                let lhs = self.builder.build_extract_value(lhs, index, "").unwrap();
+                let rhs = self.builder.build_extract_value(rhs, index, "").unwrap();
+
+                let value = match op {
+                    ast::BinaryOp::Add => self.gen_add(state, lhs, rhs, element_type.clone(), span),
+                    ast::BinaryOp::Sub => self.gen_sub(state, lhs, rhs, element_type.clone(), span),
+                    ast::BinaryOp::Mul => self.gen_mul(state, lhs, rhs, element_type.clone(), span),
+                    _ => unreachable!("{}", op),
+                };
+
+                result = self
+                    .builder
+                    .build_insert_value(result, value, index, "")
+                    .unwrap()
+                    .into_array_value();
+            }
+
+            result.into()
+        }
+    }
+
+    fn build_array_to_vector(&self, array: ArrayValue<'ctx>, len: usize) -> VectorValue<'ctx> {
+        let mut vector = match array.get_type().get_element_type() {
+            BasicTypeEnum::IntType(ty) => ty.vec_type(len as u32).get_undef(),
+            BasicTypeEnum::FloatType(ty) => ty.vec_type(len as u32).get_undef(),
+            ty => panic!("unexpected vector element type {:?}", ty),
+        };
+
+        for index in 0..len as u32 {
+            let element = self.builder.build_extract_value(array, index, "").unwrap();
+            let index = self.context.i32_type().const_int(index as u64, false);
+            vector = self.builder.build_insert_element(vector, element, index, "");
+        }
+
+        vector
+    }
+
+    fn build_vector_to_array(
+        &self,
+        vector: VectorValue<'ctx>,
+        array_type: ArrayType<'ctx>,
+        len: usize,
+    ) -> ArrayValue<'ctx> {
+        let mut array = array_type.get_undef();
+
+        for index in 0..len as u32 {
+            let element =
+                self.builder
+                    .build_extract_element(vector, self.context.i32_type().const_int(index as u64, false), "");
+
+            array = self
+                .builder
+                .build_insert_value(array, element, index, "")
+                .unwrap()
+                .into_array_value();
+        }
+
+        array
+    }
+
     pub(super) fn gen_div(
         &mut self,
         state: &mut FunctionState<'ctx>,
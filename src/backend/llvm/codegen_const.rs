@@ -40,6 +40,26 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
         self.context.const_struct(&values, false)
     }
 
+    // fills the padding fields of structs and tuples with explicit layouts, and of over-aligned structs
+    pub(super) fn const_struct_of_type(&mut self, ty: &Type, values: Vec<BasicValueEnum<'ctx>>) -> StructValue<'ctx> {
+        let struct_type = ty.llvm_type(self).into_struct_type();
+        let field_types = struct_type.get_field_types();
+
+        let mut fields: Vec<Option<BasicValueEnum>> = vec![None; field_types.len()];
+
+        for (index, value) in values.into_iter().enumerate() {
+            fields[self.llvm_field_index(ty, index) as usize] = Some(value);
+        }
+
+        let fields = fields
+            .into_iter()
+            .zip(field_types)
+            .map(|(value, field_type)| value.unwrap_or_else(|| field_type.const_zero()))
+            .collect::<Vec<BasicValueEnum>>();
+
+        struct_type.const_named_struct(&fields)
+    }
+
     #[inline]
     pub(super) fn const_bool(&self, b: bool) -> IntValue<'ctx> {
         self.context.bool_type().const_int(if b { 1 } else { 0 }, false)
@@ -83,7 +103,7 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
                     .map(|element| self.gen_const_value(state, &element.value, &element.ty.normalize(self.tcx)))
                     .collect::<Vec<BasicValueEnum>>();
 
-                self.const_struct(&values).into()
+                self.const_struct_of_type(ty, values).into()
             }
             ConstValue::Struct(fields) => {
                 let values = fields
@@ -91,8 +111,7 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
                     .map(|element| self.gen_const_value(state, &element.value, &element.ty.normalize(self.tcx)))
                     .collect::<Vec<BasicValueEnum>>();
 
-                // self.context.const_struct(&values, false).into();
-                ty.llvm_type(self).into_struct_type().const_named_struct(&values).into()
+                self.const_struct_of_type(ty, values).into()
             }
             ConstValue::Function(function) => {
                 let prev_block = if let Some(state) = state {
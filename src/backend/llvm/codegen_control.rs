@@ -8,13 +8,13 @@ impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::Control {
             hir::Control::If(x) => x.codegen(generator, state),
             hir::Control::While(x) => x.codegen(generator, state),
             hir::Control::Return(x) => x.codegen(generator, state),
-            hir::Control::Break(_) => {
-                let exit_block = state.loop_blocks.last().unwrap().exit;
+            hir::Control::Break(x) => {
+                let exit_block = state.loop_blocks[state.loop_blocks.len() - x.depth].exit;
                 generator.builder.build_unconditional_branch(exit_block);
                 generator.const_unit()
             }
-            hir::Control::Continue(_) => {
-                let head_block = state.loop_blocks.last().unwrap().head;
+            hir::Control::Continue(x) => {
+                let head_block = state.loop_blocks[state.loop_blocks.len() - x.depth].head;
                 generator.builder.build_unconditional_branch(head_block);
                 generator.const_unit()
             }
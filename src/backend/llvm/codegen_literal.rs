@@ -32,12 +32,9 @@ impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::StructLiteral {
                 let struct_ptr = generator.build_alloca(state, llvm_type);
 
                 for field in self.fields.iter() {
-                    let field_index = struct_ty.field_position(field.name).unwrap();
+                    let field_index = generator.llvm_field_index(ty, struct_ty.field_position(field.name).unwrap());
 
-                    let field_ptr = generator
-                        .builder
-                        .build_struct_gep(struct_ptr, field_index as u32, "")
-                        .unwrap();
+                    let field_ptr = generator.builder.build_struct_gep(struct_ptr, field_index, "").unwrap();
 
                     let value = field.value.codegen(generator, state);
 
@@ -78,7 +75,8 @@ impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::TupleLiteral {
         let tuple_ptr = generator.build_alloca(state, llvm_type);
 
         for (i, value) in values.iter().enumerate() {
-            let ptr = generator.builder.build_struct_gep(tuple_ptr, i as u32, "").unwrap();
+            let field_index = generator.llvm_field_index(&ty, i);
+            let ptr = generator.builder.build_struct_gep(tuple_ptr, field_index, "").unwrap();
 
             generator.build_store(ptr, *value);
         }
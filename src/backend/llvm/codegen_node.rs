@@ -148,11 +148,13 @@ impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::Assign {
 impl<'g, 'ctx> Codegen<'g, 'ctx> for hir::MemberAccess {
     fn codegen(&self, generator: &mut Generator<'g, 'ctx>, state: &mut FunctionState<'ctx>) -> BasicValueEnum<'ctx> {
         let value = self.value.codegen(generator, state);
+        let value_ty = self.value.ty().normalize(generator.tcx);
+
         generator.gep_struct(
             value,
-            self.member_index,
+            generator.llvm_field_index(&value_ty, self.member_index as usize),
             &self.member_name,
-            self.value.ty().normalize(generator.tcx).is_fat_pointer(),
+            value_ty.is_fat_pointer(),
         )
     }
 }
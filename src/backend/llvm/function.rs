@@ -1,6 +1,7 @@
 use super::{
     abi::AbiType,
     codegen::{FunctionState, Generator},
+    mangle::mangle,
     ty::IntoLlvmType,
     CallingConv,
 };
@@ -33,8 +34,11 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
                             self.builder.get_insert_block()
                         };
 
-                        let function_value =
-                            self.declare_fn_sig(&function_type, function.qualified_name, Some(Linkage::Private));
+                        let function_value = self.declare_fn_sig(
+                            &function_type,
+                            mangle(&function.qualified_name),
+                            Some(Linkage::Private),
+                        );
 
                         self.functions.insert(function.id, function_value);
 
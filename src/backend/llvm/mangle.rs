@@ -0,0 +1,88 @@
+// Symbol names of functions are mangled using the following scheme:
+//
+//     <symbol>  ::= "_CH" <segment>+ "E"
+//     <segment> ::= <length> <name>
+//
+// Where each segment is a part of the function's qualified name (separated by `.`),
+// and <length> is the segment's length in bytes, written in decimal.
+// Since every segment is length-prefixed, two different qualified names can never produce the same symbol.
+//
+// For example, `std.c.printf` is mangled to `_CH3std1c6printfE`.
+
+const PREFIX: &str = "_CH";
+const SUFFIX: &str = "E";
+
+pub fn mangle(qualified_name: &str) -> String {
+    let mut symbol = String::from(PREFIX);
+
+    for segment in qualified_name.split('.') {
+        symbol.push_str(&segment.len().to_string());
+        symbol.push_str(segment);
+    }
+
+    symbol.push_str(SUFFIX);
+
+    symbol
+}
+
+// Returns the qualified name encoded in a mangled symbol, or None if the symbol is malformed
+pub fn demangle(symbol: &str) -> Option<String> {
+    let mut rest = symbol.strip_prefix(PREFIX)?.strip_suffix(SUFFIX)?;
+    let mut segments = vec![];
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+
+        if digits == 0 {
+            return None;
+        }
+
+        let len: usize = rest[..digits].parse().ok()?;
+        let segment = rest.get(digits..digits + len)?;
+
+        segments.push(segment);
+        rest = &rest[digits + len..];
+    }
+
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{demangle, mangle};
+
+    #[test]
+    fn mangles_every_segment() {
+        assert_eq!(mangle("std.c.printf"), "_CH3std1c6printfE");
+    }
+
+    #[test]
+    fn round_trips() {
+        for qualified_name in [
+            "main",
+            "std.c.printf",
+            "demo.builtin_functions.add",
+            "demo.print@1",
+            "demo.print@12",
+        ] {
+            assert_eq!(demangle(&mangle(qualified_name)).as_deref(), Some(qualified_name));
+        }
+    }
+
+    #[test]
+    fn distinct_names_never_collide() {
+        assert_ne!(mangle("a.bc"), mangle("ab.c"));
+        assert_ne!(mangle("demo.print@1"), mangle("demo.print@12"));
+    }
+
+    #[test]
+    fn rejects_malformed_symbols() {
+        for symbol in ["", "main", "_CHE", "_CH4mainX", "_CH4main", "_CHmainE", "_CH9mainE"] {
+            assert_eq!(demangle(symbol), None, "`{}` should be rejected", symbol);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use inkwell::{module::Module, targets::TargetData, values::FunctionValue};
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    path::Path,
+};
+
+// Writes a map of every symbol defined in the module, with its section and size, sorted by section and name.
+// Global sizes are computed from the target's data layout. Machine code size is only known after
+// instruction selection, so a function's size is estimated by its number of LLVM instructions.
+pub(super) fn write_map_file(module: &Module, target_data: &TargetData, path: &Path) -> io::Result<()> {
+    let mut entries = vec![];
+
+    for function in module.get_functions() {
+        // functions without a body are declarations of external symbols
+        if function.count_basic_blocks() == 0 {
+            continue;
+        }
+
+        entries.push(MapEntry {
+            section: ".text",
+            name: function.get_name().to_string_lossy().into_owned(),
+            size: Size::Instructions(count_instructions(function)),
+        });
+    }
+
+    for global in module.get_globals() {
+        let initializer = match global.get_initializer() {
+            Some(initializer) => initializer,
+            None => continue,
+        };
+
+        entries.push(MapEntry {
+            section: if global.is_constant() { ".rodata" } else { ".data" },
+            name: global.get_name().to_string_lossy().into_owned(),
+            size: Size::Bytes(target_data.get_abi_size(&initializer.get_type())),
+        });
+    }
+
+    entries.sort_by(|a, b| (a.section, &a.name).cmp(&(b.section, &b.name)));
+
+    let mut file = std::fs::File::create(path)?;
+
+    writeln!(file, "{:<10}{:<16}{}", "section", "size", "symbol")?;
+
+    for entry in entries.iter() {
+        writeln!(file, "{:<10}{:<16}{}", entry.section, entry.size.to_string(), entry.name)?;
+    }
+
+    let (instructions, bytes) = entries.iter().fold((0, 0), |(instructions, bytes), entry| match entry.size {
+        Size::Instructions(n) => (instructions + n, bytes),
+        Size::Bytes(n) => (instructions, bytes + n),
+    });
+
+    writeln!(file)?;
+    writeln!(file, "total code: {}", Size::Instructions(instructions))?;
+    writeln!(file, "total data: {}", Size::Bytes(bytes))
+}
+
+struct MapEntry {
+    section: &'static str,
+    name: String,
+    size: Size,
+}
+
+enum Size {
+    Instructions(u64),
+    Bytes(u64),
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Size::Instructions(n) => write!(f, "~{} insts", n),
+            Size::Bytes(n) => write!(f, "{} bytes", n),
+        }
+    }
+}
+
+pub(super) fn count_instructions(function: FunctionValue) -> u64 {
+    let mut count = 0;
+
+    for block in function.get_basic_blocks() {
+        let mut instruction = block.get_first_instruction();
+
+        while let Some(current) = instruction {
+            count += 1;
+            instruction = current.get_next_instruction();
+        }
+    }
+
+    count
+}
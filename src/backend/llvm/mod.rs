@@ -9,9 +9,13 @@ mod codegen_static;
 mod conditional;
 mod function;
 mod intrinsics;
+pub mod mangle;
+mod map;
 mod panic;
 mod runtime_check;
 mod start;
+mod stats;
+mod test_runner;
 mod traits;
 mod ty;
 mod util;
@@ -22,6 +26,7 @@ use crate::{
         build_options::{self, BuildOptions},
         target::{Arch, Os, TargetMetrics},
     },
+    error::diagnostic::Diagnostic,
     hir,
     infer::type_ctx::TypeCtx,
     time,
@@ -46,7 +51,17 @@ use ustr::UstrMap;
 #[cfg(windows)]
 use super::microsoft_craziness;
 
-pub fn codegen<'w>(workspace: &Workspace, tcx: &TypeCtx, cache: &hir::Cache) -> PathBuf {
+// The LLVM version the backend is built against, as selected by inkwell's `llvm12-0` feature
+pub const LLVM_VERSION: &str = "12.0";
+
+pub fn host_target_triple() -> String {
+    TargetMachine::get_default_triple()
+        .as_str()
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn codegen<'w>(workspace: &Workspace, tcx: &TypeCtx, cache: &hir::Cache) -> Result<PathBuf, Diagnostic> {
     let context = Context::create();
     let module = context.create_module(
         workspace
@@ -143,7 +158,7 @@ fn build_executable(
     target_metrics: &TargetMetrics,
     module: &Module,
     extern_libraries: &HashSet<ast::ExternLibrary>,
-) -> PathBuf {
+) -> Result<PathBuf, Diagnostic> {
     let output_path = build_options
         .output_file
         .as_ref()
@@ -157,6 +172,28 @@ fn build_executable(
         module.print_to_file(output_path.with_extension("ll")).unwrap();
     }
 
+    if build_options.codegen_options.emit_asm() {
+        let asm_file = output_path.with_extension("s");
+
+        time! { build_options.emit_times, "write asm",
+            target_machine
+                .write_to_file(&module, FileType::Assembly, &asm_file)
+                .map_err(|err| {
+                    Diagnostic::error().with_message(format!("couldn't write `{}`: {}", asm_file.display(), err))
+                })?
+        };
+    }
+
+    if build_options.codegen_options.emit_map() {
+        let map_file = output_path.with_extension("map");
+
+        time! { build_options.emit_times, "write map",
+            map::write_map_file(module, &target_machine.get_target_data(), &map_file).map_err(|err| {
+                Diagnostic::error().with_message(format!("couldn't write `{}`: {}", map_file.display(), err))
+            })?
+        };
+    }
+
     let object_file = if target_metrics.os == Os::Windows {
         output_path.with_extension("obj")
     } else {
@@ -175,16 +212,27 @@ fn build_executable(
             .unwrap()
     };
 
+    if build_options.codegen_options.print_stats() {
+        let object_file_size = std::fs::metadata(&object_file).ok().map(|metadata| metadata.len());
+        stats::print_stats(module, &target_machine.get_target_data(), object_file_size);
+    }
+
+    // without an entry point there is nothing to link, so the object file is the final output
+    if build_options.no_entry || build_options.codegen_options.emit_obj() {
+        return Ok(object_file.absolutize().unwrap().to_path_buf());
+    }
+
     time! { build_options.emit_times, "link",
-        link(target_metrics, &output_file, &object_file, &extern_libraries)
+        link(build_options, target_metrics, &output_file, &object_file, &extern_libraries)
     }
 
     let _ = std::fs::remove_file(object_file);
 
-    output_file.absolutize().unwrap().to_path_buf()
+    Ok(output_file.absolutize().unwrap().to_path_buf())
 }
 
 fn link(
+    build_options: &BuildOptions,
     target_metrics: &TargetMetrics,
     executable_file: &PathBuf,
     object_file: &PathBuf,
@@ -194,6 +242,7 @@ fn link(
         Arch::Amd64 => match target_metrics.os {
             Os::Windows => vec!["/machine:x64"],
             Os::Linux | Os::FreeBSD => vec!["-arch x86-64"],
+            Os::Darwin => vec!["-arch", "x86_64"],
             _ => vec![],
         },
         Arch::_386 => match target_metrics.os {
@@ -266,7 +315,9 @@ fn link(
             .arg("/subsystem:CONSOLE")
             .args(lib_paths.iter().map(|path| format!("/libpath:{}", path)))
             .arg(object_file.to_str().unwrap())
+            .args(build_options.link_objects.iter().map(|path| path.to_str().unwrap()))
             .args(libs)
+            .args(build_options.link_libraries.iter().map(|lib| format!("{}.lib", lib)))
             .args(link_flags)
             .execute_output()
             .unwrap();
@@ -276,11 +327,13 @@ fn link(
     Command::new("clang")
         .arg("-Wno-unused-command-line-argument")
         .arg(object_file.to_str().unwrap())
+        .args(build_options.link_objects.iter().map(|path| path.to_str().unwrap()))
         .arg(format!("-o{}", executable_file.to_str().unwrap()))
         .args(lib_paths.iter().map(|path| format!("-L{}", path)))
         .arg("-lc")
         .arg("-lm")
         .args(libs.iter().map(|path| format!("-l:{}", path)))
+        .args(build_options.link_libraries.iter().map(|lib| format!("-l{}", lib)))
         .arg("-no-pie")
         .args(link_flags)
         .execute_output()
@@ -1,10 +1,109 @@
-use super::codegen::{FunctionState, Generator};
-use crate::{infer::normalize::Normalize, types::*};
-use inkwell::{module::Linkage, values::BasicValue, AddressSpace};
+use super::{
+    codegen::{Decl, FunctionState, Generator},
+    ty::IntoLlvmType,
+};
+use crate::{ast, infer::normalize::Normalize, types::*, workspace::BindingInfoKind};
+use inkwell::{
+    basic_block::BasicBlock,
+    module::Linkage,
+    values::{BasicValue, BasicValueEnum, FunctionValue},
+    AddressSpace, IntPredicate,
+};
 use ustr::ustr;
 
 impl<'g, 'ctx> Generator<'g, 'ctx> {
+    // When compiling without an entry point, there is no `main` to pull bindings into codegen.
+    // Instead, every public function and static variable in the root module is generated and exported.
+    // Exported symbols use their source names rather than their mangled names, so C code can link against them
+    pub(super) fn gen_exported_bindings(&mut self) {
+        let root_module_id = self.workspace.root_module_id;
+
+        let mut ids = self
+            .cache
+            .bindings
+            .values()
+            .filter(|binding| binding.module_id == root_module_id)
+            .map(|binding| binding.id)
+            .filter(|&id| {
+                let binding_info = self.workspace.binding_infos.get(id).unwrap();
+
+                binding_info.vis == ast::Vis::Public
+                    && binding_info.scope_level.is_global()
+                    && matches!(
+                        binding_info.kind,
+                        BindingInfoKind::Function | BindingInfoKind::LetStatic
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        ids.sort();
+
+        for id in ids {
+            let name = self.workspace.binding_infos.get(id).unwrap().name;
+
+            match self.gen_top_level_binding(id) {
+                Decl::Function(function) => {
+                    function.set_linkage(Linkage::External);
+                    function.as_global_value().as_pointer_value().set_name(&name);
+                }
+                Decl::Global(global) => {
+                    global.set_linkage(Linkage::External);
+                    global.as_pointer_value().set_name(&name);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    // emits bindings annotated with `@export` as constant globals, under their exported name
+    pub(super) fn gen_exported_symbols(&mut self) {
+        let cache = self.cache;
+
+        for exported in cache.exported_bindings.iter() {
+            if let Decl::Global(global) = self.gen_top_level_binding(exported.id) {
+                global.set_linkage(Linkage::External);
+                global.set_constant(true);
+                global.as_pointer_value().set_name(&exported.name);
+            }
+        }
+    }
+
     pub(super) fn gen_start_function(&mut self) {
+        let (function, mut state, entry_block) = self.begin_start_function();
+
+        // Codegen the entry point function
+        let entry_point_function = self.cache.entry_point_function().unwrap();
+
+        self.gen_function(entry_point_function.id, None);
+
+        // Call the entry point function
+        let entry_point_function_value = *self.functions.get(&entry_point_function.id).unwrap();
+
+        let entry_point_function_type = entry_point_function.ty.normalize(self.tcx).into_function();
+
+        // The entry point function may take the command-line arguments as its only parameter
+        let args = if entry_point_function_type.params.is_empty() {
+            vec![]
+        } else {
+            vec![self.gen_entry_point_args(&mut state, function)]
+        };
+
+        self.gen_function_call(
+            &mut state,
+            entry_point_function_value,
+            &entry_point_function_type,
+            args,
+            &entry_point_function_type.return_type,
+        );
+
+        // TODO: if this is DLL Main, return 1 instead of 0
+
+        self.end_start_function(&mut state, entry_block);
+    }
+
+    // Adds the platform's startup function, and starts its entry block.
+    // Returns the function, its state and its entry block
+    pub(super) fn begin_start_function(&mut self) -> (FunctionValue<'ctx>, FunctionState<'ctx>, BasicBlock<'ctx>) {
         let name = self.workspace.build_options.start_function_name().unwrap();
 
         let linkage = Some(Linkage::External);
@@ -100,34 +199,105 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
 
         self.startup_function_state = Some(state.clone());
 
-        // Codegen the entry point function
-        let entry_point_function = self.cache.entry_point_function().unwrap();
+        (function, state, entry_block)
+    }
 
-        self.gen_function(entry_point_function.id, None);
+    // Returns 0 from the startup function, unless it already returned, and branches from its declarations to its entry
+    pub(super) fn end_start_function(&mut self, state: &mut FunctionState<'ctx>, entry_block: BasicBlock<'ctx>) {
+        if self.current_block().get_terminator().is_none() {
+            self.builder.build_return(Some(&self.context.i32_type().const_zero()));
+        }
 
-        // Call the entry point function
-        let entry_point_function_value = *self.functions.get(&entry_point_function.id).unwrap();
+        let decl_block = state.decl_block;
+        self.start_block(state, decl_block);
 
-        let entry_point_function_type = entry_point_function.ty.normalize(self.tcx).into_function();
+        state.pop_scope();
 
-        self.gen_function_call(
-            &mut state,
-            entry_point_function_value,
-            &entry_point_function_type,
-            vec![],
-            &entry_point_function_type.return_type,
+        self.builder.build_unconditional_branch(entry_block);
+    }
+
+    // Builds a `*[]*str` from the startup function's `argc` and `argv`.
+    // Each argument's length is computed with libc's `strlen`
+    fn gen_entry_point_args(
+        &mut self,
+        state: &mut FunctionState<'ctx>,
+        function: FunctionValue<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        let argc = function.get_nth_param(0).unwrap().into_int_value();
+        let argv = function.get_nth_param(1).unwrap().into_pointer_value();
+
+        let raw_pointer_type = self.raw_pointer_type();
+
+        let strlen = self.get_or_add_function(
+            "strlen",
+            self.ptr_sized_int_type.fn_type(&[raw_pointer_type.into()], false),
+            None,
         );
 
-        // TODO: if this is DLL Main, return 1 instead of 0
+        let len = self
+            .builder
+            .build_int_z_extend(argc, self.ptr_sized_int_type, "args_len");
 
-        if self.current_block().get_terminator().is_none() {
-            self.builder.build_return(Some(&self.context.i32_type().const_zero()));
-        }
+        let str_type = Type::str_pointer().llvm_type(self);
+        let args_ptr = self.builder.build_array_alloca(str_type, len, "args");
 
-        self.start_block(&mut state, decl_block);
+        let loop_head = self.append_basic_block(state, "args_head");
+        let loop_body = self.append_basic_block(state, "args_body");
+        let loop_exit = self.append_basic_block(state, "args_exit");
 
-        state.pop_scope();
+        let it = self.build_alloca(state, self.ptr_sized_int_type.into());
+        self.build_store(it, self.ptr_sized_int_type.const_zero().into());
 
-        self.builder.build_unconditional_branch(entry_block);
+        self.builder.build_unconditional_branch(loop_head);
+        self.start_block(state, loop_head);
+
+        let it_value = self.build_load(it, "").into_int_value();
+
+        let condition = self.builder.build_int_compare(IntPredicate::ULT, it_value, len, "");
+
+        self.builder.build_conditional_branch(condition, loop_body, loop_exit);
+
+        self.start_block(state, loop_body);
+
+        let arg = unsafe { self.builder.build_in_bounds_gep(argv, &[it_value], "") };
+        let arg = self.build_load(arg, "").into_pointer_value();
+
+        let arg_len = self
+            .builder
+            .build_call(strlen, &[arg.into()], "")
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        let dst = unsafe { self.builder.build_in_bounds_gep(args_ptr, &[it_value], "") };
+
+        let data_ptr = self.builder.build_struct_gep(dst, 0, "str_ptr").unwrap();
+        self.build_store(data_ptr, arg.into());
+
+        let len_ptr = self.builder.build_struct_gep(dst, 1, "str_len").unwrap();
+        self.build_store(len_ptr, arg_len);
+
+        let next_it = self
+            .builder
+            .build_int_add(it_value, self.ptr_sized_int_type.const_int(1, false), "");
+
+        self.build_store(it, next_it.into());
+
+        self.builder.build_unconditional_branch(loop_head);
+
+        self.start_block(state, loop_exit);
+
+        let slice_type = Type::slice_pointer(Type::str_pointer(), false).llvm_type(self);
+        let slice_ptr = self.build_alloca(state, slice_type);
+
+        self.build_slice(
+            slice_ptr,
+            args_ptr,
+            self.ptr_sized_int_type.const_zero(),
+            len,
+            &Type::str_pointer(),
+        );
+
+        self.build_load(slice_ptr, "")
     }
 }
@@ -0,0 +1,39 @@
+use super::map::count_instructions;
+use colored::Colorize;
+use inkwell::{module::Module, targets::TargetData};
+
+// Printed to stderr after codegen, so that it doesn't interfere with the program's output in Run mode.
+// The object size includes the object file's headers and symbol table, on top of its code and data
+pub(super) fn print_stats(module: &Module, target_data: &TargetData, object_file_size: Option<u64>) {
+    let mut functions = 0;
+    let mut basic_blocks = 0;
+    let mut instructions = 0;
+
+    for function in module.get_functions() {
+        // functions without a body are declarations of external symbols
+        if function.count_basic_blocks() == 0 {
+            continue;
+        }
+
+        functions += 1;
+        basic_blocks += function.count_basic_blocks() as u64;
+        instructions += count_instructions(function);
+    }
+
+    let (globals, data_size) = module
+        .get_globals()
+        .filter_map(|global| global.get_initializer())
+        .fold((0, 0), |(globals, size), initializer| {
+            (globals + 1, size + target_data.get_abi_size(&initializer.get_type()))
+        });
+
+    eprintln!("{:<16}{:>10}", "functions".bold(), functions);
+    eprintln!("{:<16}{:>10}", "basic blocks".bold(), basic_blocks);
+    eprintln!("{:<16}{:>10}", "instructions".bold(), instructions);
+    eprintln!("{:<16}{:>10}", "globals".bold(), globals);
+    eprintln!("{:<16}{:>10} bytes", "data size".bold(), data_size);
+
+    if let Some(size) = object_file_size {
+        eprintln!("{:<16}{:>10} bytes", "object size".bold(), size);
+    }
+}
@@ -0,0 +1,226 @@
+use super::codegen::{FunctionState, Generator};
+use crate::{common::target::Os, hir, infer::normalize::Normalize};
+use inkwell::{
+    types::FunctionType,
+    values::{BasicMetadataValueEnum, BasicValueEnum, CallableValue, FunctionValue, IntValue},
+    AddressSpace, IntPredicate,
+};
+
+impl<'g, 'ctx> Generator<'g, 'ctx> {
+    // Generates a startup function which runs every function annotated with `@test`, and reports whether each passed.
+    // On unix-like targets, each test runs in a forked child process, so a test that panics or aborts only fails itself.
+    // On Windows, tests run in the same process, so a failing test ends the run.
+    // The startup function returns 1 if any test failed
+    pub(super) fn gen_test_runner_function(&mut self) {
+        let (_, mut state, entry_block) = self.begin_start_function();
+
+        let cache = self.cache;
+
+        // The tests are generated before the runner declares any libc function,
+        // so that the program's own declarations of these functions come first
+        let tests = cache
+            .test_functions
+            .iter()
+            .map(|&id| (cache.functions.get(id).unwrap(), self.gen_function(id, None)))
+            .collect::<Vec<_>>();
+
+        let i32_type = self.context.i32_type();
+
+        let failed_count = self.build_alloca(&state, i32_type.into());
+        self.build_store(failed_count, i32_type.const_zero().into());
+
+        let ok = self.builder.build_global_string_ptr("ok", "test_ok").as_pointer_value();
+        let failed = self
+            .builder
+            .build_global_string_ptr("FAILED", "test_failed")
+            .as_pointer_value();
+
+        self.build_printf(
+            "running %u test(s)\n",
+            &[i32_type.const_int(tests.len() as u64, false).into()],
+        );
+
+        for (function, function_value) in tests {
+            let passed = if self.target_metrics.os == Os::Windows {
+                self.gen_test_call(&mut state, function, function_value);
+                self.context.bool_type().const_int(1, false)
+            } else {
+                self.gen_forked_test_call(&mut state, function, function_value)
+            };
+
+            let name = self
+                .builder
+                .build_global_string_ptr(&function.qualified_name, "test_name")
+                .as_pointer_value();
+
+            let result = self.builder.build_select(passed, ok, failed, "");
+
+            self.build_printf("test %s ... %s\n", &[name.into(), result.into()]);
+
+            let test_failed = self.builder.build_not(passed, "");
+            let test_failed = self.builder.build_int_z_extend(test_failed, i32_type, "");
+
+            let count = self.build_load(failed_count, "").into_int_value();
+            let count = self.builder.build_int_add(count, test_failed, "");
+            self.build_store(failed_count, count.into());
+        }
+
+        let failed_count = self.build_load(failed_count, "").into_int_value();
+        let passed_count = self.builder.build_int_sub(
+            i32_type.const_int(cache.test_functions.len() as u64, false),
+            failed_count,
+            "",
+        );
+
+        let all_passed = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, failed_count, i32_type.const_zero(), "");
+
+        let result = self.builder.build_select(all_passed, ok, failed, "");
+
+        self.build_printf(
+            "test result: %s. %u passed; %u failed\n",
+            &[result.into(), passed_count.into(), failed_count.into()],
+        );
+
+        let exit_code = self
+            .builder
+            .build_int_z_extend(self.builder.build_not(all_passed, ""), i32_type, "");
+
+        self.builder.build_return(Some(&exit_code));
+
+        self.end_start_function(&mut state, entry_block);
+    }
+
+    // Runs a test in a forked child process, and returns whether the child exited successfully
+    fn gen_forked_test_call(
+        &mut self,
+        state: &mut FunctionState<'ctx>,
+        function: &hir::Function,
+        function_value: FunctionValue<'ctx>,
+    ) -> IntValue<'ctx> {
+        let i32_type = self.context.i32_type();
+        let raw_pointer_type = self.raw_pointer_type();
+
+        // Buffered output is flushed before forking, so that the child doesn't write it again
+        self.build_libc_call(
+            "fflush",
+            i32_type.fn_type(&[raw_pointer_type.into()], false),
+            &[raw_pointer_type.const_null().into()],
+        );
+
+        let pid = self
+            .build_libc_call("fork", i32_type.fn_type(&[], false), &[])
+            .unwrap()
+            .into_int_value();
+
+        let child_block = self.append_basic_block(state, "test_child");
+        let parent_block = self.append_basic_block(state, "test_parent");
+
+        let is_child = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, pid, i32_type.const_zero(), "");
+
+        self.builder
+            .build_conditional_branch(is_child, child_block, parent_block);
+
+        self.start_block(state, child_block);
+
+        self.gen_test_call(state, function, function_value);
+
+        if self.current_block().get_terminator().is_none() {
+            self.build_libc_call(
+                "exit",
+                self.context.void_type().fn_type(&[i32_type.into()], false),
+                &[i32_type.const_zero().into()],
+            );
+
+            self.build_unreachable();
+        }
+
+        self.start_block(state, parent_block);
+
+        // A test fails when its child couldn't be forked or waited for, since its status is never written
+        let status = self.build_alloca(state, i32_type.into());
+        self.build_store(status, i32_type.const_int(1, false).into());
+
+        let waited_pid = self
+            .build_libc_call(
+                "waitpid",
+                i32_type.fn_type(
+                    &[
+                        i32_type.into(),
+                        i32_type.ptr_type(AddressSpace::Generic).into(),
+                        i32_type.into(),
+                    ],
+                    false,
+                ),
+                &[pid.into(), status.into(), i32_type.const_zero().into()],
+            )
+            .unwrap()
+            .into_int_value();
+
+        let status = self.build_load(status, "").into_int_value();
+
+        let waited = self.builder.build_int_compare(IntPredicate::EQ, waited_pid, pid, "");
+        let exited_successfully = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, status, i32_type.const_zero(), "");
+
+        self.builder.build_and(waited, exited_successfully, "passed")
+    }
+
+    fn gen_test_call(
+        &mut self,
+        state: &mut FunctionState<'ctx>,
+        function: &hir::Function,
+        function_value: FunctionValue<'ctx>,
+    ) {
+        let function_type = function.ty.normalize(self.tcx).into_function();
+
+        self.gen_function_call(
+            state,
+            function_value,
+            &function_type,
+            vec![],
+            &function_type.return_type,
+        );
+    }
+
+    fn build_printf(&self, format: &str, args: &[BasicMetadataValueEnum<'ctx>]) {
+        let i32_type = self.context.i32_type();
+        let format = self.builder.build_global_string_ptr(format, "").as_pointer_value();
+
+        let mut printf_args = vec![format.into()];
+        printf_args.extend_from_slice(args);
+
+        self.build_libc_call(
+            "printf",
+            i32_type.fn_type(&[self.raw_pointer_type().into()], true),
+            &printf_args,
+        );
+    }
+
+    // Calls a libc function through a pointer of the given type.
+    // The program may have already declared the function with a different, but ABI compatible, signature.
+    // For example, the std declares `fork` as returning `pid_t`, which is an `int`
+    fn build_libc_call(
+        &self,
+        name: &str,
+        function_type: FunctionType<'ctx>,
+        args: &[BasicMetadataValueEnum<'ctx>],
+    ) -> Option<BasicValueEnum<'ctx>> {
+        let function = self.get_or_add_function(name, function_type, None);
+
+        let callee = self.builder.build_pointer_cast(
+            function.as_global_value().as_pointer_value(),
+            function_type.ptr_type(AddressSpace::Generic),
+            "",
+        );
+
+        self.builder
+            .build_call(CallableValue::try_from(callee).unwrap(), args, "")
+            .try_as_basic_value()
+            .left()
+    }
+}
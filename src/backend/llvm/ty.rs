@@ -4,7 +4,7 @@ use super::{
 };
 use crate::{
     infer::{display::DisplayType, normalize::Normalize},
-    types::{size_of::SizeOf, *},
+    types::{offset_of::OffsetOf, size_of::SizeOf, *},
 };
 use inkwell::{
     types::{AnyType, BasicMetadataTypeEnum, BasicType, BasicTypeEnum, PointerType},
@@ -61,6 +61,15 @@ impl<'g, 'ctx> IntoLlvmType<'g, 'ctx> for Type {
                 .ptr_type(AddressSpace::Generic)
                 .into(),
             Type::Array(inner, size) => inner.llvm_type(generator).array_type(*size as u32).into(),
+            Type::Tuple(tys) if tys.iter().any(contains_over_aligned_type) => {
+                let struct_ty = StructType::temp(
+                    tys.iter().map(|ty| StructTypeField::temp(ty.clone())).collect(),
+                    StructTypeKind::Struct,
+                );
+
+                let fields = generator.create_struct_type_explicit_fields(&struct_ty);
+                generator.context.struct_type(&fields, true).into()
+            }
             Type::Tuple(tys) => generator
                 .context
                 .struct_type(
@@ -193,16 +202,88 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
         self.types.insert(struct_ty.id.unwrap(), struct_type.into());
 
         let fields = self.create_struct_type_fields(struct_ty);
-        struct_type.set_body(&fields, struct_ty.is_packed_struct());
+        struct_type.set_body(&fields, struct_ty.is_packed_struct() || has_explicit_layout(struct_ty));
         struct_type
     }
 
     fn create_anonymous_struct_type(&mut self, struct_ty: &StructType) -> inkwell::types::StructType<'ctx> {
         let fields = self.create_struct_type_fields(struct_ty);
-        self.context.struct_type(&fields, struct_ty.is_packed_struct())
+        self.context
+            .struct_type(&fields, struct_ty.is_packed_struct() || has_explicit_layout(struct_ty))
     }
 
     fn create_struct_type_fields(&mut self, struct_ty: &StructType) -> Vec<BasicTypeEnum<'ctx>> {
+        if has_explicit_layout(struct_ty) {
+            return self.create_struct_type_explicit_fields(struct_ty);
+        }
+
+        let mut fields = self.create_struct_type_natural_fields(struct_ty);
+
+        // an over-aligned struct is padded at its end, so that its llvm size matches its size in chili
+        if struct_ty.align.is_some() {
+            let word_size = self.target_metrics.word_size;
+            let natural_size = abi::size_of(
+                self.context.struct_type(&fields, struct_ty.is_packed_struct()).into(),
+                word_size,
+            );
+            let size = struct_ty.size_of(word_size);
+
+            if size > natural_size {
+                fields.push(self.context.i8_type().array_type((size - natural_size) as u32).into());
+            }
+        }
+
+        fields
+    }
+
+    // llvm types don't carry the alignment of over-aligned structs, so a struct containing one is laid out
+    // as a packed llvm struct with explicit padding before each field, and after the last one.
+    // the offsets are the same ones `offset_of!` and the interpreter use.
+    fn create_struct_type_explicit_fields(&mut self, struct_ty: &StructType) -> Vec<BasicTypeEnum<'ctx>> {
+        let word_size = self.target_metrics.word_size;
+        let mut fields = vec![];
+        let mut offset = 0;
+
+        for (index, field) in struct_ty.fields.iter().enumerate() {
+            let field_offset = struct_ty.offset_of(index, word_size);
+
+            fields.push(self.padding_type(field_offset - offset));
+            fields.push(field.ty.llvm_type(self));
+
+            offset = field_offset + field.ty.size_of(word_size);
+        }
+
+        let size = struct_ty.size_of(word_size);
+
+        if size > offset {
+            fields.push(self.padding_type(size - offset));
+        }
+
+        fields
+    }
+
+    fn padding_type(&self, size: usize) -> BasicTypeEnum<'ctx> {
+        self.context.i8_type().array_type(size as u32).into()
+    }
+
+    /// Returns the index of a struct's or tuple's field in its llvm struct type.
+    /// The type may also be a pointer to the struct or tuple.
+    pub(super) fn llvm_field_index(&self, ty: &Type, index: usize) -> u32 {
+        let is_explicit = match ty {
+            Type::Pointer(inner, _) => return self.llvm_field_index(inner, index),
+            Type::Struct(struct_ty) => has_explicit_layout(struct_ty),
+            Type::Tuple(tys) => tys.iter().any(contains_over_aligned_type),
+            _ => false,
+        };
+
+        if is_explicit {
+            (index * 2 + 1) as u32
+        } else {
+            index as u32
+        }
+    }
+
+    fn create_struct_type_natural_fields(&mut self, struct_ty: &StructType) -> Vec<BasicTypeEnum<'ctx>> {
         if struct_ty.fields.is_empty() {
             vec![]
         } else if struct_ty.is_union() {
@@ -234,3 +315,27 @@ impl<'g, 'ctx> Generator<'g, 'ctx> {
         }
     }
 }
+
+fn has_explicit_layout(struct_ty: &StructType) -> bool {
+    struct_ty.is_struct()
+        && struct_ty
+            .fields
+            .iter()
+            .any(|field| contains_over_aligned_type(&field.ty))
+}
+
+/// Returns true if the type contains an over-aligned struct by value.
+pub(super) fn contains_over_aligned_type(ty: &Type) -> bool {
+    match ty {
+        Type::Struct(struct_ty) => {
+            struct_ty.align.is_some()
+                || struct_ty
+                    .fields
+                    .iter()
+                    .any(|field| contains_over_aligned_type(&field.ty))
+        }
+        Type::Array(inner, _) => contains_over_aligned_type(inner),
+        Type::Tuple(tys) => tys.iter().any(contains_over_aligned_type),
+        _ => false,
+    }
+}
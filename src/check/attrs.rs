@@ -1,18 +1,25 @@
 use super::{env::Env, Check, CheckResult, CheckSess};
 use crate::{
-    ast,
+    ast::{self, pat::Pat},
     common::path::RelativeTo,
     error::{
         diagnostic::{Diagnostic, Label},
-        DiagnosticResult,
+        DiagnosticResult, TypeError,
     },
     hir::{
+        self,
         attrs::{Attr, AttrKind, Attrs},
         const_value::ConstValue,
     },
-    infer::{display::OrReportErr, unify::UnifyType},
-    types::TypeId,
+    infer::{
+        display::{DisplayType, OrReportErr},
+        normalize::Normalize,
+        unify::UnifyType,
+    },
+    span::Span,
+    types::{StructType, Type, TypeId},
 };
+use ustr::Ustr;
 
 impl<'s> CheckSess<'s> {
     pub(super) fn check_attrs(&mut self, attrs: &[ast::Attr], env: &mut Env) -> CheckResult<Attrs> {
@@ -78,8 +85,13 @@ impl<'s> CheckSess<'s> {
 
     fn get_attr_expected_type(&self, kind: AttrKind) -> TypeId {
         match kind {
-            AttrKind::Intrinsic | AttrKind::TrackCaller => self.tcx.common_types.unit,
-            AttrKind::Lib | AttrKind::Dylib | AttrKind::LinkName => self.tcx.common_types.str_pointer,
+            AttrKind::Intrinsic | AttrKind::TrackCaller | AttrKind::AllowRedundantCast | AttrKind::Test => {
+                self.tcx.common_types.unit
+            }
+            AttrKind::Lib | AttrKind::Dylib | AttrKind::LinkName | AttrKind::Export => {
+                self.tcx.common_types.str_pointer
+            }
+            AttrKind::Align => self.tcx.common_types.uint,
         }
     }
 
@@ -109,16 +121,175 @@ impl<'s> CheckSess<'s> {
                         ))
                     }
                 },
-                AttrKind::TrackCaller => match &binding.kind {
+                AttrKind::TrackCaller | AttrKind::Test => match &binding.kind {
                     ast::BindingKind::Function { .. } => (),
                     _ => return Err(invalid_attr_use(attr, "can only be used on functions")),
                 },
+                AttrKind::Align => match &binding.kind {
+                    ast::BindingKind::Type { .. } | ast::BindingKind::Let { pat: Pat::Name(_), .. } => (),
+                    _ => {
+                        return Err(invalid_attr_use(
+                            attr,
+                            "can only be used on struct types and named variables",
+                        ))
+                    }
+                },
+                AttrKind::AllowRedundantCast => match &binding.kind {
+                    ast::BindingKind::Let { .. } | ast::BindingKind::Function { .. } => (),
+                    _ => return Err(invalid_attr_use(attr, "can only be used on variables and functions")),
+                },
+                AttrKind::Export => match &binding.kind {
+                    ast::BindingKind::Let { pat: Pat::Name(_), .. } => (),
+                    _ => return Err(invalid_attr_use(attr, "can only be used on named variables")),
+                },
             }
         }
 
         Ok(())
     }
 
+    pub(super) fn validate_align_attr(&self, attr: &Attr) -> DiagnosticResult<usize> {
+        let align = match &attr.value {
+            ConstValue::Int(v) => *v,
+            _ => unreachable!("got {:?}", attr.value),
+        };
+
+        if align <= 0 || align & (align - 1) != 0 {
+            return Err(Diagnostic::error()
+                .with_message(format!("alignment must be a power of two, but found {}", align))
+                .with_label(Label::primary(attr.span, "not a power of two")));
+        }
+
+        Ok(align as usize)
+    }
+
+    pub(super) fn apply_align_attr(&mut self, attr: &Attr, type_node: &hir::Node, span: Span) -> DiagnosticResult<()> {
+        let align = self.validate_align_attr(attr)?;
+
+        let ty = match type_node.as_const_value() {
+            Some(ConstValue::Type(ty)) => *ty,
+            _ => return Ok(()),
+        };
+
+        match ty.normalize(&self.tcx) {
+            Type::Struct(struct_type) => {
+                self.tcx.bind_ty(
+                    ty,
+                    Type::Struct(StructType {
+                        align: Some(align),
+                        ..struct_type
+                    }),
+                );
+
+                Ok(())
+            }
+            other => Err(TypeError::expected(span, other.display(&self.tcx), "a struct type")),
+        }
+    }
+
+    // Validates that an `@export`ed binding is global, immutable and compile-time known,
+    // and that its value has a C-compatible plain-old-data type
+    pub(super) fn validate_export_attr(
+        &self,
+        env: &Env,
+        attr: &Attr,
+        pat: &Pat,
+        value_node: &hir::Node,
+    ) -> DiagnosticResult<Ustr> {
+        fn is_plain_old_data(ty: &Type) -> bool {
+            match ty {
+                Type::Bool | Type::Int(_) | Type::Uint(_) | Type::Float(_) => true,
+                Type::Array(inner, _) => is_plain_old_data(inner),
+                Type::Tuple(elements) => elements.iter().all(is_plain_old_data),
+                Type::Struct(struct_type) => struct_type.fields.iter().all(|field| is_plain_old_data(&field.ty)),
+                _ => false,
+            }
+        }
+
+        let name = *attr.value.as_str().unwrap();
+
+        if name.is_empty() {
+            return Err(Diagnostic::error()
+                .with_message("exported symbol name cannot be empty")
+                .with_label(Label::primary(attr.span, "empty name")));
+        }
+
+        if !env.scope_level().is_global() {
+            return Err(Diagnostic::error()
+                .with_message("only global variables can be exported")
+                .with_label(Label::primary(attr.span, "not a global variable")));
+        }
+
+        if pat.is_mutable() {
+            return Err(Diagnostic::error()
+                .with_message("exported variables must be immutable")
+                .with_label(Label::primary(pat.span(), "variable is mutable")));
+        }
+
+        if !value_node.is_const() {
+            return Err(Diagnostic::error()
+                .with_message("exported value must be compile-time known")
+                .with_label(Label::primary(value_node.span(), "value is not compile-time known"))
+                .with_note("to compute the value at compile-time, use a `comptime` block"));
+        }
+
+        let ty = value_node.ty().normalize(&self.tcx);
+
+        if !is_plain_old_data(&ty) {
+            return Err(Diagnostic::error()
+                .with_message(format!(
+                    "cannot export a value of type `{}`, which is not plain-old-data",
+                    ty.display(&self.tcx)
+                ))
+                .with_label(Label::primary(value_node.span(), "cannot be exported"))
+                .with_note("only booleans, numbers, and arrays, tuples and structs of them can be exported"));
+        }
+
+        if let Some(exported) = self
+            .cache
+            .exported_bindings
+            .iter()
+            .find(|exported| exported.name == name)
+        {
+            return Err(Diagnostic::error()
+                .with_message(format!("symbol `{}` has already been exported", name))
+                .with_label(Label::primary(attr.span, "duplicate export"))
+                .with_label(Label::secondary(exported.span, "first exported here")));
+        }
+
+        Ok(name)
+    }
+
+    // Validates that a `@test` function is global and has type fn() -> (), so that it can be called on its own
+    pub(super) fn validate_test_attr(
+        &self,
+        env: &Env,
+        attr: &Attr,
+        function_node: &hir::Node,
+    ) -> DiagnosticResult<hir::FunctionId> {
+        if !env.scope_level().is_global() {
+            return Err(Diagnostic::error()
+                .with_message("only global functions can be tests")
+                .with_label(Label::primary(attr.span, "not a global function")));
+        }
+
+        let ty = function_node.ty().normalize(&self.tcx).into_function();
+
+        if !(ty.return_type.is_unit() || ty.return_type.is_never()) || !ty.params.is_empty() || ty.has_c_varargs() {
+            return Err(Diagnostic::error()
+                .with_message(format!(
+                    "test function has type `{}`, expected `fn() -> ()`",
+                    ty.display(&self.tcx)
+                ))
+                .with_label(Label::primary(function_node.span(), "invalid test function type")));
+        }
+
+        match function_node.as_const_value() {
+            Some(ConstValue::Function(f)) => Ok(f.id),
+            _ => unreachable!(),
+        }
+    }
+
     pub(super) fn maybe_get_extern_lib_attr(
         &self,
         env: &Env,
@@ -3,6 +3,7 @@ use crate::{
     error::diagnostic::{Diagnostic, Label},
     infer::{display::DisplayType, normalize::Normalize},
     span::Span,
+    types::Type,
 };
 
 impl<'s> CheckSess<'s> {
@@ -11,19 +12,23 @@ impl<'s> CheckSess<'s> {
             if let Some(function) = self.cache.entry_point_function() {
                 let ty = function.ty.normalize(&self.tcx).into_function();
 
-                // Validate its type is fn() -> ()
-                if !(ty.return_type.is_unit() || ty.return_type.is_never())
-                    || !ty.params.is_empty()
-                    || ty.has_c_varargs()
-                {
+                // Validate its type is fn() -> (), or fn(args: *[]*str) -> ()
+                let params_are_valid = match ty.params.as_slice() {
+                    [] => true,
+                    [args] => args.ty == Type::slice_pointer(Type::str_pointer(), false),
+                    _ => false,
+                };
+
+                if !(ty.return_type.is_unit() || ty.return_type.is_never()) || !params_are_valid || ty.has_c_varargs() {
                     self.workspace.diagnostics.push(
                         Diagnostic::error()
                             .with_message(format!(
-                                "entry point function `{}` has type `{}`, expected `fn() -> ()`",
+                                "entry point function `{}` has type `{}`, expected `fn() -> ()` or `fn(*[]*str) -> ()`",
                                 function.name,
                                 ty.display(&self.tcx)
                             ))
-                            .with_label(Label::primary(function.span, "invalid entry point function type")),
+                            .with_label(Label::primary(function.span, "invalid entry point function type"))
+                            .with_note("the optional parameter receives the program's command-line arguments"),
                     )
                 }
             } else {
@@ -113,6 +113,13 @@ impl Env {
         None
     }
 
+    // Returns the names of all local bindings and functions visible from the current scope
+    pub fn local_names(&self) -> impl Iterator<Item = Ustr> + '_ {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.bindings.keys().chain(scope.functions.keys()).copied())
+    }
+
     pub fn find_function(&self, name: Ustr) -> Option<hir::FunctionId> {
         for scope in self.scopes.iter().rev() {
             if let Some(id) = scope.functions.get(&name) {
@@ -11,7 +11,10 @@ use self::pat::get_qualified_name;
 use crate::{
     ast::{self, pat::Pat},
     check::intrinsics::{can_dispatch_intrinsic_at_comptime, dispatch_intrinsic},
-    common::target::TargetMetrics,
+    common::{
+        path::{maybe_resolve_relative_path, RelativeTo},
+        target::{TargetCapability, TargetMetrics},
+    },
     error::{
         diagnostic::{Diagnostic, Label},
         DiagnosticResult, SyntaxError, TypeError,
@@ -25,11 +28,12 @@ use crate::{
         cast::{can_cast_type, try_cast_const_value},
         coerce::{coerce_array_to_slice, OrCoerce, OrCoerceIntoTy},
         display::{DisplayType, OrReportErr},
+        inference_value::InferenceValue,
         misc::IsConcrete,
-        normalize::Normalize,
+        normalize::{Concrete, Normalize},
         substitute::substitute_cache,
         type_ctx::TypeCtx,
-        unify::{occurs, UnifyType, UnifyTypeErr},
+        unify::UnifyType,
     },
     interp::interp::Interp,
     span::Span,
@@ -47,14 +51,15 @@ use indexmap::{indexmap, IndexMap};
 use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
+    path::Path,
 };
 use top_level::CallerInfo;
 use ustr::{ustr, Ustr, UstrMap, UstrSet};
 
 pub type CheckData = (hir::Cache, TypeCtx);
 
-pub fn check(workspace: &mut Workspace, module: Vec<ast::Module>) -> CheckData {
-    let mut sess = CheckSess::new(workspace, &module);
+pub fn check(workspace: &mut Workspace, modules: &[ast::Module]) -> CheckData {
+    let mut sess = CheckSess::new(workspace, modules);
 
     if let Err(diag) = sess.start() {
         sess.workspace.diagnostics.push(diag);
@@ -100,9 +105,9 @@ pub(super) struct CheckSess<'s> {
     // Stack of `Self` types
     pub self_types: Vec<TypeId>,
 
-    // The current loop (while/for) depth
-    // 0 meaning we are not in a loop, > 1 means we are in a loop
-    pub loop_depth: usize,
+    // Stack of loop frames, each loop (loop/while/for) creates its own frame.
+    // An empty stack means we are not in a loop
+    pub loop_frames: Vec<LoopFrame>,
 
     pub unique_name_indices: UstrMap<usize>,
 
@@ -110,6 +115,18 @@ pub(super) struct CheckSess<'s> {
 
     // A stack of encountered items. Used to detect global bindings that refer themselves
     pub encountered_items: HashSet<(ModuleId, usize)>,
+
+    // The names of the top level bindings currently being checked, from outermost to innermost.
+    // Used to trace errors in transitively required bindings back to their origin
+    pub top_level_stack: Vec<String>,
+
+    // The evaluated conditions of `static if`s, by their index in the module.
+    // None means that the condition is currently being evaluated
+    pub static_if_conditions: HashMap<(ModuleId, usize), Option<bool>>,
+
+    // The nesting depth of the expression being checked, within its top level binding.
+    // Deeper expressions are rejected, instead of overflowing the stack
+    pub expr_depth: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -119,26 +136,48 @@ pub struct FunctionFrame {
     scope_level: ScopeLevel,
 }
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LoopFrame {
+    // Whether a `break` targets this loop. A loop that is never broken out of
+    // and has no terminating condition never produces a value
+    has_break: bool,
+}
+
 impl<'s> CheckSess<'s> {
     pub fn new(workspace: &'s mut Workspace, old_asts: &'s Vec<ast::Module>) -> Self {
         let target_metrics = workspace.build_options.target_platform.metrics();
         let interp = Interp::new(workspace.build_options.clone());
+        let max_type_var_depth = workspace.build_options.max_type_var_depth;
+        let default_int_type = workspace.build_options.default_int_type.as_type();
+
+        let mut tcx = TypeCtx::new(max_type_var_depth, default_int_type);
+
+        let main_library_id = workspace.main_library().id;
+        tcx.main_library_files = workspace
+            .module_infos
+            .iter()
+            .filter(|(_, module_info)| module_info.library_id == main_library_id)
+            .map(|(_, module_info)| module_info.file_id)
+            .collect();
 
         Self {
             workspace,
             target_metrics,
             interp,
-            tcx: TypeCtx::default(),
+            tcx,
             modules: old_asts,
             cache: hir::Cache::new(),
             queued_modules: HashMap::new(),
             global_scopes: HashMap::new(),
             function_frames: vec![],
             self_types: vec![],
-            loop_depth: 0,
+            loop_frames: vec![],
             unique_name_indices: UstrMap::default(),
             in_lvalue_context: false,
             encountered_items: HashSet::new(),
+            top_level_stack: vec![],
+            static_if_conditions: HashMap::new(),
+            expr_depth: 0,
         }
     }
 
@@ -179,21 +218,60 @@ impl<'s> CheckSess<'s> {
     }
 
     fn perform_final_substitution(&mut self) -> CheckResult<()> {
-        substitute_cache(&self.cache, &mut self.tcx).map_err(|mut diagnostics| {
+        let result = substitute_cache(&self.cache, &mut self.tcx).map_err(|mut diagnostics| {
             let last = diagnostics.pop().unwrap();
             self.workspace.diagnostics.extend(diagnostics);
             last
-        })
+        });
+
+        if self.workspace.build_options.warn_default_int {
+            self.warn_defaulted_int_types();
+        }
+
+        result
+    }
+
+    fn warn_defaulted_int_types(&mut self) {
+        let default_int_type = self.tcx.default_int_type.display(&self.tcx);
+
+        for ty in std::mem::take(&mut self.tcx.defaulted_int_types) {
+            let span = match self.tcx.ty_span(ty) {
+                Some(span) => span,
+                None => continue,
+            };
+
+            if self.tcx.main_library_files.contains(&span.file_id) {
+                self.workspace.diagnostics.push(
+                    Diagnostic::warning()
+                        .with_message(format!("integer type defaults to `{}`", default_int_type))
+                        .with_label(Label::primary(span, "the type of this value couldn't be inferred"))
+                        .with_note("add a type annotation to choose the integer type explicitly"),
+                );
+            }
+        }
     }
 
-    fn into_data(self) -> CheckData {
+    fn into_data(mut self) -> CheckData {
+        self.cache.static_if_conditions = self
+            .static_if_conditions
+            .iter()
+            .filter_map(|(&key, &condition)| condition.map(|condition| (key, condition)))
+            .collect();
+
         (self.cache, self.tcx)
     }
 
     pub fn with_function_frame<T, F: FnMut(&mut Self) -> T>(&mut self, frame: FunctionFrame, mut f: F) -> T {
         self.function_frames.push(frame);
+
+        // loops of the enclosing function can't be targeted by `break` and `continue` in this one
+        let loop_frames = std::mem::take(&mut self.loop_frames);
+
         let result = f(self);
+
+        self.loop_frames = loop_frames;
         self.function_frames.pop();
+
         result
     }
 
@@ -206,6 +284,32 @@ impl<'s> CheckSess<'s> {
         self.function_frames.last().map(|&f| f)
     }
 
+    // Validates the depth of a `break` or `continue` against the amount of enclosing loops, returning it
+    fn check_loop_control_depth(&self, term: &ast::LoopControl, word: &str) -> CheckResult<usize> {
+        if self.loop_frames.is_empty() {
+            return Err(SyntaxError::outside_of_loop(term.span, word));
+        }
+
+        let depth = term.depth.unwrap_or(1);
+
+        if depth == 0 {
+            Err(Diagnostic::error()
+                .with_message(format!("`{}` depth must be at least 1", word))
+                .with_label(Label::primary(term.span, "invalid depth")))
+        } else if depth > self.loop_frames.len() {
+            Err(Diagnostic::error()
+                .with_message(format!(
+                    "`{} {}` targets a loop that doesn't exist - there are only {} enclosing loops",
+                    word,
+                    depth,
+                    self.loop_frames.len()
+                ))
+                .with_label(Label::primary(term.span, "depth is too large")))
+        } else {
+            Ok(depth)
+        }
+    }
+
     pub fn require_const_type(&self, node: &hir::Node) -> DiagnosticResult<TypeId> {
         match node.as_const_value() {
             Some(ConstValue::Type(t)) => Ok(*t),
@@ -224,6 +328,50 @@ impl<'s> CheckSess<'s> {
         }
     }
 
+    // Makes sure an array's total size fits in the target's address space,
+    // before anyone tries to compute its layout
+    pub fn require_array_size(
+        &self,
+        array_type: &Type,
+        element_type: &Type,
+        len: i128,
+        span: Span,
+    ) -> DiagnosticResult<()> {
+        let max_size = self.target_metrics.max_object_size();
+        let element_size = element_type.size_of(self.target_metrics.word_size) as u128;
+        let total_size = (len as u128).checked_mul(element_size);
+
+        match total_size {
+            Some(total_size) if total_size <= max_size => Ok(()),
+            _ => Err(TypeError::array_too_large(
+                span,
+                array_type.display(&self.tcx),
+                total_size.unwrap_or(u128::MAX),
+                max_size,
+            )),
+        }
+    }
+
+    pub fn require_target_capability(
+        &self,
+        capability: TargetCapability,
+        builtin_name: &str,
+        span: Span,
+    ) -> DiagnosticResult<()> {
+        let target_platform = &self.workspace.build_options.target_platform;
+
+        if target_platform.supports(capability) {
+            Ok(())
+        } else {
+            Err(Diagnostic::error()
+                .with_message(format!(
+                    "builtin function `{}!` is not supported by target `{:?}`",
+                    builtin_name, target_platform
+                ))
+                .with_label(Label::primary(span, "unsupported by the target")))
+        }
+    }
+
     pub(super) fn is_lvalue(&self, node: &hir::Node) -> bool {
         match node {
             hir::Node::MemberAccess(_)
@@ -486,6 +634,10 @@ impl Check for ast::Binding {
 
         sess.check_attrs_are_assigned_to_valid_binding(&attrs, self)?;
 
+        if attrs.has(AttrKind::AllowRedundantCast) {
+            sess.cache.redundant_cast_allowances.push(self.span);
+        }
+
         match &self.kind {
             ast::BindingKind::Let { pat, type_expr, value } => {
                 let ty = check_optional_type_expr(type_expr, sess, env, pat.span())?;
@@ -536,8 +688,26 @@ impl Check for ast::Binding {
                     ))
                 }
 
+                let export_name = match attrs.get(AttrKind::Export) {
+                    Some(attr) => Some((sess.validate_export_attr(env, attr, pat, &value_node)?, attr.span)),
+                    None => None,
+                };
+
+                let align = match attrs.get(AttrKind::Align) {
+                    Some(attr) => {
+                        if let Some(ConstValue::Function(_)) = value_node.as_const_value() {
+                            return Err(Diagnostic::error()
+                                .with_message("the `align` attribute can't be used on functions")
+                                .with_label(Label::primary(attr.span, "invalid attribute use")));
+                        }
+
+                        Some(sess.validate_align_attr(attr)?)
+                    }
+                    None => None,
+                };
+
                 let value_span = value_node.span();
-                let (_, bound_node) = sess.bind_pat(
+                let (id, bound_node) = sess.bind_pat(
                     env,
                     &pat,
                     self.vis,
@@ -556,6 +726,14 @@ impl Check for ast::Binding {
                     },
                 )?;
 
+                if let Some((name, span)) = export_name {
+                    sess.cache.exported_bindings.push(hir::ExportedBinding { id, name, span });
+                }
+
+                if let Some(align) = align {
+                    sess.cache.binding_aligns.insert(id, align);
+                }
+
                 Ok(bound_node)
             }
             ast::BindingKind::Function {
@@ -581,6 +759,11 @@ impl Check for ast::Binding {
                     },
                 )?;
 
+                if let Some(attr) = attrs.get(AttrKind::Test) {
+                    let id = sess.validate_test_attr(env, attr, &node)?;
+                    sess.cache.test_functions.push(id);
+                }
+
                 // If this function binding matches the entry point function's requirements, Tag it as the entry function
                 // Requirements:
                 // - Is declared in the root module
@@ -829,6 +1012,10 @@ impl Check for ast::Binding {
 
                 let type_node = type_expr.check(sess, env, Some(sess.tcx.common_types.anytype))?;
 
+                if let Some(attr) = attrs.get(AttrKind::Align) {
+                    sess.apply_align_attr(attr, &type_node, type_expr.span())?;
+                }
+
                 match type_node.as_const_value() {
                     Some(ConstValue::Type(_)) => sess
                         .bind_name(
@@ -890,6 +1077,28 @@ impl Check for ast::FunctionSig {
 
 impl Check for ast::Ast {
     fn check(&self, sess: &mut CheckSess, env: &mut Env, expected_type: Option<TypeId>) -> CheckResult {
+        let max_expr_depth = sess.workspace.build_options.max_expr_depth;
+
+        if sess.expr_depth >= max_expr_depth {
+            return Err(Diagnostic::error()
+                .with_message("expression nesting too deep")
+                .with_label(Label::primary(
+                    self.span(),
+                    format!("exceeds the maximum nesting depth of {}", max_expr_depth),
+                ))
+                .with_note("the maximum depth can be raised with `--max-expr-depth`"));
+        }
+
+        sess.expr_depth += 1;
+        let result = self.check_inner(sess, env, expected_type);
+        sess.expr_depth -= 1;
+
+        result
+    }
+}
+
+impl ast::Ast {
+    fn check_inner(&self, sess: &mut CheckSess, env: &mut Env, expected_type: Option<TypeId>) -> CheckResult {
         match self {
             ast::Ast::Binding(binding) => binding.check(sess, env, None),
             ast::Ast::Cast(cast) => cast.check(sess, env, expected_type),
@@ -939,6 +1148,693 @@ impl Check for ast::Ast {
                         }))
                     }
                 }
+                ast::BuiltinKind::SizeOfVal(expr) => {
+                    let node = expr.check(sess, env, None)?;
+                    let ty = node.ty().normalize(&sess.tcx);
+                    let uint_type = sess.tcx.common_types.uint;
+
+                    if ty.is_fat_pointer() {
+                        // the size of a slice's value is known only at runtime: `len * size_of!(element)`
+                        let element_size = ty.element_type().unwrap().size_of(sess.target_metrics.word_size);
+
+                        if let Some(ConstValue::Str(s)) = node.as_const_value() {
+                            return Ok(hir::Node::Const(hir::Const {
+                                value: ConstValue::Int((s.len() * element_size) as _),
+                                ty: uint_type,
+                                span: builtin.span,
+                            }));
+                        }
+
+                        let len = CheckSess::get_len_node(&sess.tcx, &node).unwrap();
+
+                        Ok(hir::Node::Builtin(hir::Builtin::Mul(hir::Binary {
+                            lhs: Box::new(len),
+                            rhs: Box::new(hir::Node::Const(hir::Const {
+                                value: ConstValue::Int(element_size as _),
+                                ty: uint_type,
+                                span: builtin.span,
+                            })),
+                            ty: uint_type,
+                            span: builtin.span,
+                        })))
+                    } else if ty.is_unsized() {
+                        Err(TypeError::type_is_unsized(ty.display(&sess.tcx), expr.span()))
+                    } else {
+                        Ok(hir::Node::Const(hir::Const {
+                            value: ConstValue::Int(ty.size_of(sess.target_metrics.word_size) as _),
+                            ty: uint_type,
+                            span: builtin.span,
+                        }))
+                    }
+                }
+                ast::BuiltinKind::AlignOfVal(expr) => {
+                    let node = expr.check(sess, env, None)?;
+                    let ty = node.ty().normalize(&sess.tcx);
+
+                    // the alignment of a slice's value is the alignment of its elements
+                    let value_type = if ty.is_fat_pointer() {
+                        ty.element_type().unwrap().clone()
+                    } else {
+                        ty
+                    };
+
+                    if value_type.is_unsized() {
+                        Err(TypeError::type_is_unsized(value_type.display(&sess.tcx), expr.span()))
+                    } else {
+                        Ok(hir::Node::Const(hir::Const {
+                            value: ConstValue::Int(value_type.align_of(sess.target_metrics.word_size) as _),
+                            ty: sess.tcx.common_types.uint,
+                            span: builtin.span,
+                        }))
+                    }
+                }
+                ast::BuiltinKind::TypeId(expr) => {
+                    let ty = check_type_expr(&expr, sess, env)?;
+                    let id = sess.tcx.type_id_of(ty);
+
+                    Ok(hir::Node::Const(hir::Const {
+                        value: ConstValue::Int(id as _),
+                        ty: sess.tcx.common_types.u64,
+                        span: builtin.span,
+                    }))
+                }
+                ast::BuiltinKind::TypeEq(a, b) => {
+                    let a = check_type_expr(&a, sess, env)?;
+                    let b = check_type_expr(&b, sess, env)?;
+
+                    Ok(hir::Node::Const(hir::Const {
+                        value: ConstValue::Bool(a.concrete(&sess.tcx) == b.concrete(&sess.tcx)),
+                        ty: sess.tcx.common_types.bool,
+                        span: builtin.span,
+                    }))
+                }
+                ast::BuiltinKind::FieldCount(ty_expr) => {
+                    let ty = check_type_expr(&ty_expr, sess, env)?;
+                    let ty = ty.normalize(&sess.tcx);
+
+                    let count = match &ty {
+                        Type::Struct(st) => st.fields.len(),
+                        Type::Tuple(elements) => elements.len(),
+                        _ => {
+                            return Err(TypeError::expected(
+                                ty_expr.span(),
+                                ty.display(&sess.tcx),
+                                "a struct or tuple type",
+                            ))
+                        }
+                    };
+
+                    Ok(hir::Node::Const(hir::Const {
+                        value: ConstValue::Int(count as _),
+                        ty: sess.tcx.common_types.uint,
+                        span: builtin.span,
+                    }))
+                }
+                ast::BuiltinKind::IncludeStr(path_expr) => {
+                    let path_node = path_expr.check(sess, env, Some(sess.tcx.common_types.str_pointer))?;
+
+                    let path = match path_node.as_const_value() {
+                        Some(ConstValue::Str(path)) => *path,
+                        _ => {
+                            return Err(TypeError::expected(
+                                path_node.span(),
+                                path_node.ty().display(&sess.tcx),
+                                "compile-time known string",
+                            ))
+                        }
+                    };
+
+                    // The path is resolved relative to the current file, and then relative to each include path
+                    let module_dir = env.module_info().dir().to_path_buf();
+
+                    let include_path = Path::new(path.as_str());
+
+                    let resolved_path = std::iter::once(&module_dir)
+                        .chain(sess.workspace.build_options.include_paths.iter())
+                        .find_map(|dir| maybe_resolve_relative_path(include_path, &RelativeTo::Path(dir)))
+                        .ok_or_else(|| {
+                            Diagnostic::error()
+                                .with_message(format!("couldn't find file `{}`", path))
+                                .with_label(Label::primary(path_node.span(), "not found"))
+                        })?;
+
+                    let bytes = std::fs::read(&resolved_path).map_err(|err| {
+                        Diagnostic::error()
+                            .with_message(format!("couldn't read file `{}`: {}", resolved_path.display(), err))
+                            .with_label(Label::primary(path_node.span(), "couldn't read file"))
+                    })?;
+
+                    let contents = String::from_utf8(bytes).map_err(|err| {
+                        Diagnostic::error()
+                            .with_message(format!("file `{}` is not valid UTF-8", resolved_path.display()))
+                            .with_label(Label::primary(path_node.span(), "included here"))
+                            .with_note(format!(
+                                "found an invalid byte sequence at offset {}",
+                                err.utf8_error().valid_up_to()
+                            ))
+                    })?;
+
+                    Ok(hir::Node::Const(hir::Const {
+                        value: ConstValue::Str(ustr(&contents)),
+                        ty: sess.tcx.common_types.str_pointer,
+                        span: builtin.span,
+                    }))
+                }
+                ast::BuiltinKind::FieldType(ty_expr, field_expr) => {
+                    let ty = check_type_expr(&ty_expr, sess, env)?;
+                    let ty = ty.normalize(&sess.tcx);
+
+                    let struct_type = match &ty {
+                        Type::Struct(st) => st,
+                        _ => {
+                            return Err(TypeError::expected(
+                                ty_expr.span(),
+                                ty.display(&sess.tcx),
+                                "a struct type",
+                            ))
+                        }
+                    };
+
+                    let field_node = field_expr.check(sess, env, Some(sess.tcx.common_types.str_pointer))?;
+
+                    let field_name = match field_node.as_const_value() {
+                        Some(ConstValue::Str(name)) => *name,
+                        _ => {
+                            return Err(TypeError::expected(
+                                field_node.span(),
+                                field_node.ty().display(&sess.tcx),
+                                "compile-time known string",
+                            ))
+                        }
+                    };
+
+                    match struct_type.field(field_name) {
+                        Some(field) => {
+                            let field_type = field.ty.clone();
+
+                            Ok(hir::Node::Const(hir::Const {
+                                value: ConstValue::Type(sess.tcx.bound(field_type.clone(), builtin.span)),
+                                ty: sess.tcx.bound(field_type.create_type(), builtin.span),
+                                span: builtin.span,
+                            }))
+                        }
+                        None => Err(TypeError::invalid_struct_field(
+                            field_node.span(),
+                            field_name,
+                            ty.display(&sess.tcx),
+                        )),
+                    }
+                }
+                ast::BuiltinKind::AddWithOverflow(lhs, rhs) => {
+                    let lhs_node = lhs.check(sess, env, None)?;
+                    let rhs_node = rhs.check(sess, env, Some(lhs_node.ty()))?;
+
+                    rhs_node.ty().unify(&lhs_node.ty(), &mut sess.tcx).or_report_err(
+                        &sess.tcx,
+                        &lhs_node.ty(),
+                        Some(lhs_node.span()),
+                        &rhs_node.ty(),
+                        rhs_node.span(),
+                    )?;
+
+                    let operand_type = lhs_node.ty().normalize(&sess.tcx);
+
+                    if !operand_type.is_any_integer() {
+                        return Err(TypeError::expected(
+                            lhs_node.span(),
+                            operand_type.display(&sess.tcx),
+                            "an integer",
+                        ));
+                    }
+
+                    let ty = sess.tcx.bound(
+                        Type::Tuple(vec![lhs_node.ty().as_kind(), Type::Bool]),
+                        builtin.span,
+                    );
+
+                    Ok(hir::Node::Builtin(hir::Builtin::AddWithOverflow(hir::Binary {
+                        lhs: Box::new(lhs_node),
+                        rhs: Box::new(rhs_node),
+                        ty,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::MemCmp(lhs, rhs, len) => {
+                    let mut check_ptr = |ptr: &ast::Ast| -> CheckResult {
+                        let node = ptr.check(sess, env, None)?;
+                        let ty = node.ty().normalize(&sess.tcx);
+
+                        if ty.is_pointer() && !ty.is_fat_pointer() {
+                            Ok(node)
+                        } else {
+                            Err(TypeError::expected(node.span(), ty.display(&sess.tcx), "a pointer"))
+                        }
+                    };
+
+                    let lhs_node = check_ptr(lhs)?;
+                    let rhs_node = check_ptr(rhs)?;
+
+                    let uint_type = sess.tcx.common_types.uint;
+
+                    let mut len_node = len.check(sess, env, Some(uint_type))?;
+
+                    len_node
+                        .ty()
+                        .unify(&uint_type, &mut sess.tcx)
+                        .or_coerce_into_ty(&mut len_node, &uint_type, &mut sess.tcx, sess.target_metrics.word_size)
+                        .or_report_err(&sess.tcx, &uint_type, None, &len_node.ty(), len.span())?;
+
+                    Ok(hir::Node::Builtin(hir::Builtin::MemCmp(hir::MemCmp {
+                        lhs: Box::new(lhs_node),
+                        rhs: Box::new(rhs_node),
+                        len: Box::new(len_node),
+                        ty: sess.tcx.common_types.i32,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Splat(ty_expr, len, value) => {
+                    let element_type = check_type_expr(ty_expr, sess, env)?;
+                    let normalized = element_type.normalize(&sess.tcx);
+
+                    if !normalized.is_number() {
+                        return Err(TypeError::expected(
+                            ty_expr.span(),
+                            normalized.display(&sess.tcx),
+                            "a numeric type",
+                        ));
+                    }
+
+                    let len_node = len.check(sess, env, None)?;
+                    let len = sess.require_const_int(&len_node)?;
+
+                    if len < 0 {
+                        return Err(TypeError::negative_array_len(len_node.span(), len));
+                    }
+
+                    let mut value_node = value.check(sess, env, Some(element_type))?;
+
+                    value_node
+                        .ty()
+                        .unify(&element_type, &mut sess.tcx)
+                        .or_coerce_into_ty(
+                            &mut value_node,
+                            &element_type,
+                            &mut sess.tcx,
+                            sess.target_metrics.word_size,
+                        )
+                        .or_report_err(&sess.tcx, &element_type, None, &value_node.ty(), value.span())?;
+
+                    let array_type = Type::Array(Box::new(element_type.into()), len as _);
+                    sess.require_array_size(&array_type, &normalized, len, builtin.span)?;
+
+                    let ty = sess.tcx.bound(array_type, builtin.span);
+
+                    // `splat!(T, len, value)` is the same as the array fill literal `[value as T; len]`
+                    match value_node.as_const_value() {
+                        Some(const_value) => Ok(hir::Node::Const(hir::Const {
+                            value: ConstValue::Array(ConstArray {
+                                values: vec![const_value.clone(); len as usize],
+                                element_type,
+                            }),
+                            ty,
+                            span: builtin.span,
+                        })),
+                        None => Ok(hir::Node::Literal(hir::Literal::ArrayFill(hir::ArrayFillLiteral {
+                            value: Box::new(value_node),
+                            len: len as usize,
+                            ty,
+                            span: builtin.span,
+                        }))),
+                    }
+                }
+                ast::BuiltinKind::Asm(ty_expr, template, constraints, inputs) => {
+                    let ty = check_type_expr(ty_expr, sess, env)?;
+                    let output_type = ty.normalize(&sess.tcx);
+
+                    let expected_outputs = if output_type.is_unit() {
+                        0
+                    } else if output_type.is_any_integer() {
+                        1
+                    } else {
+                        return Err(TypeError::expected(
+                            ty_expr.span(),
+                            output_type.display(&sess.tcx),
+                            "() or an integer type",
+                        ));
+                    };
+
+                    let mut check_const_str = |expr: &ast::Ast| -> DiagnosticResult<Ustr> {
+                        let node = expr.check(sess, env, Some(sess.tcx.common_types.str_pointer))?;
+
+                        match node.as_const_value() {
+                            Some(ConstValue::Str(value)) => Ok(*value),
+                            _ => Err(TypeError::expected(
+                                node.span(),
+                                node.ty().display(&sess.tcx),
+                                "compile-time known string",
+                            )),
+                        }
+                    };
+
+                    let template = check_const_str(template)?;
+                    let constraints_value = check_const_str(constraints)?;
+
+                    // Outputs start with `=`, clobbers start with `~`, and everything else is an input
+                    let (mut outputs, mut expected_inputs) = (0, 0);
+
+                    for constraint in constraints_value.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+                        if constraint.starts_with('=') {
+                            outputs += 1;
+                        } else if !constraint.starts_with('~') {
+                            expected_inputs += 1;
+                        }
+                    }
+
+                    if outputs != expected_outputs {
+                        return Err(Diagnostic::error()
+                            .with_message(format!(
+                                "expected {} output constraint(s) for type `{}`, but found {}",
+                                expected_outputs,
+                                output_type.display(&sess.tcx),
+                                outputs
+                            ))
+                            .with_label(Label::primary(constraints.span(), "invalid constraints")));
+                    }
+
+                    if inputs.len() != expected_inputs {
+                        return Err(Diagnostic::error()
+                            .with_message(format!(
+                                "expected {} input operand(s), but found {}",
+                                expected_inputs,
+                                inputs.len()
+                            ))
+                            .with_label(Label::primary(builtin.span, "wrong number of operands")));
+                    }
+
+                    let mut input_nodes = vec![];
+
+                    for input in inputs.iter() {
+                        let node = input.check(sess, env, None)?;
+                        let input_type = node.ty().normalize(&sess.tcx);
+
+                        if !input_type.is_any_integer() && !(input_type.is_pointer() && !input_type.is_fat_pointer()) {
+                            return Err(TypeError::expected(
+                                node.span(),
+                                input_type.display(&sess.tcx),
+                                "an integer or a pointer",
+                            ));
+                        }
+
+                        input_nodes.push(node);
+                    }
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Asm(hir::Asm {
+                        template,
+                        constraints: constraints_value,
+                        inputs: input_nodes,
+                        ty,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::IsAligned(ptr, align) => {
+                    let ptr_node = ptr.check(sess, env, None)?;
+                    let ptr_type = ptr_node.ty().normalize(&sess.tcx);
+
+                    if !ptr_type.is_pointer() {
+                        return Err(TypeError::expected(
+                            ptr_node.span(),
+                            ptr_type.display(&sess.tcx),
+                            "a pointer",
+                        ));
+                    }
+
+                    let uint_type = sess.tcx.common_types.uint;
+
+                    let align_node = align.check(sess, env, Some(uint_type))?;
+                    let align_value = sess.require_const_int(&align_node)?;
+
+                    if align_value <= 0 || align_value & (align_value - 1) != 0 {
+                        return Err(Diagnostic::error()
+                            .with_message(format!("alignment must be a power of two, but found {}", align_value))
+                            .with_label(Label::primary(align_node.span(), "not a power of two")));
+                    }
+
+                    // is_aligned!(ptr, align) is lowered to `(ptr as uint & (align - 1)) == 0`
+                    let masked = hir::Node::Builtin(hir::Builtin::BitAnd(hir::Binary {
+                        lhs: Box::new(hir::Node::Cast(hir::Cast {
+                            value: Box::new(ptr_node),
+                            ty: uint_type,
+                            span: ptr.span(),
+                        })),
+                        rhs: Box::new(hir::Node::Const(hir::Const {
+                            value: ConstValue::Int(align_value - 1),
+                            ty: uint_type,
+                            span: align_node.span(),
+                        })),
+                        ty: uint_type,
+                        span: builtin.span,
+                    }));
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Eq(hir::Binary {
+                        lhs: Box::new(masked),
+                        rhs: Box::new(hir::Node::Const(hir::Const {
+                            value: ConstValue::Int(0),
+                            ty: uint_type,
+                            span: builtin.span,
+                        })),
+                        ty: sess.tcx.common_types.bool,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Assume(condition) => {
+                    let bool_type = sess.tcx.common_types.bool;
+
+                    let condition_node = condition.check(sess, env, Some(bool_type))?;
+
+                    condition_node.ty().unify(&bool_type, &mut sess.tcx).or_report_err(
+                        &sess.tcx,
+                        &bool_type,
+                        None,
+                        &condition_node.ty(),
+                        condition_node.span(),
+                    )?;
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Assume(hir::Unary {
+                        value: Box::new(condition_node),
+                        ty: sess.tcx.common_types.unit,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Breakpoint => {
+                    sess.require_target_capability(TargetCapability::DebugTrap, "breakpoint", builtin.span)?;
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Breakpoint(hir::Empty {
+                        ty: sess.tcx.common_types.unit,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Abort(code) => {
+                    sess.require_target_capability(TargetCapability::ProcessExit, "abort", builtin.span)?;
+
+                    let i32_type = sess.tcx.common_types.i32;
+
+                    let mut code_node = code.check(sess, env, Some(i32_type))?;
+
+                    code_node
+                        .ty()
+                        .unify(&i32_type, &mut sess.tcx)
+                        .or_coerce_into_ty(&mut code_node, &i32_type, &mut sess.tcx, sess.target_metrics.word_size)
+                        .or_report_err(&sess.tcx, &i32_type, None, &code_node.ty(), code.span())?;
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Abort(hir::Unary {
+                        value: Box::new(code_node),
+                        ty: sess.tcx.common_types.never,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Panic(message) => {
+                    sess.require_target_capability(TargetCapability::ProcessExit, "panic", builtin.span)?;
+
+                    let str_type = sess.tcx.common_types.str_pointer;
+
+                    let message_node = message.check(sess, env, Some(str_type))?;
+
+                    message_node.ty().unify(&str_type, &mut sess.tcx).or_report_err(
+                        &sess.tcx,
+                        &str_type,
+                        None,
+                        &message_node.ty(),
+                        message_node.span(),
+                    )?;
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Panic(hir::Unary {
+                        value: Box::new(message_node),
+                        ty: sess.tcx.common_types.never,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Ref(value, is_mutable) => {
+                    let is_mutable = *is_mutable;
+
+                    sess.in_lvalue_context = true;
+                    let node = value.check(sess, env, None)?;
+                    sess.in_lvalue_context = false;
+
+                    // unlike `&`, `ref!` doesn't implicitly bind its value to a temporary
+                    if !sess.is_lvalue(&node) {
+                        return Err(Diagnostic::error()
+                            .with_message("`ref!` expects an lvalue")
+                            .with_label(Label::primary(value.span(), "not an lvalue"))
+                            .with_note("to reference a temporary value, use `&`"));
+                    }
+
+                    let ptr_type = sess
+                        .tcx
+                        .bound(Type::Pointer(Box::new(node.ty().as_kind()), is_mutable), builtin.span);
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Ref(hir::Ref {
+                        value: Box::new(node),
+                        is_mutable,
+                        ty: ptr_type,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Deref(value) => ast::Unary {
+                    op: ast::UnaryOp::Deref,
+                    value: value.clone(),
+                    span: builtin.span,
+                }
+                .check(sess, env, expected_type),
+                ast::BuiltinKind::Call(callee_expr, args_expr) => {
+                    let callee = callee_expr.check(sess, env, None)?;
+
+                    let function_type = match callee.ty().normalize(&sess.tcx) {
+                        Type::Function(f) if f.varargs.is_none() => f,
+                        ty => {
+                            return Err(Diagnostic::error()
+                                .with_message(format!(
+                                    "`call!` expects a non-variadic function, found `{}`",
+                                    ty.display(&sess.tcx)
+                                ))
+                                .with_label(Label::primary(callee_expr.span(), "not a non-variadic function")))
+                        }
+                    };
+
+                    let args_node = args_expr.check(sess, env, None)?;
+
+                    let elem_types = match args_node.ty().normalize(&sess.tcx) {
+                        Type::Tuple(elem_types) => elem_types,
+                        ty => {
+                            return Err(Diagnostic::error()
+                                .with_message(format!(
+                                    "`call!` expects its arguments as a tuple, found `{}`",
+                                    ty.display(&sess.tcx)
+                                ))
+                                .with_label(Label::primary(args_expr.span(), "expected a tuple")))
+                        }
+                    };
+
+                    if elem_types.len() != function_type.params.len() {
+                        return Err(Diagnostic::error()
+                            .with_message(format!(
+                                "function expects {} argument{}, but the tuple has {} element{}",
+                                function_type.params.len(),
+                                if function_type.params.len() == 1 { "" } else { "s" },
+                                elem_types.len(),
+                                if elem_types.len() == 1 { "" } else { "s" },
+                            ))
+                            .with_label(Label::primary(args_expr.span(), "argument count mismatch"))
+                            .with_note(format!("function is of type `{}`", function_type.display(&sess.tcx))));
+                    }
+
+                    let mut statements = vec![];
+
+                    // the tuple is bound to a temporary, so that it is evaluated exactly once
+                    let args_value = match args_node {
+                        hir::Node::Id(_) => args_node,
+                        _ => {
+                            let ty = args_node.ty();
+                            let span = args_node.span();
+                            let name = sess.generate_name("args");
+
+                            let (id, bound_node) = sess.bind_name(
+                                env,
+                                name,
+                                ast::Vis::Private,
+                                ty,
+                                Some(args_node),
+                                false,
+                                BindingInfoKind::LetConst,
+                                span,
+                                BindingInfoFlags::NO_CONST_FOLD,
+                            )?;
+
+                            statements.push(bound_node);
+
+                            hir::Node::Id(hir::Id { id, ty, span })
+                        }
+                    };
+
+                    let mut args = vec![];
+
+                    for (index, (elem_type, param)) in elem_types.iter().zip(function_type.params.iter()).enumerate() {
+                        let param_type = sess.tcx.bound(param.ty.clone(), args_expr.span());
+
+                        let mut arg = hir::Node::MemberAccess(hir::MemberAccess {
+                            value: Box::new(args_value.clone()),
+                            member_name: ustr(&index.to_string()),
+                            member_index: index as _,
+                            ty: sess.tcx.bound(elem_type.clone(), args_expr.span()),
+                            span: args_expr.span(),
+                        });
+
+                        arg.ty()
+                            .unify(&param_type, &mut sess.tcx)
+                            .or_coerce_into_ty(&mut arg, &param_type, &mut sess.tcx, sess.target_metrics.word_size)
+                            .or_report_err(&sess.tcx, &param_type, None, &arg.ty(), args_expr.span())?;
+
+                        args.push(arg);
+                    }
+
+                    let ty = sess.tcx.bound(function_type.return_type.as_ref().clone(), builtin.span);
+
+                    statements.push(hir::Node::Call(hir::Call {
+                        callee: Box::new(callee),
+                        args,
+                        ty,
+                        span: builtin.span,
+                    }));
+
+                    Ok(hir::Node::Sequence(hir::Sequence {
+                        statements,
+                        ty,
+                        span: builtin.span,
+                        is_scope: false,
+                    }))
+                }
+                ast::BuiltinKind::Zeroed(ty_expr) => {
+                    let ty = check_type_expr(&ty_expr, sess, env)?;
+                    let normalized = ty.normalize(&sess.tcx);
+
+                    if normalized.is_unsized() {
+                        return Err(TypeError::type_is_unsized(normalized.display(&sess.tcx), ty_expr.span()));
+                    }
+
+                    Ok(hir::Node::Builtin(hir::Builtin::Zeroed(hir::Empty {
+                        ty,
+                        span: builtin.span,
+                    })))
+                }
+                ast::BuiltinKind::Src => {
+                    let ty = sess.location_type()?;
+                    let value = sess.build_location_value(env, builtin.span)?;
+
+                    Ok(hir::Node::Const(hir::Const {
+                        value,
+                        ty,
+                        span: builtin.span,
+                    }))
+                }
             },
             ast::Ast::Comptime(const_) => const_.check(sess, env, expected_type),
             ast::Ast::Function(function) => function.check(sess, env, expected_type),
@@ -946,24 +1842,25 @@ impl Check for ast::Ast {
             ast::Ast::While(while_) => while_.check(sess, env, expected_type),
             ast::Ast::For(for_) => for_.check(sess, env, expected_type),
             ast::Ast::Break(term) => {
-                if sess.loop_depth > 0 {
-                    Ok(hir::Node::Control(hir::Control::Break(hir::Empty {
-                        ty: sess.tcx.common_types.never,
-                        span: term.span,
-                    })))
-                } else {
-                    Err(SyntaxError::outside_of_loop(term.span, "break"))
-                }
+                let depth = sess.check_loop_control_depth(term, "break")?;
+
+                let frame_index = sess.loop_frames.len() - depth;
+                sess.loop_frames[frame_index].has_break = true;
+
+                Ok(hir::Node::Control(hir::Control::Break(hir::LoopControl {
+                    depth,
+                    ty: sess.tcx.common_types.never,
+                    span: term.span,
+                })))
             }
             ast::Ast::Continue(term) => {
-                if sess.loop_depth > 0 {
-                    Ok(hir::Node::Control(hir::Control::Continue(hir::Empty {
-                        ty: sess.tcx.common_types.never,
-                        span: term.span,
-                    })))
-                } else {
-                    Err(SyntaxError::outside_of_loop(term.span, "continue"))
-                }
+                let depth = sess.check_loop_control_depth(term, "continue")?;
+
+                Ok(hir::Node::Control(hir::Control::Continue(hir::LoopControl {
+                    depth,
+                    ty: sess.tcx.common_types.never,
+                    span: term.span,
+                })))
             }
             ast::Ast::Return(return_) => return_.check(sess, env, expected_type),
             ast::Ast::If(if_) => if_.check(sess, env, expected_type),
@@ -1185,7 +2082,7 @@ impl Check for ast::Ast {
                                 module_id: env.module_id(),
                                 span: access.member_span,
                             },
-                            true,
+                            None,
                         )
                     }
                     _ => (),
@@ -1228,10 +2125,10 @@ impl Check for ast::Ast {
                                     }
                                 }
                                 None => Err(TypeError::tuple_field_out_of_bounds(
-                                    access.expr.span(),
-                                    &access.member,
+                                    access.member_span,
+                                    index,
                                     ty.display(&sess.tcx),
-                                    elements.len() - 1,
+                                    elements.len(),
                                 )),
                             },
                             Err(_) => Err(TypeError::non_numeric_tuple_field(
@@ -1243,6 +2140,8 @@ impl Check for ast::Ast {
                     }
                     ty @ Type::Struct(st) => match st.field_and_position(access.member) {
                         Some((index, field)) => {
+                            sess.validate_field_vis(env, st, field, access.member_span)?;
+
                             let ty = sess.tcx.bound(field.ty.clone(), access.span);
 
                             if let Some(ConstValue::Struct(const_fields)) = node.as_const_value() {
@@ -1334,7 +2233,7 @@ impl Check for ast::Ast {
                                     module_id: env.module_id(),
                                     span: ident.span,
                                 },
-                                false,
+                                Some(env),
                             )
                         }
                     }
@@ -1457,21 +2356,45 @@ impl Check for ast::Ast {
                 },
             },
             ast::Ast::Literal(lit) => {
-                let const_value: ConstValue = lit.kind.into();
-
-                let ty = match &lit.kind {
-                    ast::LiteralKind::Nil => sess.tcx.var(lit.span),
-                    ast::LiteralKind::Bool(_) => sess.tcx.common_types.bool,
-                    ast::LiteralKind::Int(_) => sess.tcx.anyint(lit.span),
-                    ast::LiteralKind::Float(_) => sess.tcx.anyfloat(lit.span),
-                    ast::LiteralKind::Str(_) => sess.tcx.common_types.str_pointer,
-                    ast::LiteralKind::Char(_) => sess.tcx.common_types.u8,
+                let (value, ty) = match lit.kind {
+                    ast::LiteralKind::Nil => {
+                        return Err(Diagnostic::error()
+                            .with_message("`nil` is deprecated")
+                            .with_label(Label::primary(lit.span, "deprecated literal"))
+                            .with_note("use `null` for a null pointer, or `()` for the unit value"))
+                    }
+                    ast::LiteralKind::Null => {
+                        // `null` is a zero address, casted to the expected pointer type
+                        let ty = match expected_type {
+                            Some(ty) if ty.normalize(&sess.tcx).is_pointer() => ty,
+                            _ => {
+                                let pointee = sess.tcx.var(lit.span);
+                                sess.tcx
+                                    .bound(Type::Pointer(Box::new(pointee.as_kind()), true), lit.span)
+                            }
+                        };
+
+                        return Ok(hir::Node::Cast(hir::Cast {
+                            value: Box::new(hir::Node::Const(hir::Const {
+                                value: ConstValue::Int(0),
+                                ty: sess.tcx.common_types.uint,
+                                span: lit.span,
+                            })),
+                            ty,
+                            span: lit.span,
+                        }));
+                    }
+                    ast::LiteralKind::Bool(v) => (ConstValue::Bool(v), sess.tcx.common_types.bool),
+                    ast::LiteralKind::Int(v) => (ConstValue::Int(v), sess.tcx.anyint(lit.span)),
+                    ast::LiteralKind::Float(v) => (ConstValue::Float(v), sess.tcx.anyfloat(lit.span)),
+                    ast::LiteralKind::Str(v) => (ConstValue::Str(v), sess.tcx.common_types.str_pointer),
+                    ast::LiteralKind::Char(v) => (ConstValue::Int(v as i128), sess.tcx.common_types.u8),
                 };
 
                 Ok(hir::Node::Const(hir::Const {
                     ty,
                     span: lit.span,
-                    value: const_value,
+                    value,
                 }))
             }
             ast::Ast::PointerType(ast::PointerType {
@@ -1514,6 +2437,7 @@ impl Check for ast::Ast {
                 }
 
                 let array_type = Type::Array(Box::new(inner_type.into()), size_value as usize);
+                sess.require_array_size(&array_type, &inner_type_norm, size_value, *span)?;
 
                 Ok(hir::Node::Const(hir::Const {
                     ty: sess.tcx.bound(array_type.clone().create_type(), *span),
@@ -1595,13 +2519,20 @@ impl Check for ast::Ast {
 impl Check for ast::Loop {
     fn check(&self, sess: &mut CheckSess, env: &mut Env, _expected_type: Option<TypeId>) -> CheckResult {
         env.push_scope(ScopeKind::Loop);
-        sess.loop_depth += 1;
+        sess.loop_frames.push(LoopFrame::default());
 
         let block_node = self.block.check(sess, env, None)?;
 
-        sess.loop_depth -= 1;
+        let loop_frame = sess.loop_frames.pop().unwrap();
         env.pop_scope();
 
+        // a loop only produces a value (unit) if it is broken out of
+        let loop_node_type = if loop_frame.has_break {
+            sess.tcx.common_types.unit
+        } else {
+            sess.tcx.common_types.never
+        };
+
         Ok(hir::Node::Control(hir::Control::While(hir::While {
             condition: Box::new(hir::Node::Const(hir::Const {
                 value: ConstValue::Bool(true),
@@ -1609,7 +2540,7 @@ impl Check for ast::Loop {
                 span: self.span,
             })),
             body: Box::new(block_node),
-            ty: sess.tcx.common_types.never,
+            ty: loop_node_type,
             span: self.span,
         })))
     }
@@ -1632,15 +2563,17 @@ impl Check for ast::While {
             .or_report_err(&sess.tcx, &bool_type, None, &condition_node.ty(), self.condition.span())?;
 
         env.push_scope(ScopeKind::Loop);
-        sess.loop_depth += 1;
+        sess.loop_frames.push(LoopFrame::default());
 
         let block_node = self.block.check(sess, env, None)?;
 
-        sess.loop_depth -= 1;
+        let loop_frame = sess.loop_frames.pop().unwrap();
         env.pop_scope();
 
+        // like an `if` without an `else`, a while loop evaluates to unit.
+        // the only exception is an infinite loop that is never broken out of
         let while_node_type = match condition_node.as_const_value() {
-            Some(ConstValue::Bool(true)) => sess.tcx.common_types.never,
+            Some(ConstValue::Bool(true)) if !loop_frame.has_break => sess.tcx.common_types.never,
             _ => sess.tcx.common_types.unit,
         };
 
@@ -1710,7 +2643,7 @@ impl Check for ast::For {
                     )?;
 
                 env.push_scope(ScopeKind::Loop);
-                sess.loop_depth += 1;
+                sess.loop_frames.push(LoopFrame::default());
 
                 let mut statements: Vec<hir::Node> = vec![];
 
@@ -1820,7 +2753,7 @@ impl Check for ast::For {
                     span: self.span,
                 }));
 
-                sess.loop_depth -= 1;
+                sess.loop_frames.pop();
                 env.pop_scope();
 
                 statements.push(hir::Node::Control(hir::Control::While(hir::While {
@@ -1880,7 +2813,7 @@ impl Check for ast::For {
                 };
 
                 env.push_scope(ScopeKind::Loop);
-                sess.loop_depth += 1;
+                sess.loop_frames.push(LoopFrame::default());
 
                 let mut statements: Vec<hir::Node> = vec![];
 
@@ -1997,7 +2930,7 @@ impl Check for ast::For {
                     span: self.span,
                 }));
 
-                sess.loop_depth -= 1;
+                sess.loop_frames.pop();
                 env.pop_scope();
 
                 statements.push(hir::Node::Control(hir::Control::While(hir::While {
@@ -2258,7 +3191,30 @@ impl Check for ast::Binary {
 
         let lhs_node_type = lhs_node.ty().normalize(&sess.tcx);
 
-        let expected_rhs_type = match lhs_node_type {
+        // Arrays of numbers can be added, subtracted and multiplied element-wise
+        let is_element_wise = matches!(lhs_node_type, Type::Array(..))
+            && matches!(
+                self.op,
+                ast::BinaryOp::Add
+                    | ast::BinaryOp::Sub
+                    | ast::BinaryOp::Mul
+                    | ast::BinaryOp::AddAssign
+                    | ast::BinaryOp::SubAssign
+                    | ast::BinaryOp::MulAssign
+            );
+
+        let expected_rhs_type = match &lhs_node_type {
+            Type::Array(element_type, _) if is_element_wise => {
+                if !element_type.is_number() && !element_type.is_anyfloat() {
+                    return Err(TypeError::expected(
+                        lhs_node.span(),
+                        lhs_node_type.display(&sess.tcx),
+                        "an array of numbers",
+                    ));
+                }
+
+                lhs_node.ty()
+            }
             Type::Pointer(..) => match &self.op {
                 ast::BinaryOp::Add | ast::BinaryOp::Sub => sess.tcx.common_types.int,
                 ast::BinaryOp::Eq
@@ -2335,6 +3291,17 @@ impl Check for ast::Binary {
             )
             .or_report_err(&sess.tcx, &expected_rhs_type, None, &rhs_node.ty(), self.rhs.span())?;
 
+        if matches!(self.op, ast::BinaryOp::Eq | ast::BinaryOp::Ne) {
+            let ty = lhs_node.ty().normalize(&sess.tcx);
+
+            if ty.is_aggregate() && !ty.is_bytewise_comparable() {
+                return Err(Diagnostic::error()
+                    .with_message(format!("can't compare values of type `{}`", ty.display(&sess.tcx)))
+                    .with_label(Label::primary(self.span, "invalid comparison"))
+                    .with_note("only arrays of integers, booleans and pointers can be compared"));
+            }
+        }
+
         let result_type = match lhs_node_type {
             Type::Pointer(..) => match &self.op {
                 ast::BinaryOp::Add | ast::BinaryOp::Sub => lhs_node.ty(),
@@ -2384,7 +3351,7 @@ impl Check for ast::Binary {
         };
 
         match (lhs_node.as_const_value(), rhs_node.as_const_value()) {
-            (Some(lhs), Some(rhs)) if const_fold::is_valid_binary_op(self.op) => {
+            (Some(lhs), Some(rhs)) if const_fold::is_valid_binary_op(self.op) && !is_element_wise => {
                 let const_value = const_fold::binary(lhs, rhs, self.op, self.span, &sess.tcx)?;
 
                 // println!(
@@ -2580,6 +3547,18 @@ impl Check for ast::Unary {
                     .or_else(|_| node_type.unify(&anyint, &mut sess.tcx))
                     .or_report_err(&sess.tcx, &bool, None, &node_type, self.value.span())?;
 
+                // Since `anyint` also unifies with floats, we need to explicitly reject them here
+                let node_type_norm = node_type.normalize(&sess.tcx);
+
+                if node_type_norm.is_float() || node_type_norm.is_anyfloat() {
+                    return Err(Diagnostic::error()
+                        .with_message(format!(
+                            "operator `!` cannot be applied to type `{}`",
+                            node_type_norm.display(&sess.tcx)
+                        ))
+                        .with_label(Label::primary(self.value.span(), "")));
+                }
+
                 if let Some(const_value) = node.as_const_value() {
                     Ok(hir::Node::Const(hir::Const {
                         value: const_value.not(),
@@ -2608,6 +3587,16 @@ impl Check for ast::Unary {
                     self.value.span(),
                 )?;
 
+                if let Type::Uint(_) = node_type.normalize(&sess.tcx) {
+                    return Err(Diagnostic::error()
+                        .with_message(format!(
+                            "cannot negate a value of unsigned type `{}`",
+                            node_type.display(&sess.tcx)
+                        ))
+                        .with_label(Label::primary(self.span, "cannot be negated"))
+                        .with_note("unsigned values cannot be negative"));
+                }
+
                 if let Some(const_value) = node.as_const_value() {
                     Ok(hir::Node::Const(hir::Const {
                         value: const_value.neg(),
@@ -3136,10 +4125,24 @@ impl Check for ast::TupleLiteral {
             }));
         }
 
+        // when the expected type is a tuple of the same length, each element is checked
+        // against its corresponding expected element type
+        let expected_element_types = expected_type.and_then(|ty| match ty.normalize(&sess.tcx) {
+            Type::Tuple(element_types) if element_types.len() == self.elements.len() => Some(element_types),
+            _ => None,
+        });
+
         let elements = self
             .elements
             .iter()
-            .map(|el| el.check(sess, env, None))
+            .enumerate()
+            .map(|(index, el)| {
+                let expected_element_type = expected_element_types
+                    .as_ref()
+                    .map(|element_types| sess.tcx.bound(element_types[index].clone(), el.span()));
+
+                el.check(sess, env, expected_element_type)
+            })
             .collect::<DiagnosticResult<Vec<_>>>()?;
 
         let is_const_tuple = elements.iter().all(|node| node.is_const());
@@ -3290,6 +4293,7 @@ impl Check for ast::StructType {
             struct_type_fields.push(StructTypeField {
                 name: field.name,
                 ty: ty.into(),
+                vis: field.vis,
                 span: field.span,
             });
         }
@@ -3314,23 +4318,28 @@ impl Check for ast::StructType {
             }
         }
 
+        // A struct that contains itself by value, rather than behind a pointer, would have an infinite size
+        if let Some(field) = struct_type_fields
+            .iter()
+            .find(|field| contains_type_by_value(struct_type_var, &field.ty, &sess.tcx))
+        {
+            return Err(Diagnostic::error()
+                .with_message(format!("recursive type `{}` has infinite size", name))
+                .with_label(Label::primary(field.span, "recursive without indirection"))
+                .with_note(format!(
+                    "use a pointer to make `{}` representable, i.e: `{}: *{}`",
+                    name, field.name, name
+                )));
+        }
+
         let struct_type = Type::Struct(StructType {
             name,
             id,
             kind: self.kind,
             fields: struct_type_fields,
+            align: None,
         });
 
-        if occurs(struct_type_var, &struct_type, &sess.tcx) {
-            return Err(UnifyTypeErr::Occurs.into_diagnostic(
-                &sess.tcx,
-                &struct_type,
-                None,
-                &struct_type_var,
-                self.span,
-            ));
-        }
-
         Ok(hir::Node::Const(hir::Const {
             ty: sess.tcx.bound(struct_type.clone().create_type(), self.span),
             span: self.span,
@@ -3362,6 +4371,8 @@ fn check_named_struct_literal(
 
         match struct_ty.field(field.name) {
             Some(ty_field) => {
+                sess.validate_field_vis(env, &struct_ty, ty_field, field.span)?;
+
                 uninit_fields.remove(&field.name);
 
                 let expected_type = sess.tcx.bound(ty_field.ty.clone(), ty_field.span);
@@ -3434,6 +4445,7 @@ fn check_anonymous_struct_literal(
         id: None,
         kind: StructTypeKind::Struct,
         fields: vec![],
+        align: None,
     };
 
     for field in fields.iter() {
@@ -3449,6 +4461,7 @@ fn check_anonymous_struct_literal(
         struct_ty.fields.push(StructTypeField {
             name: field.name,
             ty: node.ty().into(),
+            vis: ast::Vis::Public,
             span: field.span,
         });
 
@@ -3502,6 +4515,24 @@ fn get_anonymous_struct_name(span: Span) -> Ustr {
     ustr(&format!("struct:{}:{}", span.start.line, span.start.column))
 }
 
+// Returns whether `ty` stores a value of the type `var` inline, making `var`'s size depend on itself.
+// Pointers, slices and functions break the cycle, since they only refer to their inner type
+fn contains_type_by_value(var: TypeId, ty: &Type, tcx: &TypeCtx) -> bool {
+    match ty {
+        &Type::Var(other) => {
+            var == other
+                || match tcx.value_of(other) {
+                    InferenceValue::Bound(ty) => contains_type_by_value(var, ty, tcx),
+                    InferenceValue::AnyInt | InferenceValue::AnyFloat | InferenceValue::Unbound => false,
+                }
+        }
+        Type::Array(inner, _) => contains_type_by_value(var, inner, tcx),
+        Type::Tuple(elements) => elements.iter().any(|ty| contains_type_by_value(var, ty, tcx)),
+        Type::Struct(st) => st.fields.iter().any(|field| contains_type_by_value(var, &field.ty, tcx)),
+        _ => false,
+    }
+}
+
 pub(super) fn check_optional_type_expr<'s>(
     type_expr: &Option<Box<ast::Ast>>,
     sess: &mut CheckSess<'s>,
@@ -16,7 +16,7 @@ use crate::{
     infer::{display::DisplayType, normalize::Normalize},
     span::Span,
     sym,
-    types::{Type, TypeId},
+    types::{StructType, Type, TypeId},
     workspace::{BindingId, BindingInfoFlags, BindingInfoKind, ModuleId, PartialBindingInfo, ScopeLevel},
 };
 use ustr::{ustr, Ustr, UstrMap};
@@ -331,7 +331,7 @@ impl<'s> CheckSess<'s> {
                                 span: pat.span,
                             };
 
-                            let node = self.check_top_level_name(pat.name, module_id, caller_info, true)?;
+                            let node = self.check_top_level_name(pat.name, module_id, caller_info, None)?;
 
                             let pat = match (pat.name.as_str(), node.ty().normalize(&self.tcx)) {
                                 (sym::SELF | sym::SUPER, Type::Module(module_id)) => NamePat {
@@ -361,7 +361,7 @@ impl<'s> CheckSess<'s> {
                                 span,
                             };
 
-                            let node = self.check_top_level_name(name, module_id, caller_info, true)?;
+                            let node = self.check_top_level_name(name, module_id, caller_info, None)?;
 
                             let (_, binding) = self.bind_pat(
                                 env,
@@ -418,6 +418,8 @@ impl<'s> CheckSess<'s> {
                     let span = pat.span();
 
                     if let Some(field) = struct_type.field(name) {
+                        self.validate_field_vis(env, &struct_type, field, span)?;
+
                         let ty = self.tcx.bound(field.ty.clone(), span);
 
                         let field_value = match value.as_const_value() {
@@ -480,6 +482,11 @@ impl<'s> CheckSess<'s> {
                             continue;
                         }
 
+                        // skip private fields of structs defined in other modules
+                        if self.validate_field_vis(env, &struct_type, field, glob.span).is_err() {
+                            continue;
+                        }
+
                         let ty = self.tcx.bound(field.ty.clone(), field.span);
 
                         let field_value = match value.as_const_value() {
@@ -511,6 +518,8 @@ impl<'s> CheckSess<'s> {
 
                         statements.push(bound_node);
                     }
+                } else if self.workspace.build_options.warn_partial_unpack {
+                    self.warn_unbound_struct_fields(env, unpack_pat, &struct_type);
                 }
 
                 Ok(())
@@ -521,6 +530,35 @@ impl<'s> CheckSess<'s> {
         }
     }
 
+    fn warn_unbound_struct_fields(&mut self, env: &Env, unpack_pat: &StructPat, struct_type: &StructType) {
+        let unbound_fields = struct_type
+            .fields
+            .iter()
+            .filter(|field| !unpack_pat.subpats.iter().any(|pat| pat.name() == field.name))
+            .filter(|field| {
+                self.validate_field_vis(env, struct_type, field, unpack_pat.span)
+                    .is_ok()
+            })
+            .map(|field| format!("`{}`", field.name))
+            .collect::<Vec<_>>();
+
+        if unbound_fields.is_empty() {
+            return;
+        }
+
+        self.workspace.diagnostics.push(
+            Diagnostic::warning()
+                .with_message(format!(
+                    "struct unpack of `{}` doesn't bind {} {}",
+                    struct_type.display(&self.tcx),
+                    if unbound_fields.len() == 1 { "field" } else { "fields" },
+                    unbound_fields.join(", ")
+                ))
+                .with_label(Label::primary(unpack_pat.span, "some fields are not bound"))
+                .with_note("add `*` to the pattern to bind the remaining fields by their names"),
+        );
+    }
+
     // fn find_name_in_module_bindings(
     //     &mut self,
     //     name: Ustr,
@@ -1,15 +1,23 @@
-use super::{sym, Check, CheckResult, CheckSess, QueuedModule};
+use super::{env::Env, sym, Check, CheckResult, CheckSess, QueuedModule};
 use crate::{
     ast,
-    error::diagnostic::{Diagnostic, Label},
+    common::fuzzy::find_similar_name,
+    error::{
+        diagnostic::{Diagnostic, Label},
+        TypeError,
+    },
     hir::{self, const_value::ConstValue},
-    infer::substitute::substitute_node,
+    infer::{
+        display::{DisplayType, OrReportErr},
+        substitute::substitute_node,
+        unify::UnifyType,
+    },
     span::Span,
-    types::{Type, TypeId},
+    types::{StructType, StructTypeField, Type, TypeId},
     workspace::{BindingId, ModuleId, ModuleInfo},
 };
 use std::collections::HashSet;
-use ustr::{Ustr, UstrMap};
+use ustr::{ustr, Ustr, UstrMap};
 
 #[derive(Debug, Clone, Copy)]
 pub struct CallerInfo {
@@ -23,7 +31,9 @@ impl<'s> CheckSess<'s> {
         name: Ustr,
         module_id: ModuleId,
         caller_info: CallerInfo,
-        is_other_module: bool,
+        // The caller's environment, when the name is looked up from the caller's own scope.
+        // This is `None` when the name is accessed through another module
+        env: Option<&Env>,
     ) -> CheckResult {
         // In general, top level names are searched in this order:
         // > 1. A binding in current module
@@ -53,8 +63,8 @@ impl<'s> CheckSess<'s> {
                         // Top level `super` module
                         sym::SUPER => self.super_node_module(&module.info, caller_info),
                         _ => {
-                            if is_other_module {
-                                return Err(self.name_not_found_error(module_id, name, caller_info));
+                            if env.is_none() {
+                                return Err(self.name_not_found_error(module_id, name, caller_info, None));
                             }
 
                             // A used library name
@@ -86,7 +96,7 @@ impl<'s> CheckSess<'s> {
                                 // Top level name in the `std` prelude
                                 result
                             } else {
-                                Err(self.name_not_found_error(module_id, name, caller_info))
+                                Err(self.name_not_found_error(module_id, name, caller_info, env))
                             }
                         }
                     }
@@ -121,7 +131,10 @@ impl<'s> CheckSess<'s> {
         module: &ast::Module,
         caller_info: CallerInfo,
     ) -> Option<CheckResult> {
-        let (index, binding) = module.find_binding(name)?;
+        let (index, binding) = match self.find_declared_binding(name, module) {
+            Ok(found) => found?,
+            Err(diag) => return Some(Err(diag)),
+        };
 
         // Check that this binding isn't cyclic
         if !self.encountered_items.insert((module.id, index)) {
@@ -156,10 +169,113 @@ impl<'s> CheckSess<'s> {
                     Err(diag) => Some(Err(diag)),
                 }
             }
-            Err(diag) => Some(Err(diag)),
+            Err(diag) => Some(Err(self.with_required_by_note(diag, name, caller_info))),
         }
     }
 
+    // Adds a "required by" note to an error that occurred while checking `name`,
+    // pointing at the top level binding that required it. As the error propagates
+    // through the chain of required bindings, the notes form a trail back to its origin.
+    fn with_required_by_note(&self, diag: Diagnostic, name: Ustr, caller_info: CallerInfo) -> Diagnostic {
+        let span = caller_info.span;
+
+        let location = match self.workspace.diagnostics.get_file(span.file_id) {
+            Some(file) => format!("{}:{}:{}", file.name(), span.start.line, span.start.column),
+            None => format!("{}:{}", span.start.line, span.start.column),
+        };
+
+        match self.top_level_stack.last() {
+            Some(required_by) => diag.with_note(format!(
+                "`{}` is required by `{}`, at {}",
+                name, required_by, location
+            )),
+            None => diag.with_note(format!("`{}` is required at {}", name, location)),
+        }
+    }
+
+    fn find_declared_binding<'m>(
+        &mut self,
+        name: Ustr,
+        module: &'m ast::Module,
+    ) -> CheckResult<Option<(usize, &'m ast::Binding)>> {
+        for (index, binding) in module.find_bindings(name) {
+            if self.is_binding_declared(module, index)? {
+                return Ok(Some((index, binding)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // A binding isn't declared if it is in an untaken branch of a `static if`
+    fn is_binding_declared(&mut self, module: &ast::Module, index: usize) -> CheckResult<bool> {
+        for (static_if_index, static_if) in module.static_ifs.iter().enumerate() {
+            let in_then = static_if.then_bindings.contains(&index);
+            let in_else = static_if.else_bindings.contains(&index);
+
+            if in_then || in_else {
+                let condition = self.eval_static_if_condition(module.id, static_if_index, static_if)?;
+
+                if condition != in_then {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn eval_static_if_condition(
+        &mut self,
+        module_id: ModuleId,
+        index: usize,
+        static_if: &ast::StaticIf,
+    ) -> CheckResult<bool> {
+        match self.static_if_conditions.get(&(module_id, index)) {
+            Some(Some(condition)) => return Ok(*condition),
+            Some(None) => {
+                return Err(Diagnostic::error()
+                    .with_message("cycle detected while evaluating the condition of `static if`")
+                    .with_label(Label::primary(
+                        static_if.condition.span(),
+                        "depends on a binding declared inside its own branches",
+                    )))
+            }
+            None => (),
+        }
+
+        self.static_if_conditions.insert((module_id, index), None);
+
+        let bool_type = self.tcx.common_types.bool;
+
+        let node = self.with_env(module_id, |sess, mut env| {
+            static_if.condition.check(sess, &mut env, Some(bool_type))
+        })?;
+
+        node.ty().unify(&bool_type, &mut self.tcx).or_report_err(
+            &self.tcx,
+            &bool_type,
+            None,
+            &node.ty(),
+            static_if.condition.span(),
+        )?;
+
+        let condition = match node.as_const_value() {
+            Some(ConstValue::Bool(condition)) => *condition,
+            _ => {
+                return Err(TypeError::expected(
+                    node.span(),
+                    node.ty().display(&self.tcx),
+                    "compile-time known bool",
+                ))
+            }
+        };
+
+        self.static_if_conditions.insert((module_id, index), Some(condition));
+
+        Ok(condition)
+    }
+
     fn check_name_in_std_prelude(&mut self, name: Ustr, caller_info: CallerInfo) -> Option<CheckResult> {
         let std_root_module_id = self.workspace.std_library().root_module_id;
 
@@ -176,7 +292,13 @@ impl<'s> CheckSess<'s> {
         }
     }
 
-    pub(super) fn name_not_found_error(&self, module_id: ModuleId, name: Ustr, caller_info: CallerInfo) -> Diagnostic {
+    pub(super) fn name_not_found_error(
+        &self,
+        module_id: ModuleId,
+        name: Ustr,
+        caller_info: CallerInfo,
+        env: Option<&Env>,
+    ) -> Diagnostic {
         let module_info = self.workspace.module_infos.get(module_id).unwrap();
 
         let message = if module_info.qualified_name.is_empty() {
@@ -194,9 +316,47 @@ impl<'s> CheckSess<'s> {
             format!("not found in `{}`", module_info.qualified_name)
         };
 
-        Diagnostic::error()
+        let diagnostic = Diagnostic::error()
             .with_message(message)
-            .with_label(Label::primary(caller_info.span, label_message))
+            .with_label(Label::primary(caller_info.span, label_message));
+
+        match self.find_similar_name_in_scope(module_id, name, caller_info, env) {
+            Some(suggestion) => diagnostic.with_note(format!("did you mean `{}`?", suggestion)),
+            None => diagnostic,
+        }
+    }
+
+    // Searches for a name similar to `name`, to suggest as a fix for a misspelling.
+    // When the name is searched for in the caller's own module, the `std` prelude is searched too,
+    // along with the local bindings visible from the caller's scope
+    fn find_similar_name_in_scope(
+        &self,
+        module_id: ModuleId,
+        name: Ustr,
+        caller_info: CallerInfo,
+        env: Option<&Env>,
+    ) -> Option<Ustr> {
+        let std_root_module_id = self.workspace.std_library().root_module_id;
+
+        let candidates = self
+            .modules
+            .iter()
+            .filter(|module| {
+                module.id == module_id || (module_id == caller_info.module_id && module.id == std_root_module_id)
+            })
+            .flat_map(|module| {
+                module.declared_binding_names(|index| {
+                    self.static_if_conditions.get(&(module.id, index)).copied().flatten()
+                })
+            })
+            .chain(
+                env.into_iter()
+                    .flat_map(|env| env.local_names())
+                    .filter(|name| name.as_str() != "_"),
+            )
+            .collect::<Vec<_>>();
+
+        find_similar_name(&name, candidates.iter().map(|name| name.as_str())).map(ustr)
     }
 
     pub fn validate_item_vis(&self, id: BindingId, caller_info: CallerInfo) -> CheckResult<()> {
@@ -212,6 +372,32 @@ impl<'s> CheckSess<'s> {
         }
     }
 
+    pub fn validate_field_vis(
+        &self,
+        env: &Env,
+        struct_type: &StructType,
+        field: &StructTypeField,
+        span: Span,
+    ) -> CheckResult<()> {
+        // Anonymous structs aren't owned by any module, so their fields are always accessible
+        let struct_binding_info = match struct_type.id.and_then(|id| self.workspace.binding_infos.get(id)) {
+            Some(binding_info) => binding_info,
+            None => return Ok(()),
+        };
+
+        if field.vis == ast::Vis::Private && struct_binding_info.module_id != env.module_id() {
+            Err(Diagnostic::error()
+                .with_message(format!(
+                    "field `{}` of type `{}` is private",
+                    field.name, struct_binding_info.name
+                ))
+                .with_label(Label::primary(span, "accessed here"))
+                .with_label(Label::secondary(field.span, "defined here")))
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn check_module_by_id(&mut self, id: ModuleId) -> CheckResult<TypeId> {
         let module = self
             .modules
@@ -248,6 +434,10 @@ impl<'s> CheckSess<'s> {
             };
 
             for (index, binding) in module.bindings.iter().enumerate() {
+                if !self.is_binding_declared(module, index)? {
+                    continue;
+                }
+
                 if self
                     .queued_modules
                     .get_mut(&module.id)
@@ -354,7 +544,16 @@ where
 
 impl CheckTopLevel for ast::Binding {
     fn check_top_level(&self, sess: &mut CheckSess, module_id: ModuleId) -> CheckResult<UstrMap<BindingId>> {
-        let node = sess.with_env(module_id, |sess, mut env| self.check(sess, &mut env, None))?;
+        // a top level binding's expressions are nested from its own depth, even when it's checked from another binding
+        let expr_depth = std::mem::replace(&mut sess.expr_depth, 0);
+
+        sess.top_level_stack.push(self.debug_name());
+        let result = sess.with_env(module_id, |sess, mut env| self.check(sess, &mut env, None));
+        sess.top_level_stack.pop();
+
+        sess.expr_depth = expr_depth;
+
+        let node = result?;
 
         if let Err(mut diagnostics) = substitute_node(&node, &mut sess.tcx) {
             let last = diagnostics.pop().unwrap();
@@ -1,4 +1,5 @@
 use super::target::TargetPlatform;
+use crate::types::{IntType, Type, UintType};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
@@ -6,6 +7,9 @@ pub struct BuildOptions {
     /// The root source file input from the user
     pub source_file: PathBuf,
 
+    /// The contents of the root source file, when it isn't read from disk (e.g. when it's piped from stdin)
+    pub source_contents: Option<String>,
+
     /// Optional output file path
     pub output_file: Option<PathBuf>,
 
@@ -22,17 +26,59 @@ pub struct BuildOptions {
     /// Additional include paths
     pub include_paths: Vec<PathBuf>,
 
+    /// Additional object files passed to the linker
+    pub link_objects: Vec<PathBuf>,
+
+    /// Additional libraries passed to the linker
+    pub link_libraries: Vec<String>,
+
     /// Print timing information of every compiler pass
     pub emit_times: bool,
 
+    /// Print a breakdown of the time spent in each compiler phase, after compilation
+    pub print_timings: bool,
+
+    /// Write the untyped Ast into <output>.ast.txt
+    pub emit_ast: bool,
+
     /// Print the Hir into $CWD/hir.pretty.chl
     pub emit_hir: bool,
 
+    /// Write the Hir as JSON into <output>.hir.json
+    pub emit_hir_json: bool,
+
+    /// Write the Hir as a Graphviz graph into <output>.hir.dot
+    pub emit_hir_dot: bool,
+
     /// Print the bytecode into $CWD/vm.out
     pub emit_bytecode: bool,
 
+    /// Print the resolved imports of every module
+    pub print_imports: bool,
+
     /// Whether the workspace is running in check mode
     pub check_mode: bool,
+
+    /// Compile without an entry point, emitting an object file with the root module's public bindings
+    pub no_entry: bool,
+
+    /// Generate a startup function which runs every function annotated with `@test`, instead of calling `main`
+    pub test_runner: bool,
+
+    /// The maximum number of bound type variables followed during type unification
+    pub max_type_var_depth: usize,
+
+    /// The maximum depth of nested expressions
+    pub max_expr_depth: usize,
+
+    /// The type of integer literals in the main library whose type couldn't be inferred
+    pub default_int_type: DefaultIntType,
+
+    /// Warn about integer literals in the main library that fall back to `default_int_type`
+    pub warn_default_int: bool,
+
+    /// Warn about struct unpack patterns that don't bind all of the struct's fields, and have no `*` glob
+    pub warn_partial_unpack: bool,
 }
 
 impl BuildOptions {
@@ -40,8 +86,21 @@ impl BuildOptions {
         self.source_file.parent().unwrap()
     }
 
+    // The path of an emitted artifact, named after the output file (or the source file when there's none)
+    pub fn emit_path(&self, extension: &str) -> PathBuf {
+        self.output_file
+            .as_ref()
+            .unwrap_or(&self.source_file)
+            .with_extension(extension)
+    }
+
     pub fn need_entry_point_function(&self) -> bool {
-        matches!(self.codegen_options, CodegenOptions::Codegen { .. })
+        !self.test_runner && self.need_start_function()
+    }
+
+    pub fn need_start_function(&self) -> bool {
+        !self.no_entry
+            && matches!(self.codegen_options, CodegenOptions::Codegen { .. })
             && matches!(
                 self.target_platform,
                 TargetPlatform::Windows386
@@ -58,7 +117,7 @@ impl BuildOptions {
     }
 
     pub fn start_function_name(&self) -> Option<&'static str> {
-        self.need_entry_point_function().then(|| match &self.target_platform {
+        self.need_start_function().then(|| match &self.target_platform {
             TargetPlatform::Windows386
             | TargetPlatform::WindowsAmd64
             | TargetPlatform::Linux386
@@ -75,7 +134,38 @@ impl BuildOptions {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, clap::ArgEnum)]
+pub enum DefaultIntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    Int,
+    U8,
+    U16,
+    U32,
+    U64,
+    Uint,
+}
+
+impl DefaultIntType {
+    pub fn as_type(&self) -> Type {
+        match self {
+            DefaultIntType::I8 => Type::Int(IntType::I8),
+            DefaultIntType::I16 => Type::Int(IntType::I16),
+            DefaultIntType::I32 => Type::Int(IntType::I32),
+            DefaultIntType::I64 => Type::Int(IntType::I64),
+            DefaultIntType::Int => Type::Int(IntType::Int),
+            DefaultIntType::U8 => Type::Uint(UintType::U8),
+            DefaultIntType::U16 => Type::Uint(UintType::U16),
+            DefaultIntType::U32 => Type::Uint(UintType::U32),
+            DefaultIntType::U64 => Type::Uint(UintType::U64),
+            DefaultIntType::Uint => Type::Uint(UintType::Uint),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ArgEnum)]
 pub enum OptimizationLevel {
     Debug,
     Release,
@@ -101,15 +191,53 @@ pub enum DiagnosticOptions {
 
 #[derive(Debug, Clone)]
 pub enum CodegenOptions {
-    Codegen { emit_llvm_ir: bool },
+    // When `emit_obj` is set, the object file is the final output, and linking is skipped
+    // When `emit_asm` is set, an assembly file is written alongside the regular output
+    // When `emit_map` is set, a map of the emitted symbols is written alongside the regular output
+    // When `print_stats` is set, the number of emitted functions, blocks and instructions is printed after codegen
+    Codegen {
+        emit_llvm_ir: bool,
+        emit_asm: bool,
+        emit_obj: bool,
+        emit_map: bool,
+        print_stats: bool,
+    },
     Skip { emit_llvm_ir: bool },
 }
 
 impl CodegenOptions {
     pub(crate) fn emit_llvm_ir(&self) -> bool {
         match self {
-            CodegenOptions::Codegen { emit_llvm_ir } => *emit_llvm_ir,
+            CodegenOptions::Codegen { emit_llvm_ir, .. } => *emit_llvm_ir,
             CodegenOptions::Skip { emit_llvm_ir } => *emit_llvm_ir,
         }
     }
+
+    pub(crate) fn emit_asm(&self) -> bool {
+        match self {
+            CodegenOptions::Codegen { emit_asm, .. } => *emit_asm,
+            CodegenOptions::Skip { .. } => false,
+        }
+    }
+
+    pub(crate) fn emit_obj(&self) -> bool {
+        match self {
+            CodegenOptions::Codegen { emit_obj, .. } => *emit_obj,
+            CodegenOptions::Skip { .. } => false,
+        }
+    }
+
+    pub(crate) fn emit_map(&self) -> bool {
+        match self {
+            CodegenOptions::Codegen { emit_map, .. } => *emit_map,
+            CodegenOptions::Skip { .. } => false,
+        }
+    }
+
+    pub(crate) fn print_stats(&self) -> bool {
+        match self {
+            CodegenOptions::Codegen { print_stats, .. } => *print_stats,
+            CodegenOptions::Skip { .. } => false,
+        }
+    }
 }
@@ -0,0 +1,63 @@
+// Returns the candidate which is closest to `name`, if it is close enough to be a likely misspelling of it.
+// Names are compared by their Levenshtein distance, which is allowed to be up to a third of the name's length.
+pub fn find_similar_name<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (levenshtein_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|(a_distance, a), (b_distance, b)| a_distance.cmp(b_distance).then_with(|| a.cmp(b)))
+        .map(|(_, candidate)| candidate)
+}
+
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    let mut current_row = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current_row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_similar_name, levenshtein_distance};
+
+    #[test]
+    fn measures_edit_distance() {
+        assert_eq!(levenshtein_distance("counter", "counter"), 0);
+        assert_eq!(levenshtein_distance("countr", "counter"), 1);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggests_closest_name() {
+        let candidates = ["counter", "count", "main"];
+
+        assert_eq!(find_similar_name("countr", candidates), Some("counter"));
+        assert_eq!(find_similar_name("cout", candidates), Some("count"));
+    }
+
+    #[test]
+    fn ignores_distant_names() {
+        assert_eq!(find_similar_name("xyz", ["counter", "main"]), None);
+        assert_eq!(find_similar_name("main", ["main"]), None);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod build_options;
+pub mod fuzzy;
 pub mod id_cache;
 pub mod mem;
 pub mod path;
@@ -6,7 +7,7 @@ pub mod scopes;
 pub mod target;
 
 use colored::{Color, Colorize};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use stopwatch::Stopwatch as SW;
 
 pub struct Stopwatch<'s> {
@@ -53,6 +54,61 @@ impl<'s> Stopwatch<'s> {
     }
 }
 
+// The durations of the compiler's phases, collected when `--print-timings` is passed.
+// When disabled, phases are run without being measured.
+#[derive(Debug, Default, Clone)]
+pub struct PhaseTimings {
+    enabled: bool,
+    pub phases: Vec<(&'static str, Duration)>,
+}
+
+impl PhaseTimings {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, phases: vec![] }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn measure<T>(&mut self, label: &'static str, f: impl FnOnce() -> T) -> T {
+        if self.enabled {
+            let start = Instant::now();
+            let res = f();
+            self.phases.push((label, start.elapsed()));
+            res
+        } else {
+            f()
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    // Printed to stderr, so that it doesn't interfere with check mode's output
+    pub fn print(&self) {
+        let total = self.total();
+
+        for (label, duration) in self.phases.iter() {
+            let percent = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+
+            eprintln!(
+                "{:<16}{:>10.3}ms {:>6.1}%",
+                label.bold(),
+                duration.as_secs_f64() * 1000.0,
+                percent
+            );
+        }
+
+        eprintln!("{:<16}{:>10.3}ms", "total".bold(), total.as_secs_f64() * 1000.0);
+    }
+}
+
 #[macro_export]
 macro_rules! time {
     ($enabled:expr, $label:literal, $body:expr) => {{
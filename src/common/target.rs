@@ -190,9 +190,37 @@ impl TargetPlatform {
         match env::consts::OS {
             "linux" => Ok(Self::LinuxAmd64),
             "windows" => Ok(Self::WindowsAmd64),
+            "macos" => Ok(Self::DarwinAmd64),
             os => Err(os),
         }
     }
+
+    pub fn supports(&self, capability: TargetCapability) -> bool {
+        match capability {
+            TargetCapability::ProcessExit => {
+                !matches!(self, TargetPlatform::FreestandingWasm32 | TargetPlatform::JsWasm32)
+            }
+            TargetCapability::DebugTrap => !self.is_wasm(),
+        }
+    }
+
+    // The platforms that can currently be targeted by the compiler
+    pub fn supported() -> &'static [TargetPlatform] {
+        &[
+            TargetPlatform::LinuxAmd64,
+            TargetPlatform::WindowsAmd64,
+            TargetPlatform::DarwinAmd64,
+        ]
+    }
+}
+
+// A target-specific capability that some builtin functions rely on
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TargetCapability {
+    // Exiting the process with an exit code, i.e: libc's `exit`
+    ProcessExit,
+    // Trapping into an attached debugger
+    DebugTrap,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -205,6 +233,13 @@ pub struct TargetMetrics {
     pub target_data_layout: &'static str,
 }
 
+impl TargetMetrics {
+    // The largest size of a single object in the target's address space (equivalent to isize::MAX)
+    pub fn max_object_size(&self) -> u128 {
+        (1u128 << (self.word_size * 8 - 1)) - 1
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Os {
     Windows,
@@ -278,3 +313,30 @@ impl Endianness {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{TargetCapability, TargetPlatform};
+
+    #[test]
+    fn supported_targets_support_every_capability() {
+        for target in TargetPlatform::supported() {
+            assert!(
+                target.supports(TargetCapability::ProcessExit),
+                "{:?} can't exit",
+                target
+            );
+            assert!(target.supports(TargetCapability::DebugTrap), "{:?} can't trap", target);
+        }
+    }
+
+    #[test]
+    fn wasm_targets_lack_capabilities() {
+        assert!(!TargetPlatform::FreestandingWasm32.supports(TargetCapability::ProcessExit));
+        assert!(!TargetPlatform::JsWasm32.supports(TargetCapability::ProcessExit));
+        assert!(TargetPlatform::WasiWasm32.supports(TargetCapability::ProcessExit));
+
+        assert!(!TargetPlatform::FreestandingWasm32.supports(TargetCapability::DebugTrap));
+        assert!(!TargetPlatform::WasiWasm32.supports(TargetCapability::DebugTrap));
+    }
+}
@@ -1,26 +1,36 @@
 use crate::{
+    ast::{
+        self,
+        pat::{Pat, StructSubPat},
+    },
     astgen::AstGenerationStats,
     common::{
         build_options::{BuildOptions, CodegenOptions},
         path::{resolve_relative_path, RelativeTo},
-        Stopwatch,
+        PhaseTimings, Stopwatch,
     },
     error::diagnostic::Diagnostic,
     hir,
     infer::type_ctx::TypeCtx,
     time,
-    workspace::{library::Library, LibraryId, ModuleId, Workspace},
+    workspace::{library::Library, LibraryId, ModuleId, ScopeLevel, Workspace},
 };
 use colored::Colorize;
 use num_format::{Locale, ToFormattedString};
-use std::path::PathBuf;
-use ustr::ustr;
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+use ustr::{ustr, Ustr};
 
 pub struct StartWorkspaceResult {
     pub workspace: Workspace,
     pub tcx: Option<TypeCtx>,
     pub cache: Option<hir::Cache>,
     pub output_file: Option<PathBuf>,
+    pub timings: PhaseTimings,
 }
 
 impl StartWorkspaceResult {
@@ -30,6 +40,7 @@ impl StartWorkspaceResult {
             tcx: None,
             cache: None,
             output_file: None,
+            timings: PhaseTimings::default(),
         }
     }
 
@@ -39,6 +50,7 @@ impl StartWorkspaceResult {
             tcx: Some(tcx),
             cache: Some(cache),
             output_file: None,
+            timings: PhaseTimings::default(),
         }
     }
 
@@ -48,40 +60,40 @@ impl StartWorkspaceResult {
             tcx: Some(tcx),
             cache: Some(cache),
             output_file: Some(output_file),
+            timings: PhaseTimings::default(),
         }
     }
 }
 
 pub fn start_workspace(name: String, build_options: BuildOptions) -> StartWorkspaceResult {
-    // Set up workspace
-    let source_file = resolve_relative_path(&build_options.source_file, &RelativeTo::Cwd);
+    let mut timings = PhaseTimings::new(build_options.print_timings);
 
-    let main_library = Library {
-        id: LibraryId::unknown(),
-        name: ustr(&name),
-        root_file: source_file.clone(),
-        root_module_id: ModuleId::unknown(),
-        is_main: true,
-    };
+    let mut result = start_workspace_timed(name, build_options, &mut timings);
 
-    let mut workspace = Workspace::new(name, build_options, main_library);
+    if timings.is_enabled() {
+        timings.print();
+    }
 
-    let all_sw = workspace.build_options.emit_times.then(|| Stopwatch::start_new("time"));
+    result.timings = timings;
+    result
+}
 
-    // Check that root file exists
-    if !source_file.exists() {
-        workspace
-            .diagnostics
-            .push(Diagnostic::error().with_message(format!("file `{}` doesn't exist", source_file.display())));
+fn start_workspace_timed(
+    name: String,
+    build_options: BuildOptions,
+    timings: &mut PhaseTimings,
+) -> StartWorkspaceResult {
+    let mut workspace = new_workspace(name, build_options);
 
-        workspace.emit_diagnostics();
+    let all_sw = workspace.build_options.emit_times.then(|| Stopwatch::start_new("time"));
 
+    if !check_root_file_exists(&mut workspace) || !check_link_objects_exist(&mut workspace) {
         return StartWorkspaceResult::new_untyped(workspace);
     }
 
     // Parse all source files into ast's
     let (modules, stats) = time! { workspace.build_options.emit_times, "parse", {
-        crate::astgen::generate_ast(&mut workspace)
+        timings.measure("parse", || crate::astgen::generate_ast(&mut workspace))
     }};
 
     if workspace.diagnostics.has_errors() {
@@ -89,9 +101,17 @@ pub fn start_workspace(name: String, build_options: BuildOptions) -> StartWorksp
         return StartWorkspaceResult::new_untyped(workspace);
     }
 
+    if workspace.build_options.emit_ast {
+        let path = workspace.build_options.emit_path("ast.txt");
+
+        if let Err(diagnostic) = emit_to_file(&path, |file| write_ast(&modules, file, None)) {
+            workspace.diagnostics.push(diagnostic);
+        }
+    }
+
     // Type inference, type checking, static analysis, const folding, etc..
     let (cache, tcx) = time! { workspace.build_options.emit_times, "check", {
-        crate::check::check(&mut workspace, modules)
+        timings.measure("check", || crate::check::check(&mut workspace, &modules))
     }};
 
     if workspace.diagnostics.has_errors() {
@@ -99,13 +119,40 @@ pub fn start_workspace(name: String, build_options: BuildOptions) -> StartWorksp
         return StartWorkspaceResult::new_typed(workspace, tcx, cache);
     }
 
+    if workspace.build_options.print_imports {
+        let imports = collect_imports(&workspace, &modules, &cache);
+        print_imports(&workspace, &imports);
+    }
+
     if workspace.build_options.emit_hir {
-        hir::pretty::print(&cache, &workspace, &tcx);
+        let path = Path::new("hir.pretty.chl");
+
+        if let Err(diagnostic) = emit_to_file(path, |file| {
+            hir::pretty::print_to_writer(&cache, &workspace, &tcx, file, None, false)
+        }) {
+            workspace.diagnostics.push(diagnostic);
+        }
+    }
+
+    if workspace.build_options.emit_hir_json {
+        let path = workspace.build_options.emit_path("hir.json");
+
+        if let Err(diagnostic) = emit_to_file(&path, |file| hir::json::write(&cache, &workspace, &tcx, file)) {
+            workspace.diagnostics.push(diagnostic);
+        }
+    }
+
+    if workspace.build_options.emit_hir_dot {
+        let path = workspace.build_options.emit_path("hir.dot");
+
+        if let Err(diagnostic) = emit_to_file(&path, |file| hir::dot::write(&cache, &workspace, &tcx, file)) {
+            workspace.diagnostics.push(diagnostic);
+        }
     }
 
     // Lint - does auxillary checks which are not required for compilation
     time! { workspace.build_options.emit_times, "lint",
-        crate::lint::lint(&mut workspace, &tcx, &cache)
+        timings.measure("lint", || crate::lint::lint(&mut workspace, &tcx, &cache))
     }
 
     if workspace.diagnostics.has_errors() {
@@ -116,7 +163,16 @@ pub fn start_workspace(name: String, build_options: BuildOptions) -> StartWorksp
     // Code generation
     match &workspace.build_options.codegen_options {
         CodegenOptions::Codegen { .. } => {
-            let output_file = crate::backend::llvm::codegen(&workspace, &tcx, &cache);
+            let codegen_result = timings.measure("codegen", || crate::backend::llvm::codegen(&workspace, &tcx, &cache));
+
+            let output_file = match codegen_result {
+                Ok(output_file) => output_file,
+                Err(diagnostic) => {
+                    workspace.diagnostics.push(diagnostic);
+                    workspace.emit_diagnostics();
+                    return StartWorkspaceResult::new_typed(workspace, tcx, cache);
+                }
+            };
 
             if workspace.build_options.emit_times {
                 print_stats(stats, all_sw.unwrap().elapsed().as_millis());
@@ -134,6 +190,262 @@ pub fn start_workspace(name: String, build_options: BuildOptions) -> StartWorksp
     }
 }
 
+// Parses all source files into ast's, without checking them
+pub fn parse_workspace(name: String, build_options: BuildOptions) -> (Workspace, Vec<ast::Module>) {
+    let mut workspace = new_workspace(name, build_options);
+
+    if !check_root_file_exists(&mut workspace) {
+        return (workspace, vec![]);
+    }
+
+    let (modules, _) = crate::astgen::generate_ast(&mut workspace);
+
+    if workspace.diagnostics.has_errors() {
+        workspace.emit_diagnostics();
+    }
+
+    (workspace, modules)
+}
+
+// Prints the workspace's typed tree, or its untyped ast when `untyped` is set.
+// When `module_name` is specified, only that module's tree is printed.
+// When `show_spans` is set, the typed tree's nodes are printed with their source locations.
+pub fn print_ast(
+    name: String,
+    build_options: BuildOptions,
+    untyped: bool,
+    module_name: Option<&str>,
+    show_spans: bool,
+) {
+    if untyped {
+        let (mut workspace, modules) = parse_workspace(name, build_options);
+
+        if workspace.diagnostics.has_errors() {
+            return;
+        }
+
+        if !check_module_exists(&mut workspace, module_name) {
+            return;
+        }
+
+        if let Err(err) = write_ast(&modules, &mut io::stdout(), module_name) {
+            workspace
+                .diagnostics
+                .push(Diagnostic::error().with_message(format!("couldn't print the ast: {}", err)));
+
+            workspace.emit_diagnostics();
+        }
+    } else {
+        let mut result = start_workspace(name, build_options);
+
+        if result.workspace.diagnostics.has_errors() {
+            return;
+        }
+
+        if !check_module_exists(&mut result.workspace, module_name) {
+            return;
+        }
+
+        if let (Some(tcx), Some(cache)) = (&result.tcx, &result.cache) {
+            if let Err(err) = hir::pretty::print_to_stdout(cache, &result.workspace, tcx, module_name, show_spans) {
+                result
+                    .workspace
+                    .diagnostics
+                    .push(Diagnostic::error().with_message(format!("couldn't print the typed tree: {}", err)));
+
+                result.workspace.emit_diagnostics();
+            }
+        }
+    }
+}
+
+// Writes the untyped ast of every module to `writer`.
+// When `module_name` is specified, only that module's ast is written.
+pub fn write_ast(modules: &[ast::Module], writer: &mut dyn Write, module_name: Option<&str>) -> io::Result<()> {
+    modules
+        .iter()
+        .filter(|module| module_name.map_or(true, |name| module.info.qualified_name.as_str() == name))
+        .try_for_each(|module| writeln!(writer, "{:#?}", module))
+}
+
+// Creates the file at `path` and writes an emitted artifact into it
+fn emit_to_file(path: &Path, write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> Result<(), Diagnostic> {
+    std::fs::File::create(path)
+        .and_then(|mut file| write(&mut file))
+        .map_err(|err| Diagnostic::error().with_message(format!("couldn't write `{}`: {}", path.display(), err)))
+}
+
+fn new_workspace(name: String, build_options: BuildOptions) -> Workspace {
+    let source_file = resolve_relative_path(&build_options.source_file, &RelativeTo::Cwd);
+
+    let main_library = Library {
+        id: LibraryId::unknown(),
+        name: ustr(&name),
+        root_file: source_file,
+        root_module_id: ModuleId::unknown(),
+        is_main: true,
+    };
+
+    Workspace::new(name, build_options, main_library)
+}
+
+fn check_root_file_exists(workspace: &mut Workspace) -> bool {
+    let root_file = workspace.main_library().root_file.clone();
+
+    if root_file.exists() || workspace.build_options.source_contents.is_some() {
+        true
+    } else {
+        workspace
+            .diagnostics
+            .push(Diagnostic::error().with_message(format!("file `{}` doesn't exist", root_file.display())));
+
+        workspace.emit_diagnostics();
+
+        false
+    }
+}
+
+fn check_link_objects_exist(workspace: &mut Workspace) -> bool {
+    let missing_objects: Vec<_> = workspace
+        .build_options
+        .link_objects
+        .iter()
+        .filter(|path| !path.exists())
+        .cloned()
+        .collect();
+
+    for path in missing_objects.iter() {
+        workspace
+            .diagnostics
+            .push(Diagnostic::error().with_message(format!("object file `{}` doesn't exist", path.display())));
+    }
+
+    if missing_objects.is_empty() {
+        true
+    } else {
+        workspace.emit_diagnostics();
+        false
+    }
+}
+
+fn check_module_exists(workspace: &mut Workspace, module_name: Option<&str>) -> bool {
+    match module_name {
+        Some(name) if !workspace.module_infos.iter().any(|(_, m)| m.qualified_name.as_str() == name) => {
+            workspace
+                .diagnostics
+                .push(Diagnostic::error().with_message(format!("module `{}` doesn't exist", name)));
+
+            workspace.emit_diagnostics();
+
+            false
+        }
+        _ => true,
+    }
+}
+
+// A checked workspace which is kept alive, so that multiple IDE queries
+// can be answered against the same type context, without re-checking the workspace.
+pub struct IdeSession {
+    result: StartWorkspaceResult,
+}
+
+impl IdeSession {
+    pub fn start(name: String, build_options: BuildOptions) -> Self {
+        Self::from_result(start_workspace(name, build_options))
+    }
+
+    pub fn from_result(result: StartWorkspaceResult) -> Self {
+        Self { result }
+    }
+
+    pub fn result(&self) -> &StartWorkspaceResult {
+        &self.result
+    }
+
+    pub fn diagnostics(&self) {
+        crate::ide::diagnostics(&self.result.workspace, self.result.tcx.as_ref(), self.result.cache.as_ref());
+    }
+
+    pub fn hover_info(&self, offset: usize) {
+        crate::ide::hover_info(&self.result.workspace, self.result.tcx.as_ref(), offset);
+    }
+
+    pub fn goto_definition(&self, offset: usize) {
+        crate::ide::goto_definition(&self.result.workspace, self.result.tcx.as_ref(), offset);
+    }
+
+    pub fn symbol_index(&self) {
+        crate::ide::symbol_index(&self.result.workspace, self.result.tcx.as_ref());
+    }
+}
+
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Checks the workspace, and re-checks it whenever one of its source files changes, printing its diagnostics each time.
+// Files are polled for changes, so a file which is deleted and later recreated is picked up again.
+pub fn watch(name: String, build_options: BuildOptions) {
+    loop {
+        let session = IdeSession::start(name.clone(), build_options.clone());
+        session.diagnostics();
+
+        // the root file is always watched, even when it failed to load
+        let watched_files = std::iter::once(build_options.source_file.clone())
+            .chain(
+                session
+                    .result()
+                    .workspace
+                    .module_infos
+                    .iter()
+                    .map(|(_, module_info)| PathBuf::from(module_info.file_path.as_str())),
+            )
+            .collect::<Vec<_>>();
+
+        let last_modified = modification_times(&watched_files);
+
+        while modification_times(&watched_files) == last_modified {
+            std::thread::sleep(WATCH_POLL_INTERVAL);
+        }
+    }
+}
+
+// A missing file has no modification time, so deleting and recreating a file both count as changes
+fn modification_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| std::fs::metadata(file).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+// Compiles the workspace into an executable whose entry point runs each function annotated with `@test`,
+// and runs it. The executable reports whether each test passed. Returns whether all tests passed.
+pub fn test(name: String, build_options: BuildOptions) -> bool {
+    let StartWorkspaceResult {
+        mut workspace,
+        output_file,
+        ..
+    } = start_workspace(name, build_options);
+
+    let output_file = match output_file {
+        Some(output_file) if !workspace.diagnostics.has_errors() => output_file,
+        _ => return false,
+    };
+
+    let status = Command::new(&output_file).status();
+
+    let _ = std::fs::remove_file(&output_file);
+
+    match status {
+        Ok(status) => status.success(),
+        Err(err) => {
+            workspace.diagnostics.push(
+                Diagnostic::error().with_message(format!("couldn't run `{}`: {}", output_file.display(), err)),
+            );
+            workspace.emit_diagnostics();
+            false
+        }
+    }
+}
+
 fn print_stats(stats: AstGenerationStats, elapsed_ms: u128) {
     println!("------------------------");
     println!(
@@ -143,3 +455,182 @@ fn print_stats(stats: AstGenerationStats, elapsed_ms: u128) {
     );
     println!("{}\t{}m", "time:".cyan().bold(), elapsed_ms);
 }
+
+// A single imported name, and the module it was resolved to
+struct ImportEntry {
+    module_id: ModuleId,
+    target: Ustr,
+    path: Vec<Ustr>,
+    alias: Option<Ustr>,
+}
+
+fn collect_imports(workspace: &Workspace, modules: &[ast::Module], cache: &hir::Cache) -> Vec<ImportEntry> {
+    let mut imports = vec![];
+
+    for module in modules.iter() {
+        for (index, binding) in module.bindings.iter().enumerate() {
+            // imports in untaken branches of `static if`s are never declared
+            let is_declared = module.is_binding_in_taken_branch(index, |static_if_index| {
+                cache.static_if_conditions.get(&(module.id, static_if_index)).copied()
+            });
+
+            if !is_declared {
+                continue;
+            }
+
+            if let ast::BindingKind::Let { pat, value, .. } = &binding.kind {
+                if let ast::Ast::Import(import) = value.as_ref() {
+                    let import_path = import.path.to_str().unwrap();
+
+                    let target = workspace
+                        .module_infos
+                        .iter()
+                        .find(|(_, m)| m.file_path == import_path)
+                        .map(|(_, m)| m.qualified_name);
+
+                    if let Some(target) = target {
+                        collect_import_pat(&mut imports, module.id, target, vec![], pat);
+                    }
+                }
+            }
+        }
+    }
+
+    imports
+}
+
+fn collect_import_pat(
+    imports: &mut Vec<ImportEntry>,
+    module_id: ModuleId,
+    target: Ustr,
+    path: Vec<Ustr>,
+    pat: &Pat,
+) {
+    match pat {
+        Pat::Name(pat) => imports.push(ImportEntry {
+            module_id,
+            target,
+            path,
+            alias: Some(pat.name),
+        }),
+        Pat::Struct(pat) => {
+            for subpat in pat.subpats.iter() {
+                match subpat {
+                    StructSubPat::Name(name_pat) => {
+                        let mut path = path.clone();
+                        path.push(name_pat.name);
+                        collect_import_pat(imports, module_id, target, path, &Pat::Name(name_pat.clone()));
+                    }
+                    StructSubPat::NameAndPat(name, pat) => {
+                        let mut path = path.clone();
+                        path.push(name.name);
+                        collect_import_pat(imports, module_id, target, path, pat);
+                    }
+                }
+            }
+
+            if pat.glob.is_some() {
+                imports.push(ImportEntry {
+                    module_id,
+                    target,
+                    path,
+                    alias: None,
+                });
+            }
+        }
+        Pat::Tuple(_) | Pat::Hybrid(_) => (),
+    }
+}
+
+fn print_imports(workspace: &Workspace, imports: &[ImportEntry]) {
+    for (index, module_info) in workspace.module_infos.iter() {
+        let module_id = ModuleId::from(index);
+        let module_imports: Vec<&ImportEntry> = imports.iter().filter(|i| i.module_id == module_id).collect();
+
+        if module_imports.is_empty() {
+            continue;
+        }
+
+        println!("{} `{}`", "module".cyan().bold(), module_info.qualified_name);
+
+        for import in module_imports {
+            let full_path = std::iter::once(import.target.as_str())
+                .chain(import.path.iter().map(|p| p.as_str()))
+                .collect::<Vec<&str>>()
+                .join(".");
+
+            match import.alias {
+                Some(alias) => println!("    use {} as {}", full_path, alias),
+                None => {
+                    // A glob import is expanded into all of the public bindings in the target module
+                    let names = workspace
+                        .module_infos
+                        .iter()
+                        .find(|(_, m)| m.qualified_name == full_path.as_str())
+                        .map(|(target_index, _)| {
+                            let target_id = ModuleId::from(target_index);
+
+                            let mut names: Vec<&str> = workspace
+                                .binding_infos
+                                .iter()
+                                .filter(|(_, b)| {
+                                    b.module_id == target_id
+                                        && b.scope_level == ScopeLevel::Global
+                                        && b.vis == ast::Vis::Public
+                                })
+                                .map(|(_, b)| b.name.as_str())
+                                .collect();
+
+                            names.sort();
+                            names.dedup();
+                            names
+                        })
+                        .unwrap_or_default();
+
+                    println!("    use {}.* (glob, expanded into: {})", full_path, names.join(", "));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_ast;
+    use crate::{ast, workspace::ModuleInfo};
+    use ustr::ustr;
+
+    fn module(qualified_name: &str) -> ast::Module {
+        ast::Module::new(
+            0,
+            ModuleInfo {
+                qualified_name: ustr(qualified_name),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn writes_ast_into_buffer() {
+        let modules = [module("demo"), module("demo.other")];
+        let mut buffer: Vec<u8> = vec![];
+
+        write_ast(&modules, &mut buffer, None).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("\"demo\""), "got: {}", output);
+        assert!(output.contains("\"demo.other\""), "got: {}", output);
+    }
+
+    #[test]
+    fn writes_only_the_requested_module() {
+        let modules = [module("demo"), module("demo.other")];
+        let mut buffer: Vec<u8> = vec![];
+
+        write_ast(&modules, &mut buffer, Some("demo")).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("\"demo\""), "got: {}", output);
+        assert!(!output.contains("demo.other"), "got: {}", output);
+    }
+}
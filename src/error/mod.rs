@@ -69,6 +69,24 @@ impl LexerError {
             .with_message("integer literal is too large")
             .with_label(Label::primary(span, ""))
     }
+
+    pub fn invalid_digit(span: Span, digit: char, radix_name: &str) -> Diagnostic {
+        Diagnostic::error()
+            .with_message(format!("invalid digit `{}` in {} literal", digit, radix_name))
+            .with_label(Label::primary(span, "invalid digit"))
+    }
+
+    pub fn misplaced_underscore(span: Span) -> Diagnostic {
+        Diagnostic::error()
+            .with_message("underscores in numeric literals must separate two digits")
+            .with_label(Label::primary(span, "misplaced underscore"))
+    }
+
+    pub fn missing_digits(span: Span, radix_name: &str) -> Diagnostic {
+        Diagnostic::error()
+            .with_message(format!("{} literal has no digits", radix_name))
+            .with_label(Label::primary(span, ""))
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -152,15 +170,33 @@ impl TypeError {
             .with_label(Label::primary(span, ""))
     }
 
-    pub fn tuple_field_out_of_bounds(span: Span, field: &str, ty: String, max: usize) -> Diagnostic {
+    pub fn array_too_large(span: Span, ty: String, size: u128, max: u128) -> Diagnostic {
         Diagnostic::error()
             .with_message(format!(
-                "no field `{}` on type `{}`, expected index between 0 and {}",
-                field, ty, max
+                "array too large: `{}` has a size of {} bytes, exceeding the target's maximum of {} bytes",
+                ty, size, max
             ))
             .with_label(Label::primary(span, ""))
     }
 
+    pub fn tuple_field_out_of_bounds(span: Span, index: usize, ty: String, arity: usize) -> Diagnostic {
+        let label_message = if arity == 0 {
+            "the tuple has no elements".to_string()
+        } else {
+            format!("valid indices are 0 to {}", arity - 1)
+        };
+
+        Diagnostic::error()
+            .with_message(format!(
+                "tuple index `{}` is out of bounds for type `{}`, which has {} element{}",
+                index,
+                ty,
+                arity,
+                if arity == 1 { "" } else { "s" }
+            ))
+            .with_label(Label::primary(span, label_message))
+    }
+
     pub fn non_numeric_tuple_field(span: Span, field: &str, ty: String) -> Diagnostic {
         Diagnostic::error()
             .with_message(format!("can only access tuple `{}` fields by its indices", ty))
@@ -0,0 +1,169 @@
+use crate::{
+    error::{emit_diagnostics, emitter::ColorMode, DiagnosticResult, Diagnostics},
+    span::FileId,
+    token::{lexer::Lexer, TokenKind},
+};
+use std::{io, path::Path};
+
+const INDENT: &str = "    ";
+
+pub enum FormatOutcome {
+    // The file was already formatted
+    Unchanged,
+    // The file was formatted and rewritten, or would have been rewritten when checking
+    Changed,
+    // The file contains lexical errors, which have already been emitted
+    Failed,
+    // The file couldn't be read or written
+    Io(io::Error),
+}
+
+// Formats the file in place. When `check` is set, the file is only checked for being formatted, and isn't rewritten.
+pub fn format_file(path: &Path, check: bool, color_mode: ColorMode) -> FormatOutcome {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(error) => return FormatOutcome::Io(error),
+    };
+
+    let mut diagnostics = Diagnostics::new();
+    let file_id = diagnostics.add_file(path.to_string_lossy().to_string(), source.clone());
+
+    let formatted = match format_source(file_id, &source) {
+        Ok(formatted) => formatted,
+        Err(diagnostic) => {
+            diagnostics.push(diagnostic);
+            emit_diagnostics(&diagnostics, color_mode);
+            return FormatOutcome::Failed;
+        }
+    };
+
+    if formatted == source {
+        FormatOutcome::Unchanged
+    } else if check {
+        FormatOutcome::Changed
+    } else {
+        match std::fs::write(path, formatted) {
+            Ok(()) => FormatOutcome::Changed,
+            Err(error) => FormatOutcome::Io(error),
+        }
+    }
+}
+
+// Formats source code canonically:
+// - Lines are indented by four spaces for every bracket they are nested in
+// - Trailing whitespace is removed
+// - Consecutive blank lines are collapsed, and leading and trailing blank lines are removed
+//
+// Only whitespace between tokens is changed, so comments, attributes and the order of bindings are preserved.
+// The contents of multiline strings are kept verbatim. Formatting is idempotent.
+pub fn format_source(file_id: FileId, source: &str) -> DiagnosticResult<String> {
+    let tokens = Lexer::new(file_id, source).scan()?;
+
+    let line_starts = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+        .collect::<Vec<_>>();
+
+    let line_of = |index: usize| line_starts.partition_point(|&start| start <= index) - 1;
+
+    let mut lines = vec![LineInfo::default(); line_starts.len()];
+
+    let mut depth = 0;
+    let mut current_line = 0;
+
+    for token in tokens.iter() {
+        match token.kind {
+            TokenKind::Eof => break,
+            TokenKind::Newline => continue,
+            _ => (),
+        }
+
+        let start_line = line_of(token.span.start.index);
+
+        while current_line < start_line {
+            current_line += 1;
+            lines[current_line].depth = depth;
+        }
+
+        let line = &mut lines[start_line];
+
+        if !line.has_tokens {
+            line.has_tokens = true;
+            line.starts_with_close = matches!(
+                token.kind,
+                TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseCurly
+            );
+        }
+
+        match token.kind {
+            TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenCurly => depth += 1,
+            TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseCurly => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+
+        // a token which spans multiple lines is a multiline string, whose contents must be kept as-is
+        let end_line = line_of(token.span.end.index.saturating_sub(1).max(token.span.start.index));
+
+        if end_line > start_line {
+            lines[start_line].keep_end = true;
+
+            for line in lines[start_line + 1..=end_line].iter_mut() {
+                line.verbatim = true;
+            }
+        }
+    }
+
+    while current_line + 1 < lines.len() {
+        current_line += 1;
+        lines[current_line].depth = depth;
+    }
+
+    let mut output = String::with_capacity(source.len());
+    let mut pending_blank_line = false;
+
+    for (text, line) in source.split('\n').zip(lines.iter()) {
+        if line.verbatim {
+            output.push_str(text);
+            output.push('\n');
+            continue;
+        }
+
+        let text = if line.keep_end { text.trim_start() } else { text.trim() };
+
+        if text.is_empty() {
+            pending_blank_line = !output.is_empty();
+            continue;
+        }
+
+        if pending_blank_line {
+            output.push('\n');
+            pending_blank_line = false;
+        }
+
+        let indent = if line.starts_with_close {
+            line.depth.saturating_sub(1)
+        } else {
+            line.depth
+        };
+
+        for _ in 0..indent {
+            output.push_str(INDENT);
+        }
+
+        output.push_str(text);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+#[derive(Default, Clone, Copy)]
+struct LineInfo {
+    // The number of brackets which are open at the start of the line
+    depth: usize,
+    has_tokens: bool,
+    starts_with_close: bool,
+    // The line is inside a multiline string
+    verbatim: bool,
+    // The line starts a multiline string, so its trailing whitespace is part of the string
+    keep_end: bool,
+}
@@ -57,6 +57,10 @@ pub enum AttrKind {
     Dylib,
     LinkName,
     TrackCaller,
+    Align,
+    AllowRedundantCast,
+    Export,
+    Test,
 }
 
 pub const ATTR_NAME_INTRINSIC: &str = "intrinsic";
@@ -64,6 +68,10 @@ pub const ATTR_NAME_LIB: &str = "lib";
 pub const ATTR_NAME_DYLIB: &str = "dylib";
 pub const ATTR_NAME_LINK_NAME: &str = "link_name";
 pub const ATTR_NAME_TRACK_CALLER: &str = "track_caller";
+pub const ATTR_NAME_ALIGN: &str = "align";
+pub const ATTR_NAME_ALLOW_REDUNDANT_CAST: &str = "allow_redundant_cast";
+pub const ATTR_NAME_EXPORT: &str = "export";
+pub const ATTR_NAME_TEST: &str = "test";
 
 impl TryFrom<&str> for AttrKind {
     type Error = ();
@@ -75,6 +83,10 @@ impl TryFrom<&str> for AttrKind {
             ATTR_NAME_DYLIB => Ok(AttrKind::Dylib),
             ATTR_NAME_LINK_NAME => Ok(AttrKind::LinkName),
             ATTR_NAME_TRACK_CALLER => Ok(AttrKind::TrackCaller),
+            ATTR_NAME_ALIGN => Ok(AttrKind::Align),
+            ATTR_NAME_ALLOW_REDUNDANT_CAST => Ok(AttrKind::AllowRedundantCast),
+            ATTR_NAME_EXPORT => Ok(AttrKind::Export),
+            ATTR_NAME_TEST => Ok(AttrKind::Test),
             _ => Err(()),
         }
     }
@@ -91,6 +103,10 @@ impl Display for AttrKind {
                 AttrKind::Dylib => ATTR_NAME_DYLIB,
                 AttrKind::LinkName => ATTR_NAME_LINK_NAME,
                 AttrKind::TrackCaller => ATTR_NAME_TRACK_CALLER,
+                AttrKind::Align => ATTR_NAME_ALIGN,
+                AttrKind::AllowRedundantCast => ATTR_NAME_ALLOW_REDUNDANT_CAST,
+                AttrKind::Export => ATTR_NAME_EXPORT,
+                AttrKind::Test => ATTR_NAME_TEST,
             }
         )
     }
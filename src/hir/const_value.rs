@@ -1,6 +1,6 @@
 use super::FunctionId;
 use crate::{
-    ast::{self, ExternLibrary},
+    ast::ExternLibrary,
     common::target::{Arch, Os},
     infer::{display::DisplayType, type_ctx::TypeCtx},
     types::TypeId,
@@ -54,19 +54,6 @@ pub struct ConstExternVariable {
     pub ty: TypeId,
 }
 
-impl From<ast::LiteralKind> for ConstValue {
-    fn from(lit: ast::LiteralKind) -> Self {
-        match lit {
-            ast::LiteralKind::Nil => panic!("nil is deprecated"),
-            ast::LiteralKind::Bool(v) => ConstValue::Bool(v),
-            ast::LiteralKind::Int(v) => ConstValue::Int(v),
-            ast::LiteralKind::Float(v) => ConstValue::Float(v),
-            ast::LiteralKind::Str(v) => ConstValue::Str(v),
-            ast::LiteralKind::Char(v) => ConstValue::Int(v as i128),
-        }
-    }
-}
-
 impl ConstValue {
     pub fn eq(&self, other: &Self) -> Self {
         Self::Bool(match (self, other) {
@@ -306,7 +293,8 @@ impl ConstValue {
         Self::Int(match os {
             Os::Linux => 0,
             Os::Windows => 1,
-            Os::Darwin | Os::Essence | Os::FreeBSD | Os::Wasi | Os::Js | Os::Freestanding => {
+            Os::Darwin => 2,
+            Os::Essence | Os::FreeBSD | Os::Wasi | Os::Js | Os::Freestanding => {
                 todo!("{}", os.name())
             }
         })
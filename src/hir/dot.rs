@@ -0,0 +1,63 @@
+use super::json;
+use crate::{hir, infer::type_ctx::TypeCtx, workspace::Workspace};
+use serde_json::Value;
+use std::io::{self, Write};
+
+// Writes the typed tree as a Graphviz `digraph`.
+// Nodes are labeled with their kind, and edges point from each node to its children.
+pub fn write(cache: &hir::Cache, workspace: &Workspace, tcx: &TypeCtx, writer: &mut dyn Write) -> io::Result<()> {
+    let document = json::document(cache, workspace, tcx);
+
+    let mut graph = DotGraph { writer, node_count: 0 };
+
+    graph.writer.write_all(b"digraph hir {\n")?;
+    graph.writer.write_all(b"    node [shape=box];\n")?;
+
+    for key in ["bindings", "functions"] {
+        for root in document[key].as_array().unwrap() {
+            graph.write_node(root)?;
+        }
+    }
+
+    graph.writer.write_all(b"}\n")
+}
+
+struct DotGraph<'w> {
+    writer: &'w mut dyn Write,
+    node_count: usize,
+}
+
+impl<'w> DotGraph<'w> {
+    // Writes the node and its children recursively, returning the node's id
+    fn write_node(&mut self, node: &Value) -> io::Result<usize> {
+        let id = self.node_count;
+        self.node_count += 1;
+
+        let kind = node["kind"].as_str().unwrap_or("?");
+        let ty = escape(node["type"].as_str().unwrap_or("?"));
+
+        // the label's first line is the node's kind, followed by its name or value, and its second line is its type
+        let label = match ["name", "member", "field", "value"]
+            .iter()
+            .find_map(|key| node[*key].as_str())
+        {
+            Some(detail) => format!("{} {}\\n{}", kind, escape(detail), ty),
+            None => format!("{}\\n{}", kind, ty),
+        };
+
+        writeln!(self.writer, "    n{} [label=\"{}\"];", id, label)?;
+
+        if let Some(children) = node["children"].as_array() {
+            for child in children {
+                let child_id = self.write_node(child)?;
+                writeln!(self.writer, "    n{} -> n{};", id, child_id)?;
+            }
+        }
+
+        Ok(id)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,220 @@
+use crate::{
+    hir,
+    infer::{display::DisplayType, normalize::Normalize, type_ctx::TypeCtx},
+    span::Span,
+    types::TypeId,
+    workspace::Workspace,
+};
+use serde_json::{json, Value};
+use std::io::{self, Write};
+
+// Writes the typed tree as a JSON document, for consumption by external tools.
+// Every node is written with its kind, its resolved type and its span.
+pub fn write(cache: &hir::Cache, workspace: &Workspace, tcx: &TypeCtx, writer: &mut dyn Write) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, &document(cache, workspace, tcx))?;
+    writeln!(writer)
+}
+
+// Builds the JSON document of the typed tree.
+// The document has a `bindings` array and a `functions` array, each holding root nodes.
+// Every node has a `kind`, `type`, `span` and `children`, and some have additional fields, such as `name`.
+pub(super) fn document(cache: &hir::Cache, workspace: &Workspace, tcx: &TypeCtx) -> Value {
+    let ctx = JsonCtx { workspace, tcx };
+
+    // bindings are stored in a hash map, so they are sorted to keep the output stable
+    let mut bindings = cache.bindings.values().collect::<Vec<_>>();
+    bindings.sort_by_key(|binding| binding.id);
+
+    json!({
+        "bindings": bindings.into_iter().map(|binding| ctx.binding(binding)).collect::<Vec<_>>(),
+        "functions": cache.functions.iter().map(|(_, function)| ctx.function(function)).collect::<Vec<_>>(),
+    })
+}
+
+struct JsonCtx<'a> {
+    workspace: &'a Workspace,
+    tcx: &'a TypeCtx,
+}
+
+impl<'a> JsonCtx<'a> {
+    fn ty(&self, ty: TypeId) -> String {
+        ty.normalize(self.tcx).display(self.tcx)
+    }
+
+    fn span(&self, span: Span) -> Value {
+        let file = self
+            .workspace
+            .diagnostics
+            .get_file(span.file_id)
+            .map(|file| file.name().to_string());
+
+        json!({
+            "file": file,
+            "start": span.start.index,
+            "end": span.end.index,
+        })
+    }
+
+    fn node_json(&self, kind: &str, ty: TypeId, span: Span, children: Vec<Value>) -> Value {
+        json!({
+            "kind": kind,
+            "type": self.ty(ty),
+            "span": self.span(span),
+            "children": children,
+        })
+    }
+
+    fn with_field(mut value: Value, key: &str, field: Value) -> Value {
+        value.as_object_mut().unwrap().insert(key.to_string(), field);
+        value
+    }
+
+    fn binding(&self, binding: &hir::Binding) -> Value {
+        let module = self
+            .workspace
+            .module_infos
+            .get(binding.module_id)
+            .unwrap()
+            .qualified_name;
+
+        let value = self.node_json("Binding", binding.ty, binding.span, vec![self.node(&binding.value)]);
+        let value = Self::with_field(value, "name", json!(binding.name.as_str()));
+        Self::with_field(value, "module", json!(module.as_str()))
+    }
+
+    fn function(&self, function: &hir::Function) -> Value {
+        let children = match &function.kind {
+            hir::FunctionKind::Orphan { body: Some(body), .. } => vec![self.sequence(body)],
+            _ => vec![],
+        };
+
+        let kind = match &function.kind {
+            hir::FunctionKind::Orphan { .. } => "Function",
+            hir::FunctionKind::Extern { .. } => "ExternFunction",
+            hir::FunctionKind::Intrinsic(_) => "IntrinsicFunction",
+        };
+
+        let value = self.node_json(kind, function.ty, function.span, children);
+        Self::with_field(value, "name", json!(function.qualified_name.as_str()))
+    }
+
+    fn sequence(&self, sequence: &hir::Sequence) -> Value {
+        self.node_json("Sequence", sequence.ty, sequence.span, self.nodes(&sequence.statements))
+    }
+
+    fn nodes(&self, nodes: &[hir::Node]) -> Vec<Value> {
+        nodes.iter().map(|node| self.node(node)).collect()
+    }
+
+    fn node(&self, node: &hir::Node) -> Value {
+        let (ty, span) = (node.ty(), node.span());
+
+        match node {
+            hir::Node::Const(x) => {
+                let value = self.node_json("Const", ty, span, vec![]);
+                Self::with_field(value, "value", json!(x.value.display(self.tcx)))
+            }
+            hir::Node::Binding(x) => self.binding(x),
+            hir::Node::Id(x) => {
+                let name = self.workspace.binding_infos.get(x.id).unwrap().name;
+                let value = self.node_json("Id", ty, span, vec![]);
+                Self::with_field(value, "name", json!(name.as_str()))
+            }
+            hir::Node::Assign(x) => self.node_json("Assign", ty, span, vec![self.node(&x.lhs), self.node(&x.rhs)]),
+            hir::Node::MemberAccess(x) => {
+                let value = self.node_json("MemberAccess", ty, span, vec![self.node(&x.value)]);
+                Self::with_field(value, "member", json!(x.member_name.as_str()))
+            }
+            hir::Node::Call(x) => {
+                let mut children = vec![self.node(&x.callee)];
+                children.extend(self.nodes(&x.args));
+                self.node_json("Call", ty, span, children)
+            }
+            hir::Node::Cast(x) => self.node_json("Cast", ty, span, vec![self.node(&x.value)]),
+            hir::Node::Sequence(x) => self.sequence(x),
+            hir::Node::Control(x) => self.control(x),
+            hir::Node::Builtin(x) => self.builtin(x),
+            hir::Node::Literal(x) => self.literal(x),
+        }
+    }
+
+    fn control(&self, control: &hir::Control) -> Value {
+        let (kind, children) = match control {
+            hir::Control::If(x) => {
+                let mut children = vec![self.node(&x.condition), self.node(&x.then)];
+                children.extend(x.otherwise.as_ref().map(|otherwise| self.node(otherwise)));
+                ("If", children)
+            }
+            hir::Control::While(x) => ("While", vec![self.node(&x.condition), self.node(&x.body)]),
+            hir::Control::Return(x) => ("Return", vec![self.node(&x.value)]),
+            hir::Control::Break(_) => ("Break", vec![]),
+            hir::Control::Continue(_) => ("Continue", vec![]),
+        };
+
+        self.node_json(kind, control.ty(), control.span(), children)
+    }
+
+    fn builtin(&self, builtin: &hir::Builtin) -> Value {
+        let binary = |x: &hir::Binary| vec![self.node(&x.lhs), self.node(&x.rhs)];
+
+        let (kind, children) = match builtin {
+            hir::Builtin::Add(x) => ("Add", binary(x)),
+            hir::Builtin::Sub(x) => ("Sub", binary(x)),
+            hir::Builtin::Mul(x) => ("Mul", binary(x)),
+            hir::Builtin::Div(x) => ("Div", binary(x)),
+            hir::Builtin::Rem(x) => ("Rem", binary(x)),
+            hir::Builtin::AddWithOverflow(x) => ("AddWithOverflow", binary(x)),
+            hir::Builtin::Shl(x) => ("Shl", binary(x)),
+            hir::Builtin::Shr(x) => ("Shr", binary(x)),
+            hir::Builtin::And(x) => ("And", binary(x)),
+            hir::Builtin::Or(x) => ("Or", binary(x)),
+            hir::Builtin::Lt(x) => ("Lt", binary(x)),
+            hir::Builtin::Le(x) => ("Le", binary(x)),
+            hir::Builtin::Gt(x) => ("Gt", binary(x)),
+            hir::Builtin::Ge(x) => ("Ge", binary(x)),
+            hir::Builtin::Eq(x) => ("Eq", binary(x)),
+            hir::Builtin::Ne(x) => ("Ne", binary(x)),
+            hir::Builtin::BitAnd(x) => ("BitAnd", binary(x)),
+            hir::Builtin::BitOr(x) => ("BitOr", binary(x)),
+            hir::Builtin::BitXor(x) => ("BitXor", binary(x)),
+            hir::Builtin::Not(x) => ("Not", vec![self.node(&x.value)]),
+            hir::Builtin::Neg(x) => ("Neg", vec![self.node(&x.value)]),
+            hir::Builtin::Deref(x) => ("Deref", vec![self.node(&x.value)]),
+            hir::Builtin::Ref(x) => ("Ref", vec![self.node(&x.value)]),
+            hir::Builtin::Offset(x) => ("Offset", vec![self.node(&x.value), self.node(&x.index)]),
+            hir::Builtin::Slice(x) => (
+                "Slice",
+                vec![self.node(&x.value), self.node(&x.low), self.node(&x.high)],
+            ),
+            hir::Builtin::MemCmp(x) => ("MemCmp", vec![self.node(&x.lhs), self.node(&x.rhs), self.node(&x.len)]),
+            hir::Builtin::Asm(x) => ("Asm", x.inputs.iter().map(|input| self.node(input)).collect()),
+            hir::Builtin::Assume(x) => ("Assume", vec![self.node(&x.value)]),
+            hir::Builtin::Breakpoint(_) => ("Breakpoint", vec![]),
+            hir::Builtin::Abort(x) => ("Abort", vec![self.node(&x.value)]),
+            hir::Builtin::Zeroed(_) => ("Zeroed", vec![]),
+            hir::Builtin::Panic(x) => ("Panic", vec![self.node(&x.value)]),
+        };
+
+        self.node_json(kind, builtin.ty(), builtin.span(), children)
+    }
+
+    fn literal(&self, literal: &hir::Literal) -> Value {
+        let (kind, children) = match literal {
+            hir::Literal::Struct(x) => (
+                "StructLiteral",
+                x.fields
+                    .iter()
+                    .map(|field| {
+                        let value = self.node(&field.value);
+                        Self::with_field(value, "field", json!(field.name.as_str()))
+                    })
+                    .collect(),
+            ),
+            hir::Literal::Tuple(x) => ("TupleLiteral", self.nodes(&x.elements)),
+            hir::Literal::Array(x) => ("ArrayLiteral", self.nodes(&x.elements)),
+            hir::Literal::ArrayFill(x) => ("ArrayFillLiteral", vec![self.node(&x.value)]),
+        };
+
+        self.node_json(kind, literal.ty(), literal.span(), children)
+    }
+}
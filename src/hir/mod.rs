@@ -1,5 +1,7 @@
 pub mod attrs;
 pub mod const_value;
+pub mod dot;
+pub mod json;
 pub mod pretty;
 
 use self::const_value::ConstValue;
@@ -44,6 +46,28 @@ pub struct Cache {
 
     // The entry point function's id (usually named "main"). Resolved during semantic analysis
     pub entry_point_function_id: Option<FunctionId>,
+
+    // The spans of bindings annotated with `@allow_redundant_cast`
+    pub redundant_cast_allowances: Vec<Span>,
+
+    // Compile-time known bindings annotated with `@export`, which are emitted as named global symbols
+    pub exported_bindings: Vec<ExportedBinding>,
+
+    // Functions annotated with `@test`, which are run by `chili test`
+    pub test_functions: Vec<FunctionId>,
+
+    // Minimum alignments of variables annotated with `@align`
+    pub binding_aligns: HashMap<BindingId, usize>,
+
+    // The evaluated conditions of module-level `static if`s, by their module and index
+    pub static_if_conditions: HashMap<(ModuleId, usize), bool>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExportedBinding {
+    pub id: BindingId,
+    pub name: Ustr,
+    pub span: Span,
 }
 
 impl Cache {
@@ -52,6 +76,11 @@ impl Cache {
             bindings: HashMap::new(),
             functions: IdCache::new(),
             entry_point_function_id: None,
+            redundant_cast_allowances: vec![],
+            exported_bindings: vec![],
+            test_functions: vec![],
+            binding_aligns: HashMap::new(),
+            static_if_conditions: HashMap::new(),
         }
     }
 
@@ -204,6 +233,8 @@ node_struct!(Sequence, { statements: Vec<Node>, is_scope: bool });
 node_struct!(If, { condition: Box<Node>, then: Box<Node>, otherwise: Option<Box<Node>> });
 node_struct!(While, { condition: Box<Node>, body: Box<Node> });
 node_struct!(Return, { value: Box<Node> });
+// `depth` is the targeted loop's position, counting outwards from the innermost enclosing loop, which is 1
+node_struct!(LoopControl, { depth: usize });
 
 node_struct!(Binary, { lhs: Box<Node>, rhs: Box<Node> });
 node_struct!(Unary, { value: Box<Node> });
@@ -211,6 +242,8 @@ node_struct!(Ref, { value: Box<Node>, is_mutable: bool });
 
 node_struct!(Offset, { value: Box<Node>, index: Box<Node> });
 node_struct!(Slice, { value: Box<Node>, low: Box<Node>, high: Box<Node> });
+node_struct!(MemCmp, { lhs: Box<Node>, rhs: Box<Node>, len: Box<Node> });
+node_struct!(Asm, { template: Ustr, constraints: Ustr, inputs: Vec<Node> });
 // node_struct!(Transmute, { value: Box<Node> });
 
 node_struct!(StructLiteral, { fields: Vec<StructLiteralField> });
@@ -224,8 +257,8 @@ pub enum Control {
     If(If),
     While(While),
     Return(Return),
-    Break(Empty),
-    Continue(Empty),
+    Break(LoopControl),
+    Continue(LoopControl),
 }
 
 #[derive(Debug, PartialEq, Clone, EnumAsInner)]
@@ -236,6 +269,9 @@ pub enum Builtin {
     Div(Binary),
     Rem(Binary),
 
+    // Wrapping addition, resulting in a `(T, bool)` tuple of the result and whether it overflowed
+    AddWithOverflow(Binary),
+
     Shl(Binary),
     Shr(Binary),
 
@@ -260,6 +296,18 @@ pub enum Builtin {
     Ref(Ref),
     Offset(Offset),
     Slice(Slice),
+
+    // Compares `len` bytes of two pointers, like C's `memcmp`
+    MemCmp(MemCmp),
+
+    // Inline assembly, with LLVM-style constraints. Produces at most a single integer output
+    Asm(Asm),
+
+    Assume(Unary),
+    Breakpoint(Empty),
+    Abort(Unary),
+    Zeroed(Empty),
+    Panic(Unary),
     // TODO: Transmute(Transmute),
 }
 
@@ -359,6 +407,14 @@ impl Builtin {
             Self::Ref(x) => x.ty,
             Self::Offset(x) => x.ty,
             Self::Slice(x) => x.ty,
+            Self::Assume(x) => x.ty,
+            Self::Breakpoint(x) => x.ty,
+            Self::Abort(x) => x.ty,
+            Self::Zeroed(x) => x.ty,
+            Self::Panic(x) => x.ty,
+            Self::AddWithOverflow(x) => x.ty,
+            Self::MemCmp(x) => x.ty,
+            Self::Asm(x) => x.ty,
         }
     }
 
@@ -389,6 +445,14 @@ impl Builtin {
             Self::Ref(x) => x.span,
             Self::Offset(x) => x.span,
             Self::Slice(x) => x.span,
+            Self::Assume(x) => x.span,
+            Self::Breakpoint(x) => x.span,
+            Self::Abort(x) => x.span,
+            Self::Zeroed(x) => x.span,
+            Self::Panic(x) => x.span,
+            Self::AddWithOverflow(x) => x.span,
+            Self::MemCmp(x) => x.span,
+            Self::Asm(x) => x.span,
         }
     }
 }
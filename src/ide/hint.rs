@@ -234,6 +234,7 @@ impl<'a> CollectHints<'a> for hir::Builtin {
             | hir::Builtin::Mul(x)
             | hir::Builtin::Div(x)
             | hir::Builtin::Rem(x)
+            | hir::Builtin::AddWithOverflow(x)
             | hir::Builtin::Shl(x)
             | hir::Builtin::Shr(x)
             | hir::Builtin::And(x)
@@ -251,6 +252,13 @@ impl<'a> CollectHints<'a> for hir::Builtin {
             hir::Builtin::Ref(x) => x.collect_hints(sess),
             hir::Builtin::Offset(x) => x.collect_hints(sess),
             hir::Builtin::Slice(x) => x.collect_hints(sess),
+            hir::Builtin::MemCmp(x) => x.collect_hints(sess),
+            hir::Builtin::Asm(x) => x.collect_hints(sess),
+            hir::Builtin::Assume(x) => x.collect_hints(sess),
+            hir::Builtin::Breakpoint(_) => (),
+            hir::Builtin::Abort(x) => x.collect_hints(sess),
+            hir::Builtin::Zeroed(_) => (),
+            hir::Builtin::Panic(x) => x.collect_hints(sess),
         }
     }
 }
@@ -289,6 +297,22 @@ impl<'a> CollectHints<'a> for hir::Slice {
     }
 }
 
+impl<'a> CollectHints<'a> for hir::MemCmp {
+    fn collect_hints(&self, sess: &mut HintSess<'a>) {
+        self.lhs.collect_hints(sess);
+        self.rhs.collect_hints(sess);
+        self.len.collect_hints(sess);
+    }
+}
+
+impl<'a> CollectHints<'a> for hir::Asm {
+    fn collect_hints(&self, sess: &mut HintSess<'a>) {
+        for input in self.inputs.iter() {
+            input.collect_hints(sess);
+        }
+    }
+}
+
 impl<'a> CollectHints<'a> for hir::Literal {
     fn collect_hints(&self, sess: &mut HintSess<'a>) {
         match self {
@@ -4,12 +4,13 @@ mod util;
 
 use self::hint::{CollectHints, HintSess};
 use crate::{
+    ast,
     error::diagnostic::DiagnosticSeverity,
     hir,
     infer::{display::DisplayType, normalize::Normalize, type_ctx::TypeCtx},
     span::{EndPosition, Position, Span},
     types::Type,
-    workspace::Workspace,
+    workspace::{BindingInfoKind, Workspace},
 };
 use indexmap::indexmap;
 use types::*;
@@ -125,3 +126,44 @@ pub fn goto_definition(workspace: &Workspace, tcx: Option<&TypeCtx>, offset: usi
 
     write_null();
 }
+
+// Writes every public top-level binding in the workspace, across all modules
+pub fn symbol_index(workspace: &Workspace, tcx: Option<&TypeCtx>) {
+    let tcx = match tcx {
+        Some(tcx) => tcx,
+        None => return write_null(),
+    };
+
+    let symbols: Vec<IdeSymbol> = workspace
+        .binding_infos
+        .iter()
+        .map(|(_, b)| b)
+        .filter(|binding_info| {
+            binding_info.vis == ast::Vis::Public
+                && binding_info.scope_level.is_global()
+                && binding_info.is_is_user_defined()
+        })
+        .map(|binding_info| {
+            let module_info = workspace.module_infos.get(binding_info.module_id).unwrap();
+
+            IdeSymbol {
+                name: binding_info.name.to_string(),
+                module: module_info.qualified_name.to_string(),
+                kind: match binding_info.kind {
+                    BindingInfoKind::LetConst => "const",
+                    BindingInfoKind::LetStatic => "static",
+                    BindingInfoKind::Function => "function",
+                    BindingInfoKind::ExternFunction => "extern_function",
+                    BindingInfoKind::ExternVariable => "extern_variable",
+                    BindingInfoKind::Intrinsic(_) => "intrinsic",
+                    BindingInfoKind::Type => "type",
+                }
+                .to_string(),
+                type_name: binding_info.ty.normalize(tcx).display(tcx),
+                span: IdeSpan::from_span_and_file(binding_info.span, module_info.file_path.to_string()),
+            }
+        })
+        .collect();
+
+    write(&symbols);
+}
@@ -75,6 +75,15 @@ pub enum IdeObject {
     Hint(Hint),
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdeSymbol {
+    pub name: String,
+    pub module: String,
+    pub kind: String,
+    pub type_name: String,
+    pub span: IdeSpan,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HoverInfo {
     pub contents: String,
@@ -58,7 +58,7 @@ impl NormalizeCtx {
     fn normalize_ty(&mut self, tcx: &TypeCtx, ty: TypeId) -> Type {
         match tcx.value_of(ty) {
             InferenceValue::Bound(kind) => self.normalize_kind(tcx, kind),
-            InferenceValue::AnyInt => self.normalize_anyint(ty),
+            InferenceValue::AnyInt => self.normalize_anyint(tcx, ty),
             InferenceValue::AnyFloat => self.normalize_anyfloat(ty),
             InferenceValue::Unbound => ty.as_kind(),
         }
@@ -105,6 +105,7 @@ impl NormalizeCtx {
                         .map(|f| StructTypeField {
                             name: f.name,
                             ty: self.normalize_kind(tcx, &f.ty),
+                            vis: f.vis,
                             span: f.span,
                         })
                         .collect();
@@ -114,6 +115,7 @@ impl NormalizeCtx {
                         id: struct_type.id,
                         fields,
                         kind: struct_type.kind,
+                        align: struct_type.align,
                     });
 
                     self.parent_binding_id = old_id;
@@ -122,7 +124,7 @@ impl NormalizeCtx {
                 }
             },
             Type::Type(inner) => self.normalize_kind(tcx, inner).create_type(),
-            Type::Infer(ty, InferType::AnyInt) => self.normalize_anyint(*ty),
+            Type::Infer(ty, InferType::AnyInt) => self.normalize_anyint(tcx, *ty),
             Type::Infer(ty, InferType::AnyFloat) => self.normalize_anyfloat(*ty),
             Type::Never
             | Type::Unit
@@ -135,9 +137,9 @@ impl NormalizeCtx {
         }
     }
 
-    fn normalize_anyint(&self, ty: TypeId) -> Type {
+    fn normalize_anyint(&self, tcx: &TypeCtx, ty: TypeId) -> Type {
         if self.concrete {
-            Type::int()
+            tcx.default_int_type_of(ty)
         } else {
             Type::Infer(ty, InferType::AnyInt)
         }
@@ -145,7 +147,7 @@ impl NormalizeCtx {
 
     fn normalize_anyfloat(&self, ty: TypeId) -> Type {
         if self.concrete {
-            Type::float()
+            Type::f64()
         } else {
             Type::Infer(ty, InferType::AnyFloat)
         }
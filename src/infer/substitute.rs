@@ -194,6 +194,7 @@ impl<'a> Substitute<'a> for hir::Builtin {
             hir::Builtin::Mul(x) => x.substitute(sess),
             hir::Builtin::Div(x) => x.substitute(sess),
             hir::Builtin::Rem(x) => x.substitute(sess),
+            hir::Builtin::AddWithOverflow(x) => x.substitute(sess),
             hir::Builtin::Shl(x) => x.substitute(sess),
             hir::Builtin::Shr(x) => x.substitute(sess),
             hir::Builtin::And(x) => x.substitute(sess),
@@ -213,6 +214,13 @@ impl<'a> Substitute<'a> for hir::Builtin {
             hir::Builtin::Deref(x) => x.substitute(sess),
             hir::Builtin::Offset(x) => x.substitute(sess),
             hir::Builtin::Slice(x) => x.substitute(sess),
+            hir::Builtin::MemCmp(x) => x.substitute(sess),
+            hir::Builtin::Asm(x) => x.substitute(sess),
+            hir::Builtin::Assume(x) => x.substitute(sess),
+            hir::Builtin::Breakpoint(x) => x.ty.substitute(sess, x.span),
+            hir::Builtin::Abort(x) => x.substitute(sess),
+            hir::Builtin::Zeroed(x) => x.ty.substitute(sess, x.span),
+            hir::Builtin::Panic(x) => x.substitute(sess),
         }
     }
 }
@@ -256,6 +264,24 @@ impl<'a> Substitute<'a> for hir::Slice {
     }
 }
 
+impl<'a> Substitute<'a> for hir::MemCmp {
+    fn substitute(&self, sess: &mut Sess<'a>) {
+        self.ty.substitute(sess, self.span);
+        self.lhs.substitute(sess);
+        self.rhs.substitute(sess);
+        self.len.substitute(sess);
+    }
+}
+
+impl<'a> Substitute<'a> for hir::Asm {
+    fn substitute(&self, sess: &mut Sess<'a>) {
+        self.ty.substitute(sess, self.span);
+        for input in self.inputs.iter() {
+            input.substitute(sess);
+        }
+    }
+}
+
 impl<'a> Substitute<'a> for hir::Function {
     fn substitute(&self, sess: &mut Sess<'a>) {
         self.ty.substitute(sess, self.span);
@@ -344,13 +370,14 @@ fn extract_free_type_vars(ty: &mut Type, free_types: &mut HashSet<TypeId>, tcx:
         }
 
         Type::Infer(id, InferType::AnyInt) => {
-            let concrete = Type::int();
+            let concrete = tcx.default_int_type_of(*id);
             tcx.bind_ty(*id, concrete.clone());
+            tcx.defaulted_int_types.push(*id);
             *ty = concrete;
         }
 
         Type::Infer(id, InferType::AnyFloat) => {
-            let concrete = Type::float();
+            let concrete = Type::f64();
             tcx.bind_ty(*id, concrete.clone());
             *ty = concrete;
         }
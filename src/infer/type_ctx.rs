@@ -1,18 +1,48 @@
-use super::{display::DisplayType, inference_value::InferenceValue, normalize::Normalize};
+use super::{
+    display::DisplayType,
+    inference_value::InferenceValue,
+    normalize::{Concrete, Normalize},
+};
 use crate::{
     common::id_cache::IdCache,
-    span::Span,
+    span::{FileId, Span},
     types::{InferType, StructType, Type, TypeId},
 };
+use std::collections::HashSet;
+
+pub const DEFAULT_MAX_TYPE_VAR_DEPTH: usize = 256;
 
 pub struct TypeCtx {
     pub bindings: IdCache<TypeId, InferenceValue>,
     pub binding_spans: IdCache<TypeId, Option<Span>>,
     pub common_types: CommonTypes,
+
+    /// The maximum number of bound type variables unification may follow before giving up
+    pub max_type_var_depth: usize,
+    pub(super) type_var_depth: usize,
+
+    /// Every type that was assigned an identifier by `type_id!`, indexed by its identifier
+    type_ids: Vec<Type>,
+
+    /// The type that integer literals fall back to, when their type couldn't be inferred
+    pub default_int_type: Type,
+
+    /// The files of the main library, which are the only ones `default_int_type` applies to.
+    /// Redundant casts are also only reported in these files
+    pub main_library_files: HashSet<FileId>,
+
+    /// Integer literal types that fell back to `default_int_type` during substitution
+    pub defaulted_int_types: Vec<TypeId>,
 }
 
 impl Default for TypeCtx {
     fn default() -> Self {
+        Self::new(DEFAULT_MAX_TYPE_VAR_DEPTH, Type::i32())
+    }
+}
+
+impl TypeCtx {
+    pub fn new(max_type_var_depth: usize, default_int_type: Type) -> Self {
         let mut bindings = IdCache::new();
         let mut binding_spans = IdCache::new();
         let common_types = CommonTypes::new(&mut bindings, &mut binding_spans);
@@ -20,11 +50,15 @@ impl Default for TypeCtx {
             bindings,
             binding_spans,
             common_types,
+            max_type_var_depth,
+            type_var_depth: 0,
+            type_ids: vec![],
+            default_int_type,
+            main_library_files: HashSet::new(),
+            defaulted_int_types: vec![],
         }
     }
-}
 
-impl TypeCtx {
     #[inline]
     fn insert(&mut self, binding: InferenceValue, span: Option<Span>) -> TypeId {
         self.binding_spans.insert(span);
@@ -70,6 +104,31 @@ impl TypeCtx {
         }
     }
 
+    /// Follows a chain of variables bound to other variables, and returns the variable at its end.
+    /// Every variable along the way is re-pointed directly at that variable, so the next lookup is a single step.
+    pub fn find(&mut self, id: TypeId) -> TypeId {
+        let mut chain = vec![];
+        let mut root = id;
+
+        while let InferenceValue::Bound(Type::Var(next) | Type::Infer(next, _)) = self.value_of(root) {
+            if *next == root || chain.contains(next) {
+                break;
+            }
+
+            chain.push(root);
+            root = *next;
+        }
+
+        // the last variable in the chain already points at the root
+        chain.pop();
+
+        for var in chain {
+            self.bind_ty(var, Type::Var(root));
+        }
+
+        root
+    }
+
     #[allow(unused)]
     #[inline]
     pub fn normalize(&self, ty: TypeId) -> Type {
@@ -81,6 +140,15 @@ impl TypeCtx {
         self.binding_spans.get(ty).cloned().flatten()
     }
 
+    /// The type that the integer literal type `ty` falls back to, when it couldn't be inferred.
+    /// Literals outside of the main library always fall back to `i32`, so that `--default-int` doesn't change the std.
+    pub fn default_int_type_of(&self, ty: TypeId) -> Type {
+        match self.ty_span(ty) {
+            Some(span) if self.main_library_files.contains(&span.file_id) => self.default_int_type.clone(),
+            _ => Type::i32(),
+        }
+    }
+
     #[inline]
     pub fn bind_ty(&mut self, id: TypeId, ty: Type) {
         self.bind_value(id, InferenceValue::Bound(ty))
@@ -94,17 +162,31 @@ impl TypeCtx {
             .unwrap_or_else(|| panic!("type id not found: {:?}", id)) = value;
     }
 
+    /// Returns an identifier which is unique to the concrete form of `ty`.
+    /// Identifiers are only consistent within the current compilation, and are not stable across compilations
+    pub fn type_id_of(&mut self, ty: TypeId) -> u64 {
+        let ty = ty.concrete(self);
+
+        match self.type_ids.iter().position(|other| *other == ty) {
+            Some(index) => index as u64,
+            None => {
+                self.type_ids.push(ty);
+                (self.type_ids.len() - 1) as u64
+            }
+        }
+    }
+
     #[allow(unused)]
     pub fn make_concrete(&mut self, ty: &mut Type) {
         match ty {
             Type::Infer(id, InferType::AnyInt) => {
-                let concrete = Type::int();
+                let concrete = self.default_int_type_of(*id);
                 self.bind_ty(*id, concrete.clone());
                 *ty = concrete;
             }
 
             Type::Infer(id, InferType::AnyFloat) => {
-                let concrete = Type::float();
+                let concrete = Type::f64();
                 self.bind_ty(*id, concrete.clone());
                 *ty = concrete;
             }
@@ -213,3 +295,47 @@ impl CommonTypes {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TypeCtx;
+    use crate::{infer::inference_value::InferenceValue, span::Span, types::Type};
+
+    #[test]
+    fn find_compresses_chains_of_variables() {
+        let mut tcx = TypeCtx::default();
+        let root = tcx.var(Span::unknown());
+        let middle = tcx.bound(Type::Var(root), Span::unknown());
+        let first = tcx.bound(Type::Var(middle), Span::unknown());
+
+        assert_eq!(tcx.find(first), root);
+        assert_eq!(tcx.value_of(first), &InferenceValue::Bound(Type::Var(root)));
+        assert_eq!(tcx.value_of(middle), &InferenceValue::Bound(Type::Var(root)));
+        assert_eq!(tcx.value_of(root), &InferenceValue::Unbound);
+    }
+
+    #[test]
+    fn find_stops_at_concrete_types() {
+        let mut tcx = TypeCtx::default();
+        let concrete = tcx.bound(Type::i32(), Span::unknown());
+        let var = tcx.bound(Type::Var(concrete), Span::unknown());
+
+        assert_eq!(tcx.find(var), concrete);
+        assert_eq!(tcx.find(concrete), concrete);
+    }
+
+    #[test]
+    fn find_terminates_on_cycles() {
+        let mut tcx = TypeCtx::default();
+        let a = tcx.var(Span::unknown());
+        let b = tcx.bound(Type::Var(a), Span::unknown());
+        tcx.bind_ty(a, Type::Var(b));
+
+        let root = tcx.find(a);
+        assert!(root == a || root == b);
+
+        let this = tcx.var(Span::unknown());
+        tcx.bind_ty(this, Type::Var(this));
+        assert_eq!(tcx.find(this), this);
+    }
+}
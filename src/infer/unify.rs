@@ -157,8 +157,20 @@ impl UnifyType<StructType> for StructType {
 }
 
 fn unify_var_ty(var: TypeId, other: &Type, tcx: &mut TypeCtx) -> UnifyTypeResult {
+    let var = tcx.find(var);
+
     match tcx.value_of(var).clone() {
-        InferenceValue::Bound(kind) => kind.unify(other, tcx),
+        InferenceValue::Bound(kind) => {
+            if tcx.type_var_depth >= tcx.max_type_var_depth {
+                return Err(UnifyTypeErr::TooDeep);
+            }
+
+            tcx.type_var_depth += 1;
+            let result = kind.unify(other, tcx);
+            tcx.type_var_depth -= 1;
+
+            result
+        }
         InferenceValue::AnyInt => {
             let other_kind = other.normalize(tcx);
             match other_kind {
@@ -166,7 +178,10 @@ fn unify_var_ty(var: TypeId, other: &Type, tcx: &mut TypeCtx) -> UnifyTypeResult
                     tcx.bind_ty(var, other_kind);
                     Ok(())
                 }
-                Type::Infer(other, InferType::AnyInt | InferType::AnyFloat) | Type::Var(other) => {
+                // An integer literal isn't unified with a float literal, so that it's coerced with a cast instead.
+                // i.e: in `1 + 2.0`, `1` is cast to a float
+                Type::Infer(_, InferType::AnyFloat) => Err(UnifyTypeErr::Mismatch),
+                Type::Infer(other, InferType::AnyInt) | Type::Var(other) => {
                     if other != var {
                         tcx.bind_ty(other, var.as_kind());
                     }
@@ -183,7 +198,8 @@ fn unify_var_ty(var: TypeId, other: &Type, tcx: &mut TypeCtx) -> UnifyTypeResult
                     tcx.bind_ty(var, other_kind);
                     Ok(())
                 }
-                Type::Infer(other, InferType::AnyInt | InferType::AnyFloat) | Type::Var(other) => {
+                Type::Infer(_, InferType::AnyInt) => Err(UnifyTypeErr::Mismatch),
+                Type::Infer(other, InferType::AnyFloat) | Type::Var(other) => {
                     if other != var {
                         tcx.bind_ty(other, var.as_kind());
                     }
@@ -196,6 +212,12 @@ fn unify_var_ty(var: TypeId, other: &Type, tcx: &mut TypeCtx) -> UnifyTypeResult
         InferenceValue::Unbound => {
             let other_kind = other.normalize(tcx);
 
+            if let Type::Var(other) | Type::Infer(other, _) = other_kind {
+                if other == var {
+                    return Ok(());
+                }
+            }
+
             if occurs(var, &other_kind, tcx) {
                 Err(UnifyTypeErr::Occurs)
             } else {
@@ -207,18 +229,38 @@ fn unify_var_ty(var: TypeId, other: &Type, tcx: &mut TypeCtx) -> UnifyTypeResult
 }
 
 pub fn occurs(var: TypeId, kind: &Type, tcx: &TypeCtx) -> bool {
+    occurs_inner(var, kind, tcx, &mut vec![])
+}
+
+// `visited` holds the bound type variables we already walked through.
+// Named types can refer to themselves behind a pointer, so following them again would never terminate.
+fn occurs_inner(var: TypeId, kind: &Type, tcx: &TypeCtx, visited: &mut Vec<TypeId>) -> bool {
     match kind {
-        &Type::Var(other) => {
-            use InferenceValue::*;
+        &Type::Var(other) | &Type::Infer(other, _) => {
+            if var == other {
+                return true;
+            }
+
+            if visited.contains(&other) {
+                return false;
+            }
+
+            visited.push(other);
+
             match tcx.value_of(other) {
-                Bound(ty) => occurs(var, ty, tcx) || var == other,
-                AnyInt | AnyFloat | Unbound => var == other,
+                InferenceValue::Bound(ty) => occurs_inner(var, ty, tcx, visited),
+                InferenceValue::AnyInt | InferenceValue::AnyFloat | InferenceValue::Unbound => false,
             }
         }
-        Type::Function(f) => f.params.iter().any(|p| occurs(var, &p.ty, tcx)) || occurs(var, &f.return_type, tcx),
-        Type::Array(ty, _) => occurs(var, ty, tcx),
-        Type::Tuple(tys) => tys.iter().any(|ty| occurs(var, ty, tcx)),
-        Type::Struct(st) => st.fields.iter().any(|f| occurs(var, &f.ty, tcx)),
+        Type::Function(f) => {
+            f.params.iter().any(|p| occurs_inner(var, &p.ty, tcx, visited))
+                || occurs_inner(var, &f.return_type, tcx, visited)
+        }
+        Type::Pointer(ty, _) | Type::Array(ty, _) | Type::Slice(ty) | Type::Str(ty) | Type::Type(ty) => {
+            occurs_inner(var, ty, tcx, visited)
+        }
+        Type::Tuple(tys) => tys.iter().any(|ty| occurs_inner(var, ty, tcx, visited)),
+        Type::Struct(st) => st.fields.iter().any(|f| occurs_inner(var, &f.ty, tcx, visited)),
         _ => false,
     }
 }
@@ -229,6 +271,7 @@ pub type UnifyTypeResult = Result<(), UnifyTypeErr>;
 pub enum UnifyTypeErr {
     Mismatch,
     Occurs,
+    TooDeep,
 }
 
 impl UnifyTypeErr {
@@ -249,8 +292,17 @@ impl UnifyTypeErr {
                 .with_label(Label::primary(found_span, format!("expected {}", expected)))
                 .maybe_with_label(expected_span.map(|span| Label::secondary(span, "expected due to this"))),
             UnifyTypeErr::Occurs => Diagnostic::error()
-                .with_message(format!("recursive type `{}` has infinite size", expected,))
-                .with_label(Label::primary(found_span, "type is recursive")),
+                .with_message(format!("infinite type - cannot unify {} with {}, which contains it", expected, found))
+                .with_label(Label::primary(found_span, "type is recursive"))
+                .maybe_with_label(expected_span.map(|span| Label::secondary(span, "expected due to this"))),
+            UnifyTypeErr::TooDeep => Diagnostic::error()
+                .with_message(format!(
+                    "infinite type - reached the maximum type variable depth of {} while unifying {} with {}",
+                    tcx.max_type_var_depth, expected, found
+                ))
+                .with_label(Label::primary(found_span, "type is recursive"))
+                .maybe_with_label(expected_span.map(|span| Label::secondary(span, "expected due to this")))
+                .with_note("the maximum depth can be raised with `--max-type-var-depth`"),
         }
     }
 }
@@ -259,3 +311,75 @@ impl UnifyTypeErr {
 pub fn can_coerce_mut(from_mut: bool, to_mut: bool) -> bool {
     from_mut == to_mut || (from_mut && !to_mut)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{occurs, UnifyType, UnifyTypeErr};
+    use crate::{
+        infer::type_ctx::{TypeCtx, DEFAULT_MAX_TYPE_VAR_DEPTH},
+        span::Span,
+        types::{Type, TypeId},
+    };
+
+    // `depth` pointers to `i32`, where every pointee is a bound type variable
+    fn nested_pointers(tcx: &mut TypeCtx, depth: usize) -> TypeId {
+        let mut ty = tcx.bound(Type::i32(), Span::unknown());
+
+        for _ in 0..depth {
+            ty = tcx.bound(Type::Pointer(Box::new(Type::Var(ty)), false), Span::unknown());
+        }
+
+        ty
+    }
+
+    #[test]
+    fn unifies_within_max_type_var_depth() {
+        let mut tcx = TypeCtx::new(DEFAULT_MAX_TYPE_VAR_DEPTH, Type::i32());
+        let a = nested_pointers(&mut tcx, 8);
+        let b = nested_pointers(&mut tcx, 8);
+
+        assert!(Type::Var(a).unify(&Type::Var(b), &mut tcx).is_ok());
+    }
+
+    #[test]
+    fn gives_up_past_max_type_var_depth() {
+        let mut tcx = TypeCtx::new(4, Type::i32());
+        let a = nested_pointers(&mut tcx, 8);
+        let b = nested_pointers(&mut tcx, 8);
+
+        assert!(matches!(
+            Type::Var(a).unify(&Type::Var(b), &mut tcx),
+            Err(UnifyTypeErr::TooDeep)
+        ));
+    }
+
+    #[test]
+    fn occurs_through_pointers_slices_and_variables() {
+        let mut tcx = TypeCtx::default();
+        let var = tcx.var(Span::unknown());
+        let pointer_to_var = tcx.bound(Type::Pointer(Box::new(Type::Var(var)), false), Span::unknown());
+
+        assert!(occurs(var, &Type::Pointer(Box::new(Type::Var(var)), true), &tcx));
+        assert!(occurs(var, &Type::Slice(Box::new(Type::Var(var))), &tcx));
+        assert!(occurs(var, &Type::Slice(Box::new(Type::Var(pointer_to_var))), &tcx));
+
+        assert!(matches!(
+            Type::Var(var).unify(&Type::Var(pointer_to_var), &mut tcx),
+            Err(UnifyTypeErr::Occurs)
+        ));
+    }
+
+    #[test]
+    fn unrelated_types_dont_occur() {
+        let mut tcx = TypeCtx::default();
+        let var = tcx.var(Span::unknown());
+        let other = tcx.var(Span::unknown());
+
+        assert!(!occurs(var, &Type::Pointer(Box::new(Type::Var(other)), false), &tcx));
+        assert!(!occurs(var, &Type::Slice(Box::new(Type::i32())), &tcx));
+
+        assert!(Type::Var(var)
+            .unify(&Type::Pointer(Box::new(Type::Var(other)), false), &mut tcx)
+            .is_ok());
+    }
+}
@@ -3,17 +3,22 @@ use super::{
     vm::{
         bytecode::Op,
         value::{ExternFunction, Function, FunctionValue, Pointer, Value},
-        VM,
+        VMError, VMResult, VM,
     },
-    IS_64BIT,
+    IS_64BIT, WORD_SIZE,
 };
-use crate::types::*;
+use crate::types::{size_of::SizeOf, *};
 use bumpalo::Bump;
 use libffi::{
     low::{ffi_cif, CodePtr},
     middle::{Cif, Closure, Type as FfiType},
 };
-use std::{collections::HashMap, ffi::c_void, path::Path};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, HashMap},
+    ffi::c_void,
+    path::Path,
+};
 use ustr::{ustr, Ustr, UstrMap};
 
 macro_rules! raw_ptr {
@@ -25,6 +30,7 @@ macro_rules! raw_ptr {
 pub type RawPointer = *mut c_void;
 
 pub struct Ffi {
+    // each library is loaded once, and is unloaded when the interpreter is dropped
     libs: UstrMap<libloading::Library>,
     symbols: HashMap<(Ustr, Ustr), RawPointer>,
     libc: Ustr,
@@ -59,20 +65,33 @@ impl Ffi {
         }
     }
 
-    pub unsafe fn load_symbol(&mut self, lib_path: Ustr, name: Ustr) -> &mut RawPointer {
-        self.symbols.entry((lib_path, name)).or_insert_with(|| {
+    pub unsafe fn load_symbol(&mut self, lib_path: Ustr, name: Ustr) -> VMResult<&mut RawPointer> {
+        let key = (lib_path, name);
+
+        if !self.symbols.contains_key(&key) {
             let lib_name = match lib_path.as_str() {
                 "c" | "C" => self.libc,
                 _ => lib_path,
             };
 
-            let lib = self
-                .libs
-                .entry(lib_name)
-                .or_insert_with(|| libloading::Library::new(lib_name.as_str()).unwrap());
+            let lib = match self.libs.entry(lib_name) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let lib = libloading::Library::new(lib_name.as_str())
+                        .map_err(|err| VMError::LoadLibrary(lib_name, err.to_string()))?;
+
+                    entry.insert(lib)
+                }
+            };
+
+            let symbol = *lib
+                .get::<RawPointer>(name.as_bytes())
+                .map_err(|_| VMError::LoadSymbol(lib_name, name))?;
 
-            *lib.get(name.as_bytes()).unwrap()
-        })
+            self.symbols.insert(key, symbol);
+        }
+
+        Ok(self.symbols.get_mut(&key).unwrap())
     }
 
     pub unsafe fn call(
@@ -81,8 +100,8 @@ impl Ffi {
         mut args: Vec<Value>,
         vm: *mut VM,
         interp: *const Interp,
-    ) -> Value {
-        let symbol = self.load_symbol(function.lib_path, function.name);
+    ) -> VMResult {
+        let symbol = *self.load_symbol(function.lib_path, function.name)?;
 
         let function_type = &function.ty;
         let param_types = function_type.params.iter().map(|p| p.ty.clone()).collect::<Vec<Type>>();
@@ -99,9 +118,9 @@ impl Ffi {
             FfiFunction::new(&param_types, &function_type.return_type)
         };
 
-        let result = function.call(*symbol, &mut args, self, vm);
+        let result = function.call(symbol, &mut args, self, vm)?;
 
-        Value::from_type_and_ptr(&function_type.return_type, result as RawPointer)
+        Ok(Value::from_type_and_ptr(&function_type.return_type, result as RawPointer))
     }
 }
 
@@ -139,12 +158,15 @@ impl FfiFunction {
         arg_values: &mut [Value],
         ffi: &mut Ffi,
         vm: *mut VM<'vm>,
-    ) -> RawPointer {
+    ) -> VMResult<RawPointer> {
         let code_ptr = CodePtr::from_ptr(fun);
 
         let mut args: Vec<RawPointer> = Vec::with_capacity(arg_values.len());
         let bump = Bump::new();
 
+        // errors can't unwind through the foreign function, so callbacks store them here instead
+        let callback_error: RefCell<Option<VMError>> = RefCell::new(None);
+
         for arg in arg_values.iter_mut() {
             let arg_ptr = match arg {
                 Value::I8(v) => raw_ptr!(v),
@@ -178,7 +200,11 @@ impl FfiFunction {
                             &function.ty.return_type,
                         );
 
-                        let user_data = bump.alloc(ClosureUserData { vm, function });
+                        let user_data = bump.alloc(ClosureUserData {
+                            vm,
+                            function,
+                            error: &callback_error,
+                        });
 
                         let closure = bump.alloc(Closure::new(ffi_function.cif, closure_callback, user_data));
 
@@ -187,7 +213,7 @@ impl FfiFunction {
                         raw_ptr!(code_ptr)
                     }
                     FunctionValue::Extern(function) => {
-                        let symbol = ffi.load_symbol(function.lib_path, function.name);
+                        let symbol = ffi.load_symbol(function.lib_path, function.name)?;
                         raw_ptr!(symbol)
                     }
                 },
@@ -206,13 +232,17 @@ impl FfiFunction {
             args.as_mut_ptr(),
         );
 
-        call_result.assume_init_mut()
+        match callback_error.into_inner() {
+            Some(err) => Err(err),
+            None => Ok(raw_ptr!(call_result.assume_init_mut())),
+        }
     }
 }
 
 struct ClosureUserData<'vm> {
     vm: *mut VM<'vm>,
     function: *const Function,
+    error: *const RefCell<Option<VMError>>,
 }
 
 // TODO: closures don't work in multithreaded code right now.
@@ -227,18 +257,37 @@ unsafe extern "C" fn closure_callback(
     let mut func = (&*userdata.function).clone();
     let arg_count = func.ty.params.len();
 
+    let error = &*userdata.error;
+
+    // a previous call of this callback failed, so the rest of the foreign call's result is discarded
+    if error.borrow().is_some() {
+        write_zeroed_result(result, &func.ty.return_type);
+        return;
+    }
+
     // set up function args
     if arg_count > 0 {
         let args = std::slice::from_raw_parts(args, arg_count);
-        for (param, arg) in func.ty.params.iter().zip(args) {
+        for (index, (param, arg)) in func.ty.params.iter().zip(args).enumerate() {
             let value = Value::from_type_and_ptr(&param.ty, *arg as RawPointer);
-            (*userdata.vm).stack.push(value);
+
+            if (*userdata.vm).stack.push(value).is_err() {
+                // pop the args that were already pushed
+                for _ in 0..index {
+                    (*userdata.vm).stack.pop();
+                }
+
+                *error.borrow_mut() = Some(VMError::StackOverflow(func.name));
+                write_zeroed_result(result, &func.ty.return_type);
+                return;
+            }
         }
     }
 
     // we need the VM to Halt instead of Return
     *func.code.as_mut_slice().last_mut().unwrap() = u8::from(Op::Halt);
 
+    let return_type = func.ty.return_type.as_ref().clone();
     let value = (*userdata.vm).run_function(func);
 
     // pop the function args manually
@@ -252,6 +301,15 @@ unsafe extern "C" fn closure_callback(
     (*userdata.vm).frames.pop();
     (*userdata.vm).frame = (*userdata.vm).frames.last_mut() as _;
 
+    let value = match value {
+        Ok(value) => value,
+        Err(err) => {
+            *error.borrow_mut() = Some(err);
+            write_zeroed_result(result, &return_type);
+            return;
+        }
+    };
+
     match value {
         Value::I8(v) => *(result as *mut _ as *mut _) = v,
         Value::I16(v) => *(result as *mut _ as *mut _) = v,
@@ -271,6 +329,10 @@ unsafe extern "C" fn closure_callback(
     }
 }
 
+unsafe fn write_zeroed_result(result: &mut c_void, return_type: &Type) {
+    std::ptr::write_bytes(result as *mut c_void as *mut u8, 0, return_type.size_of(WORD_SIZE));
+}
+
 trait AsFfiType {
     unsafe fn as_ffi_type(&self) -> FfiType;
 }
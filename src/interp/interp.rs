@@ -5,7 +5,7 @@ use super::{
         bytecode::{Bytecode, Inst},
         disassemble::dump_bytecode_to_file,
         value::{ExternFunction, Function, FunctionAddress, FunctionValue, Value},
-        Constants, Globals, VM,
+        Constants, Globals, VMError, VM,
     },
 };
 use crate::{
@@ -14,7 +14,7 @@ use crate::{
         scopes::Scopes,
         target::{TargetMetrics, TargetPlatform},
     },
-    error::diagnostic::Diagnostic,
+    error::diagnostic::{Diagnostic, Label},
     hir,
     infer::type_ctx::TypeCtx,
     types::{FunctionType, FunctionTypeKind, Type},
@@ -32,7 +32,9 @@ pub struct Interp {
     pub globals: Globals,
     pub constants: Constants,
 
-    pub functions: HashMap<hir::FunctionId, Function>,
+    // Functions are boxed, because the VM's stack frames point at the function they're running.
+    // Lowering a function while others are running would otherwise move them when the map grows
+    pub functions: HashMap<hir::FunctionId, Box<Function>>,
     pub extern_functions: HashMap<hir::FunctionId, ExternFunction>,
 
     pub ffi: Ffi,
@@ -80,7 +82,7 @@ impl Interp {
     pub fn get_function(&self, id: hir::FunctionId) -> Option<FunctionValue> {
         self.functions
             .get(&id)
-            .map(FunctionValue::Orphan)
+            .map(|function| FunctionValue::Orphan(function))
             .or_else(|| self.extern_functions.get(&id).map(FunctionValue::Extern))
     }
 }
@@ -156,9 +158,17 @@ impl<'i> InterpSess<'i> {
                 code: start_code,
             };
 
-            let result = vm.run_function(start_func);
+            vm.run_function(start_func).map_err(|err| {
+                let (message, label) = match err {
+                    VMError::Panic(_) => (err.to_string(), "panicked during compile-time evaluation"),
+                    VMError::StackOverflow(_) => (err.to_string(), "evaluated here"),
+                    _ => (format!("compile-time evaluation failed: {}", err), "evaluated here"),
+                };
 
-            Ok(result)
+                vec![Diagnostic::error()
+                    .with_message(message)
+                    .with_label(Label::primary(node.span(), label))]
+            })
         } else {
             Err(self.diagnostics.clone())
         }
@@ -174,7 +184,7 @@ impl<'i> InterpSess<'i> {
 
             self.interp.functions.insert(
                 id,
-                Function {
+                Box::new(Function {
                     id,
                     name,
                     ty: FunctionType {
@@ -184,7 +194,7 @@ impl<'i> InterpSess<'i> {
                         kind: FunctionTypeKind::Orphan,
                     },
                     code: global_eval_code.clone(),
-                },
+                }),
             );
 
             self.interp.constants.push(Value::Function(FunctionAddress {
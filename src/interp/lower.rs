@@ -84,12 +84,12 @@ impl Lower for hir::Function {
 
                 sess.interp.functions.insert(
                     self.id,
-                    Function {
+                    Box::new(Function {
                         id: self.id,
                         name: self.qualified_name,
                         ty: function_type,
                         code: function_code,
-                    },
+                    }),
                 );
             }
             hir::FunctionKind::Extern { dylib, link_name, .. } => {
@@ -274,13 +274,15 @@ impl Lower for hir::Control {
             hir::Control::If(x) => x.lower(sess, code, ctx),
             hir::Control::While(x) => x.lower(sess, code, ctx),
             hir::Control::Return(x) => x.lower(sess, code, ctx),
-            hir::Control::Break(_) => {
+            hir::Control::Break(x) => {
                 let pos = code.write_inst(Inst::Jmp(INVALID_JMP_OFFSET));
-                sess.loop_env_stack.last_mut().unwrap().break_offsets.push(pos);
+                let index = sess.loop_env_stack.len() - x.depth;
+                sess.loop_env_stack[index].break_offsets.push(pos);
             }
-            hir::Control::Continue(_) => {
+            hir::Control::Continue(x) => {
                 let pos = code.write_inst(Inst::Jmp(INVALID_JMP_OFFSET));
-                sess.loop_env_stack.last_mut().unwrap().break_offsets.push(pos);
+                let index = sess.loop_env_stack.len() - x.depth;
+                sess.loop_env_stack[index].continue_offsets.push(pos);
             }
         }
     }
@@ -407,6 +409,18 @@ impl Lower for hir::Builtin {
 
                 code.write_inst(Inst::Rem);
             }
+            hir::Builtin::AddWithOverflow(binary) => {
+                let tuple_type = binary.ty.normalize(sess.tcx);
+                let tuple_size = tuple_type.size_of(WORD_SIZE) as u32;
+
+                sess.push_const(code, Value::Type(tuple_type.clone()));
+                code.write_inst(Inst::BufferAlloc(tuple_size));
+
+                binary.lhs.lower(sess, code, LowerContext { take_ptr: false });
+                binary.rhs.lower(sess, code, LowerContext { take_ptr: false });
+
+                code.write_inst(Inst::AddWithOverflow(tuple_type.offset_of(1, WORD_SIZE) as u32));
+            }
             hir::Builtin::Shl(binary) => {
                 binary.lhs.lower(sess, code, LowerContext { take_ptr: false });
                 binary.rhs.lower(sess, code, LowerContext { take_ptr: false });
@@ -489,19 +503,19 @@ impl Lower for hir::Builtin {
                 binary.lhs.lower(sess, code, LowerContext { take_ptr: false });
                 binary.rhs.lower(sess, code, LowerContext { take_ptr: false });
 
-                code.write_inst(Inst::And);
+                code.write_inst(Inst::BitAnd);
             }
             hir::Builtin::BitOr(binary) => {
                 binary.lhs.lower(sess, code, LowerContext { take_ptr: false });
                 binary.rhs.lower(sess, code, LowerContext { take_ptr: false });
 
-                code.write_inst(Inst::Or);
+                code.write_inst(Inst::BitOr);
             }
             hir::Builtin::BitXor(binary) => {
                 binary.lhs.lower(sess, code, LowerContext { take_ptr: false });
                 binary.rhs.lower(sess, code, LowerContext { take_ptr: false });
 
-                code.write_inst(Inst::Xor);
+                code.write_inst(Inst::BitXor);
             }
             hir::Builtin::Not(unary) => {
                 unary.value.lower(sess, code, LowerContext { take_ptr: false });
@@ -546,6 +560,13 @@ impl Lower for hir::Builtin {
                     code.write_inst(Inst::Deref);
                 }
             }
+            hir::Builtin::MemCmp(memcmp) => {
+                memcmp.lhs.lower(sess, code, LowerContext { take_ptr: false });
+                memcmp.rhs.lower(sess, code, LowerContext { take_ptr: false });
+                memcmp.len.lower(sess, code, LowerContext { take_ptr: false });
+
+                code.write_inst(Inst::MemCmp);
+            }
             hir::Builtin::Slice(slice) => {
                 let result_type = Type::Pointer(Box::new(slice.ty.normalize(sess.tcx)), true);
                 // Size of a fat pointer
@@ -635,6 +656,37 @@ impl Lower for hir::Builtin {
                     }
                 }
             }
+            hir::Builtin::Asm(asm) => {
+                sess.diagnostics.push(
+                    Diagnostic::error()
+                        .with_message("inline assembly can't be evaluated at compile-time")
+                        .with_label(Label::primary(asm.span, "cannot use during compile-time")),
+                );
+
+                // Push a placeholder so the rest of the lowering stays consistent
+                let ty = asm.ty.normalize(sess.tcx);
+                sess.push_const(code, Value::zeroed(&ty));
+            }
+            hir::Builtin::Assume(_) => {
+                // Note (Ron): Assumptions are only used as optimization hints, so they are ignored during compile-time evaluation
+                sess.push_const_unit(code);
+            }
+            hir::Builtin::Breakpoint(_) => {
+                // Note (Ron): Breakpoints are ignored during compile-time evaluation
+                sess.push_const_unit(code);
+            }
+            hir::Builtin::Abort(unary) => {
+                unary.value.lower(sess, code, LowerContext { take_ptr: false });
+                code.write_inst(Inst::Abort);
+            }
+            hir::Builtin::Panic(unary) => {
+                unary.value.lower(sess, code, LowerContext { take_ptr: false });
+                code.write_inst(Inst::Panic);
+            }
+            hir::Builtin::Zeroed(x) => {
+                let ty = x.ty.normalize(sess.tcx);
+                sess.push_const(code, Value::zeroed(&ty));
+            }
         }
     }
 }
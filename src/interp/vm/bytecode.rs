@@ -52,11 +52,11 @@ impl Bytecode {
             Inst::Le => self.write_op(Op::Le),
             Inst::Gt => self.write_op(Op::Gt),
             Inst::Ge => self.write_op(Op::Ge),
-            Inst::And => self.write_op(Op::And),
-            Inst::Or => self.write_op(Op::Or),
+            Inst::BitAnd => self.write_op(Op::BitAnd),
+            Inst::BitOr => self.write_op(Op::BitOr),
             Inst::Shl => self.write_op(Op::Shl),
             Inst::Shr => self.write_op(Op::Shr),
-            Inst::Xor => self.write_op(Op::Xor),
+            Inst::BitXor => self.write_op(Op::BitXor),
             Inst::Jmp(addr) => {
                 let op_addr = self.write_op(Op::Jmp);
                 self.write_i32(addr);
@@ -142,6 +142,14 @@ impl Bytecode {
                 addr
             }
             Inst::Halt => self.write_op(Op::Halt),
+            Inst::Abort => self.write_op(Op::Abort),
+            Inst::Panic => self.write_op(Op::Panic),
+            Inst::AddWithOverflow(overflow_offset) => {
+                let addr = self.write_op(Op::AddWithOverflow);
+                self.write_u32(overflow_offset);
+                addr
+            }
+            Inst::MemCmp => self.write_op(Op::MemCmp),
         }
     }
 
@@ -307,11 +315,11 @@ pub enum Op {
     Le,
     Gt,
     Ge,
-    And,
-    Or,
+    BitAnd,
+    BitOr,
     Shl,
     Shr,
-    Xor,
+    BitXor,
     Jmp,
     Jmpf,
     Return,
@@ -333,6 +341,10 @@ pub enum Op {
     Copy,
     Swap,
     Halt,
+    Abort,
+    Panic,
+    AddWithOverflow,
+    MemCmp,
 }
 
 impl From<u8> for Op {
@@ -356,11 +368,11 @@ impl From<u8> for Op {
             14 => Le,
             15 => Gt,
             16 => Ge,
-            17 => And,
-            18 => Or,
+            17 => BitAnd,
+            18 => BitOr,
             19 => Shl,
             20 => Shr,
-            21 => Xor,
+            21 => BitXor,
             22 => Jmp,
             23 => Jmpf,
             24 => Return,
@@ -382,6 +394,10 @@ impl From<u8> for Op {
             40 => Copy,
             41 => Swap,
             42 => Halt,
+            43 => Abort,
+            44 => Panic,
+            45 => AddWithOverflow,
+            46 => MemCmp,
             _ => panic!(),
         }
     }
@@ -408,11 +424,11 @@ impl From<Op> for u8 {
             Le => 14,
             Gt => 15,
             Ge => 16,
-            And => 17,
-            Or => 18,
+            BitAnd => 17,
+            BitOr => 18,
             Shl => 19,
             Shr => 20,
-            Xor => 21,
+            BitXor => 21,
             Jmp => 22,
             Jmpf => 23,
             Return => 24,
@@ -434,6 +450,10 @@ impl From<Op> for u8 {
             Copy => 40,
             Swap => 41,
             Halt => 42,
+            Abort => 43,
+            Panic => 44,
+            AddWithOverflow => 45,
+            MemCmp => 46,
         }
     }
 }
@@ -457,11 +477,11 @@ impl Display for Op {
             Op::Le => write!(f, "le"),
             Op::Gt => write!(f, "gt"),
             Op::Ge => write!(f, "ge"),
-            Op::And => write!(f, "and"),
-            Op::Or => write!(f, "or"),
+            Op::BitAnd => write!(f, "bitand"),
+            Op::BitOr => write!(f, "bitor"),
             Op::Shl => write!(f, "shl"),
             Op::Shr => write!(f, "shr"),
-            Op::Xor => write!(f, "xor"),
+            Op::BitXor => write!(f, "bitxor"),
             Op::Jmp => write!(f, "jmp"),
             Op::Jmpf => write!(f, "jmpf"),
             Op::Return => write!(f, "return"),
@@ -483,6 +503,10 @@ impl Display for Op {
             Op::Copy => write!(f, "copy"),
             Op::Swap => write!(f, "swap"),
             Op::Halt => write!(f, "halt"),
+            Op::Abort => write!(f, "abort"),
+            Op::Panic => write!(f, "panic"),
+            Op::AddWithOverflow => write!(f, "add_with_overflow"),
+            Op::MemCmp => write!(f, "memcmp"),
         }
     }
 }
@@ -505,11 +529,11 @@ pub enum Inst {
     Le,
     Gt,
     Ge,
-    And,
-    Or,
+    BitAnd,
+    BitOr,
     Shl,
     Shr,
-    Xor,
+    BitXor,
     Jmp(i32),
     Jmpf(i32),
     Return,
@@ -531,4 +555,11 @@ pub enum Inst {
     Copy(u32),
     Swap(u32),
     Halt,
+    Abort,
+    Panic,
+    // Adds the two values on top of the stack, and writes the wrapped result and the overflow flag
+    // into the `(T, bool)` buffer below them. The operand is the flag's offset in the buffer
+    AddWithOverflow(u32),
+    // Compares the bytes behind the two pointers below the length on top of the stack, pushing an `i32`
+    MemCmp,
 }
@@ -23,7 +23,7 @@ use bumpalo::Bump;
 use colored::Colorize;
 use path_absolutize::Absolutize;
 use std::{ffi::c_void, fmt::Display, path::PathBuf, ptr};
-use ustr::ustr;
+use ustr::{ustr, Ustr};
 
 macro_rules! cast_to_int {
     ($value:expr => $name:ident, $to:ty) => {
@@ -41,6 +41,7 @@ macro_rules! cast_to_int {
             Value::F32(v) => Value::$name(v as $to),
             Value::F64(v) => Value::$name(v as $to),
             Value::Bool(v) => Value::$name(v as $to),
+            Value::Pointer(v) => Value::$name(v.as_inner_raw() as $to),
             _ => panic!("invalid value {}", $value.to_string()),
         }
     };
@@ -78,6 +79,10 @@ const STACK_MAX: usize = FRAMES_MAX * (std::u8::MAX as usize) + 1;
 pub type Constants = Vec<Value>;
 pub type Globals = Vec<Value>;
 
+// The error of pushing onto a `Stack` that is already at its capacity
+#[derive(Debug, Clone, Copy)]
+pub struct StackOverflow;
+
 #[derive(Debug)]
 pub struct Stack<T, const CAPACITY: usize> {
     inner: Vec<T>,
@@ -90,9 +95,15 @@ impl<T: ToString, const CAPACITY: usize> Stack<T, CAPACITY> {
         }
     }
 
-    pub fn push(&mut self, value: T) {
-        debug_assert!(self.inner.len() <= self.inner.capacity(), "stack overflow");
-        self.inner.push(value);
+    // The stack never grows past its capacity, since `VM::frame` points into `VM::frames`
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), StackOverflow> {
+        if self.inner.len() >= CAPACITY {
+            Err(StackOverflow)
+        } else {
+            self.inner.push(value);
+            Ok(())
+        }
     }
 
     #[inline]
@@ -198,53 +209,132 @@ macro_rules! binary_op_int_only {
         let a = $vm.stack.pop();
 
         match (&a, &b) {
-            (Value::I8(a), Value::I8(b)) => $vm.stack.push(Value::I8(a $op b)),
-            (Value::I16(a), Value::I16(b)) => $vm.stack.push(Value::I16(a $op b)),
-            (Value::I32(a), Value::I32(b)) => $vm.stack.push(Value::I32(a $op b)),
-            (Value::I64(a), Value::I64(b)) => $vm.stack.push(Value::I64(a $op b)),
-            (Value::Int(a), Value::Int(b)) => $vm.stack.push(Value::Int(a $op b)),
-            (Value::U8(a), Value::U8(b)) => $vm.stack.push(Value::U8(a $op b)),
-            (Value::U16(a), Value::U16(b)) => $vm.stack.push(Value::U16(a $op b)),
-            (Value::U32(a), Value::U32(b)) => $vm.stack.push(Value::U32(a $op b)),
-            (Value::U64(a), Value::U64(b)) => $vm.stack.push(Value::U64(a $op b)),
-            (Value::Uint(a), Value::Uint(b)) => $vm.stack.push(Value::Uint(a $op b)),
+            (Value::I8(a), Value::I8(b)) => $vm.push(Value::I8(a $op b))?,
+            (Value::I16(a), Value::I16(b)) => $vm.push(Value::I16(a $op b))?,
+            (Value::I32(a), Value::I32(b)) => $vm.push(Value::I32(a $op b))?,
+            (Value::I64(a), Value::I64(b)) => $vm.push(Value::I64(a $op b))?,
+            (Value::Int(a), Value::Int(b)) => $vm.push(Value::Int(a $op b))?,
+            (Value::U8(a), Value::U8(b)) => $vm.push(Value::U8(a $op b))?,
+            (Value::U16(a), Value::U16(b)) => $vm.push(Value::U16(a $op b))?,
+            (Value::U32(a), Value::U32(b)) => $vm.push(Value::U32(a $op b))?,
+            (Value::U64(a), Value::U64(b)) => $vm.push(Value::U64(a $op b))?,
+            (Value::Uint(a), Value::Uint(b)) => $vm.push(Value::Uint(a $op b))?,
             _=> panic!("invalid types in binary operation `{}` : `{}` and `{}`", stringify!($op), a.to_string() ,b.to_string())
         }
     }};
 }
 
+// `>>` is an arithmetic shift for signed integers and a logical shift for unsigned integers,
+// following rust's semantics. The shift amount may be of any integer type
+macro_rules! shift_op {
+    ($vm:expr, $op:ident) => {{
+        let b = $vm.stack.pop();
+        let a = $vm.stack.pop();
+
+        let amount = match &b {
+            Value::I8(b) => *b as i128,
+            Value::I16(b) => *b as i128,
+            Value::I32(b) => *b as i128,
+            Value::I64(b) => *b as i128,
+            Value::Int(b) => *b as i128,
+            Value::U8(b) => *b as i128,
+            Value::U16(b) => *b as i128,
+            Value::U32(b) => *b as i128,
+            Value::U64(b) => *b as i128,
+            Value::Uint(b) => *b as i128,
+            _ => panic!("invalid shift amount `{}`, expected an integer", b.to_string()),
+        };
+
+        if amount < 0 {
+            break Err(VMError::NegativeShift(amount));
+        }
+
+        // amounts that don't fit in a u32 are wrapped by the bit width anyway
+        let amount = u32::try_from(amount).unwrap_or(u32::MAX);
+
+        match &a {
+            Value::I8(a) => $vm.push(Value::I8(a.$op(amount)))?,
+            Value::I16(a) => $vm.push(Value::I16(a.$op(amount)))?,
+            Value::I32(a) => $vm.push(Value::I32(a.$op(amount)))?,
+            Value::I64(a) => $vm.push(Value::I64(a.$op(amount)))?,
+            Value::Int(a) => $vm.push(Value::Int(a.$op(amount)))?,
+            Value::U8(a) => $vm.push(Value::U8(a.$op(amount)))?,
+            Value::U16(a) => $vm.push(Value::U16(a.$op(amount)))?,
+            Value::U32(a) => $vm.push(Value::U32(a.$op(amount)))?,
+            Value::U64(a) => $vm.push(Value::U64(a.$op(amount)))?,
+            Value::Uint(a) => $vm.push(Value::Uint(a.$op(amount)))?,
+            _ => panic!(
+                "invalid type in shift operation `{}` : `{}`",
+                stringify!($op),
+                a.to_string()
+            ),
+        }
+    }};
+}
+
 macro_rules! compare_op {
     ($vm:expr, $op:tt) => {
         let b = $vm.stack.pop();
         let a = $vm.stack.pop();
 
         match (&a, &b) {
-            (Value::Bool(a), Value::Bool(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::I8(a), Value::I8(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::I16(a), Value::I16(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::I32(a), Value::I32(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::I64(a), Value::I64(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::Int(a), Value::Int(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::U8(a), Value::U8(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::U16(a), Value::U16(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::U32(a), Value::U32(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::U64(a), Value::U64(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::Uint(a), Value::Uint(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::F32(a), Value::F32(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::F64(a), Value::F64(b)) => $vm.stack.push(Value::Bool(a $op b)),
-            (Value::Pointer(a), Value::Pointer(b)) => $vm.stack.push(Value::Bool(a.as_inner_raw() $op b.as_inner_raw())),
+            (Value::Bool(a), Value::Bool(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::I8(a), Value::I8(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::I16(a), Value::I16(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::I32(a), Value::I32(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::I64(a), Value::I64(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::Int(a), Value::Int(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::U8(a), Value::U8(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::U16(a), Value::U16(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::U32(a), Value::U32(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::U64(a), Value::U64(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::Uint(a), Value::Uint(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::F32(a), Value::F32(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::F64(a), Value::F64(b)) => $vm.push(Value::Bool(a $op b))?,
+            (Value::Pointer(a), Value::Pointer(b)) => $vm.push(Value::Bool(a.as_inner_raw() $op b.as_inner_raw()))?,
+            // Arrays of integers, booleans and pointers are compared by their bytes. The checker rejects other aggregates
+            (Value::Buffer(a), Value::Buffer(b)) if a.ty.is_bytewise_comparable() => $vm.push(Value::Bool(a.bytes.as_ref() $op b.bytes.as_ref()))?,
             _ => panic!("invalid types in compare operation `{}` and `{}`", a.to_string() ,b.to_string())
         }
     };
 }
 
-macro_rules! logic_op {
-    ($vm:expr, $op:tt) => {
-        let b = $vm.stack.pop();
-        let a = $vm.stack.pop();
+pub type VMResult<T = Value> = Result<T, VMError>;
 
-        $vm.stack.push(Value::Bool(a.into_bool() $op b.into_bool()));
-    };
+// An error that stops the execution of the VM
+#[derive(Debug, Clone)]
+pub enum VMError {
+    Abort(i32),
+    DivideByZero,
+    RemainderByZero,
+    DivideOverflow,
+    RemainderOverflow,
+    NegativeShift(i128),
+    StackOverflow(Ustr),
+    Panic(String),
+    LoadLibrary(Ustr, String),
+    LoadSymbol(Ustr, Ustr),
+}
+
+impl Display for VMError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VMError::Abort(code) => write!(f, "aborted with exit code {}", code),
+            VMError::DivideByZero => write!(f, "attempted to divide by zero"),
+            VMError::RemainderByZero => write!(f, "attempted to calculate the remainder with a divisor of zero"),
+            VMError::DivideOverflow => write!(f, "attempted to divide with overflow"),
+            VMError::RemainderOverflow => write!(f, "attempted to calculate the remainder with overflow"),
+            VMError::NegativeShift(amount) => write!(f, "attempted to shift by a negative amount `{}`", amount),
+            VMError::StackOverflow(name) => write!(
+                f,
+                "compile-time evaluation exceeded maximum recursion depth while calling `{}`",
+                name
+            ),
+            VMError::Panic(message) => write!(f, "{}", message),
+            VMError::LoadLibrary(lib, reason) => write!(f, "failed to load library `{}`: {}", lib, reason),
+            VMError::LoadSymbol(lib, name) => write!(f, "couldn't find symbol `{}` in library `{}`", name, lib),
+        }
+    }
 }
 
 pub struct VM<'vm> {
@@ -266,12 +356,12 @@ impl<'vm> VM<'vm> {
         }
     }
 
-    pub fn run_function(&mut self, function: Function) -> Value {
-        self.push_frame(&function);
+    pub fn run_function(&mut self, function: Function) -> VMResult {
+        self.push_frame(&function)?;
         self.run_inner()
     }
 
-    fn run_inner(&mut self) -> Value {
+    fn run_inner(&mut self) -> VMResult {
         loop {
             // self.trace(TraceLevel::Full);
 
@@ -290,110 +380,59 @@ impl<'vm> VM<'vm> {
                             let symbol =
                                 unsafe { self.interp.ffi.load_symbol(ustr(&variable.lib.path()), variable.name) };
 
-                            unsafe { Value::from_type_and_ptr(&variable.ty, *symbol as RawPointer) }
+                            match symbol {
+                                Ok(symbol) => unsafe { Value::from_type_and_ptr(&variable.ty, *symbol as RawPointer) },
+                                Err(err) => break Err(err),
+                            }
                         }
                         value => value.clone(),
                     };
 
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 Op::Add => {
                     let b = self.stack.pop();
                     let a = self.stack.pop();
-
-                    match (&a, &b) {
-                        (Value::I8(a), Value::I8(b)) => self.stack.push(Value::I8(a + b)),
-                        (Value::I16(a), Value::I16(b)) => self.stack.push(Value::I16(a + b)),
-                        (Value::I32(a), Value::I32(b)) => self.stack.push(Value::I32(a + b)),
-                        (Value::I64(a), Value::I64(b)) => self.stack.push(Value::I64(a + b)),
-                        (Value::Int(a), Value::Int(b)) => self.stack.push(Value::Int(a + b)),
-                        (Value::U8(a), Value::U8(b)) => self.stack.push(Value::U8(a + b)),
-                        (Value::U16(a), Value::U16(b)) => self.stack.push(Value::U16(a + b)),
-                        (Value::U32(a), Value::U32(b)) => self.stack.push(Value::U32(a + b)),
-                        (Value::U64(a), Value::U64(b)) => self.stack.push(Value::U64(a + b)),
-                        (Value::Uint(a), Value::Uint(b)) => self.stack.push(Value::Uint(a + b)),
-                        (Value::F32(a), Value::F32(b)) => self.stack.push(Value::F32(a + b)),
-                        (Value::F64(a), Value::F64(b)) => self.stack.push(Value::F64(a + b)),
-                        (Value::Pointer(a), Value::Int(b)) => self.stack.push(Value::Pointer(unsafe { a.offset(*b) })),
-                        _ => panic!(
-                            "invalid types in binary operation `{}` : `{}` and `{}`",
-                            stringify!(+),
-                            a.to_string(),
-                            b.to_string()
-                        ),
-                    }
+                    self.push(add_values(&a, &b))?;
                 }
                 Op::Sub => {
                     let b = self.stack.pop();
                     let a = self.stack.pop();
-
-                    match (&a, &b) {
-                        (Value::I8(a), Value::I8(b)) => self.stack.push(Value::I8(a - b)),
-                        (Value::I16(a), Value::I16(b)) => self.stack.push(Value::I16(a - b)),
-                        (Value::I32(a), Value::I32(b)) => self.stack.push(Value::I32(a - b)),
-                        (Value::I64(a), Value::I64(b)) => self.stack.push(Value::I64(a - b)),
-                        (Value::Int(a), Value::Int(b)) => self.stack.push(Value::Int(a - b)),
-                        (Value::U8(a), Value::U8(b)) => self.stack.push(Value::U8(a - b)),
-                        (Value::U16(a), Value::U16(b)) => self.stack.push(Value::U16(a - b)),
-                        (Value::U32(a), Value::U32(b)) => self.stack.push(Value::U32(a - b)),
-                        (Value::U64(a), Value::U64(b)) => self.stack.push(Value::U64(a - b)),
-                        (Value::Uint(a), Value::Uint(b)) => self.stack.push(Value::Uint(a - b)),
-                        (Value::F32(a), Value::F32(b)) => self.stack.push(Value::F32(a - b)),
-                        (Value::F64(a), Value::F64(b)) => self.stack.push(Value::F64(a - b)),
-                        (Value::Pointer(a), Value::Int(b)) => self.stack.push(Value::Pointer(unsafe { a.offset(-*b) })),
-                        _ => panic!(
-                            "invalid types in binary operation `{}` : `{}` and `{}`",
-                            stringify!(-),
-                            a.to_string(),
-                            b.to_string()
-                        ),
-                    }
+                    self.push(sub_values(&a, &b))?;
                 }
                 Op::Mul => {
                     let b = self.stack.pop();
                     let a = self.stack.pop();
-
-                    match (&a, &b) {
-                        (Value::I8(a), Value::I8(b)) => self.stack.push(Value::I8(a * b)),
-                        (Value::I16(a), Value::I16(b)) => self.stack.push(Value::I16(a * b)),
-                        (Value::I32(a), Value::I32(b)) => self.stack.push(Value::I32(a * b)),
-                        (Value::I64(a), Value::I64(b)) => self.stack.push(Value::I64(a * b)),
-                        (Value::Int(a), Value::Int(b)) => self.stack.push(Value::Int(a * b)),
-                        (Value::U8(a), Value::U8(b)) => self.stack.push(Value::U8(a * b)),
-                        (Value::U16(a), Value::U16(b)) => self.stack.push(Value::U16(a * b)),
-                        (Value::U32(a), Value::U32(b)) => self.stack.push(Value::U32(a * b)),
-                        (Value::U64(a), Value::U64(b)) => self.stack.push(Value::U64(a * b)),
-                        (Value::Uint(a), Value::Uint(b)) => self.stack.push(Value::Uint(a * b)),
-                        (Value::F32(a), Value::F32(b)) => self.stack.push(Value::F32(a * b)),
-                        (Value::F64(a), Value::F64(b)) => self.stack.push(Value::F64(a * b)),
-                        _ => panic!(
-                            "invalid types in binary operation `{}` : `{}` and `{}`",
-                            stringify!(*),
-                            a.to_string(),
-                            b.to_string()
-                        ),
-                    }
+                    self.push(mul_values(&a, &b))?;
                 }
                 Op::Div => {
                     let b = self.stack.pop();
                     let a = self.stack.pop();
 
+                    if is_int_zero(&b) {
+                        break Err(VMError::DivideByZero);
+                    }
+
+                    if is_signed_division_overflow(&a, &b) {
+                        break Err(VMError::DivideOverflow);
+                    }
+
                     match (&a, &b) {
-                        (Value::I8(a), Value::I8(b)) => self.stack.push(Value::I8(a % b)),
-                        (Value::I16(a), Value::I16(b)) => self.stack.push(Value::I16(a % b)),
-                        (Value::I32(a), Value::I32(b)) => self.stack.push(Value::I32(a % b)),
-                        (Value::I64(a), Value::I64(b)) => self.stack.push(Value::I64(a % b)),
-                        (Value::Int(a), Value::Int(b)) => self.stack.push(Value::Int(a % b)),
-                        (Value::U8(a), Value::U8(b)) => self.stack.push(Value::U8(a % b)),
-                        (Value::U16(a), Value::U16(b)) => self.stack.push(Value::U16(a % b)),
-                        (Value::U32(a), Value::U32(b)) => self.stack.push(Value::U32(a % b)),
-                        (Value::U64(a), Value::U64(b)) => self.stack.push(Value::U64(a % b)),
-                        (Value::Uint(a), Value::Uint(b)) => self.stack.push(Value::Uint(a % b)),
-                        (Value::F32(a), Value::F32(b)) => self.stack.push(Value::F32(a % b)),
-                        (Value::F64(a), Value::F64(b)) => self.stack.push(Value::F64(a % b)),
+                        (Value::I8(a), Value::I8(b)) => self.push(Value::I8(a / b))?,
+                        (Value::I16(a), Value::I16(b)) => self.push(Value::I16(a / b))?,
+                        (Value::I32(a), Value::I32(b)) => self.push(Value::I32(a / b))?,
+                        (Value::I64(a), Value::I64(b)) => self.push(Value::I64(a / b))?,
+                        (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a / b))?,
+                        (Value::U8(a), Value::U8(b)) => self.push(Value::U8(a / b))?,
+                        (Value::U16(a), Value::U16(b)) => self.push(Value::U16(a / b))?,
+                        (Value::U32(a), Value::U32(b)) => self.push(Value::U32(a / b))?,
+                        (Value::U64(a), Value::U64(b)) => self.push(Value::U64(a / b))?,
+                        (Value::Uint(a), Value::Uint(b)) => self.push(Value::Uint(a / b))?,
+                        (Value::F32(a), Value::F32(b)) => self.push(Value::F32(a / b))?,
+                        (Value::F64(a), Value::F64(b)) => self.push(Value::F64(a / b))?,
                         _ => panic!(
                             "invalid types in binary operation `{}` : `{}` and `{}`",
-                            stringify!(%),
+                            stringify!(/),
                             a.to_string(),
                             b.to_string()
                         ),
@@ -403,19 +442,27 @@ impl<'vm> VM<'vm> {
                     let b = self.stack.pop();
                     let a = self.stack.pop();
 
+                    if is_int_zero(&b) {
+                        break Err(VMError::RemainderByZero);
+                    }
+
+                    if is_signed_division_overflow(&a, &b) {
+                        break Err(VMError::RemainderOverflow);
+                    }
+
                     match (&a, &b) {
-                        (Value::I8(a), Value::I8(b)) => self.stack.push(Value::I8(a % b)),
-                        (Value::I16(a), Value::I16(b)) => self.stack.push(Value::I16(a % b)),
-                        (Value::I32(a), Value::I32(b)) => self.stack.push(Value::I32(a % b)),
-                        (Value::I64(a), Value::I64(b)) => self.stack.push(Value::I64(a % b)),
-                        (Value::Int(a), Value::Int(b)) => self.stack.push(Value::Int(a % b)),
-                        (Value::U8(a), Value::U8(b)) => self.stack.push(Value::U8(a % b)),
-                        (Value::U16(a), Value::U16(b)) => self.stack.push(Value::U16(a % b)),
-                        (Value::U32(a), Value::U32(b)) => self.stack.push(Value::U32(a % b)),
-                        (Value::U64(a), Value::U64(b)) => self.stack.push(Value::U64(a % b)),
-                        (Value::Uint(a), Value::Uint(b)) => self.stack.push(Value::Uint(a % b)),
-                        (Value::F32(a), Value::F32(b)) => self.stack.push(Value::F32(a % b)),
-                        (Value::F64(a), Value::F64(b)) => self.stack.push(Value::F64(a % b)),
+                        (Value::I8(a), Value::I8(b)) => self.push(Value::I8(a % b))?,
+                        (Value::I16(a), Value::I16(b)) => self.push(Value::I16(a % b))?,
+                        (Value::I32(a), Value::I32(b)) => self.push(Value::I32(a % b))?,
+                        (Value::I64(a), Value::I64(b)) => self.push(Value::I64(a % b))?,
+                        (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a % b))?,
+                        (Value::U8(a), Value::U8(b)) => self.push(Value::U8(a % b))?,
+                        (Value::U16(a), Value::U16(b)) => self.push(Value::U16(a % b))?,
+                        (Value::U32(a), Value::U32(b)) => self.push(Value::U32(a % b))?,
+                        (Value::U64(a), Value::U64(b)) => self.push(Value::U64(a % b))?,
+                        (Value::Uint(a), Value::Uint(b)) => self.push(Value::Uint(a % b))?,
+                        (Value::F32(a), Value::F32(b)) => self.push(Value::F32(a % b))?,
+                        (Value::F64(a), Value::F64(b)) => self.push(Value::F64(a % b))?,
                         _ => panic!(
                             "invalid types in binary operation `{}` : `{}` and `{}`",
                             stringify!(%),
@@ -424,10 +471,19 @@ impl<'vm> VM<'vm> {
                         ),
                     }
                 }
-                Op::Neg => match self.stack.pop() {
-                    Value::Int(v) => self.stack.push(Value::Int(-v)),
-                    value => panic!("invalid value {}", value.to_string()),
-                },
+                Op::Neg => {
+                    let result = match self.stack.pop() {
+                        Value::I8(v) => Value::I8(v.wrapping_neg()),
+                        Value::I16(v) => Value::I16(v.wrapping_neg()),
+                        Value::I32(v) => Value::I32(v.wrapping_neg()),
+                        Value::I64(v) => Value::I64(v.wrapping_neg()),
+                        Value::Int(v) => Value::Int(v.wrapping_neg()),
+                        Value::F32(v) => Value::F32(-v),
+                        Value::F64(v) => Value::F64(-v),
+                        v => panic!("invalid value {}", v.to_string()),
+                    };
+                    self.push(result)?;
+                }
                 Op::Not => {
                     let result = match self.stack.pop() {
                         Value::I8(v) => Value::I8(!v),
@@ -443,12 +499,12 @@ impl<'vm> VM<'vm> {
                         Value::Bool(v) => Value::Bool(!v),
                         v => panic!("invalid value {}", v.to_string()),
                     };
-                    self.stack.push(result);
+                    self.push(result)?;
                 }
                 Op::Deref => match self.stack.pop() {
                     Value::Pointer(ptr) => {
                         let value = unsafe { ptr.deref_value() };
-                        self.stack.push(value);
+                        self.push(value)?;
                     }
                     value => panic!("invalid value {}", value.to_string()),
                 },
@@ -470,19 +526,19 @@ impl<'vm> VM<'vm> {
                 Op::Ge => {
                     compare_op!(self, >=);
                 }
-                Op::And => {
-                    logic_op!(self, &&);
+                Op::BitAnd => {
+                    binary_op_int_only!(self, &);
                 }
-                Op::Or => {
-                    logic_op!(self, ||);
+                Op::BitOr => {
+                    binary_op_int_only!(self, |);
                 }
                 Op::Shl => {
-                    binary_op_int_only!(self, <<)
+                    shift_op!(self, wrapping_shl);
                 }
                 Op::Shr => {
-                    binary_op_int_only!(self, >>);
+                    shift_op!(self, wrapping_shr);
                 }
-                Op::Xor => {
+                Op::BitXor => {
                     binary_op_int_only!(self, ^);
                 }
                 Op::Jmp => {
@@ -501,11 +557,11 @@ impl<'vm> VM<'vm> {
                     let return_value = self.stack.pop();
 
                     if self.frames.is_empty() {
-                        break return_value;
+                        break Ok(return_value);
                     } else {
                         self.stack.truncate(frame.stack_slot - frame.func().ty.params.len());
                         self.frame = self.frames.last_mut() as _;
-                        self.stack.push(return_value);
+                        self.push(return_value)?;
                     }
                 }
                 Op::Call => {
@@ -520,7 +576,9 @@ impl<'vm> VM<'vm> {
 
                             match function {
                                 FunctionValue::Orphan(function) => {
-                                    self.push_frame(function);
+                                    if let Err(err) = self.push_frame(function) {
+                                        break Err(err);
+                                    }
                                 }
                                 FunctionValue::Extern(function) => {
                                     let mut values = (0..arg_count)
@@ -534,31 +592,36 @@ impl<'vm> VM<'vm> {
                                     let vm_ptr = self as *mut _;
                                     let interp_ptr = self.interp as *const _;
 
-                                    let result = unsafe { self.interp.ffi.call(function, values, vm_ptr, interp_ptr) };
-
-                                    self.stack.push(result);
+                                    match unsafe { self.interp.ffi.call(function, values, vm_ptr, interp_ptr) } {
+                                        Ok(result) => self.push(result)?,
+                                        Err(err) => break Err(err),
+                                    }
                                 }
                             }
                         }
-                        Value::Intrinsic(intrinsic) => self.dispatch_intrinsic(intrinsic),
+                        Value::Intrinsic(intrinsic) => self.dispatch_intrinsic(intrinsic)?,
                         value => panic!("tried to call uncallable value `{}`", value.to_string()),
                     }
                 }
                 Op::LoadGlobal => {
                     let slot = reader.read_u32();
 
-                    match self.interp.globals.get(slot as usize) {
-                        Some(value) => self.stack.push(value.clone()),
+                    let value = match self.interp.globals.get(slot as usize) {
+                        Some(value) => value.clone(),
                         None => panic!("undefined global `{}`", slot),
-                    }
+                    };
+
+                    self.push(value)?;
                 }
                 Op::LoadGlobalPtr => {
                     let slot = reader.read_u32();
 
-                    match self.interp.globals.get_mut(slot as usize) {
-                        Some(value) => self.stack.push(Value::Pointer(value.into())),
+                    let value = match self.interp.globals.get_mut(slot as usize) {
+                        Some(value) => Value::Pointer(value.into()),
                         None => panic!("undefined global `{}`", slot),
-                    }
+                    };
+
+                    self.push(value)?;
                 }
                 Op::StoreGlobal => {
                     let slot = reader.read_u32();
@@ -569,7 +632,7 @@ impl<'vm> VM<'vm> {
                     let slot = self.frame().stack_slot as isize + offset as isize;
 
                     let value = self.stack.get(slot as usize).clone();
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 Op::PeekPtr => {
                     let offset = reader.read_i32();
@@ -577,7 +640,7 @@ impl<'vm> VM<'vm> {
 
                     let value = self.stack.get_mut(slot as usize);
                     let value = Value::Pointer(value.into());
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 Op::StoreLocal => {
                     let offset = reader.read_i32();
@@ -589,19 +652,19 @@ impl<'vm> VM<'vm> {
                 Op::Offset => {
                     let index = self.stack.pop().into_uint();
                     let value = self.stack.pop();
-                    self.offset(value, index);
+                    self.offset(value, index)?;
                 }
                 Op::ConstIndex => {
                     let index = reader.read_u32();
 
                     let value = self.stack.pop();
-                    self.index(value, index as usize);
+                    self.index(value, index as usize)?;
                 }
                 Op::ConstIndexPtr => {
                     let index = reader.read_u32();
 
                     let value = self.stack.pop();
-                    self.index_ptr(value, index as usize);
+                    self.index_ptr(value, index as usize)?;
                 }
                 Op::Assign => {
                     let lhs = self.stack.pop().into_pointer();
@@ -609,16 +672,16 @@ impl<'vm> VM<'vm> {
                     unsafe { lhs.write_value(rhs) }
                 }
                 Op::Cast => {
-                    self.cast_op();
+                    self.cast_op()?;
                 }
                 Op::BufferAlloc => {
                     let size = reader.read_u32();
 
                     let ty = self.stack.pop().into_type();
-                    self.stack.push(Value::Buffer(Buffer {
+                    self.push(Value::Buffer(Buffer {
                         bytes: ByteSeq::new(size as usize),
                         ty,
-                    }));
+                    }))?;
                 }
                 Op::BufferPut => {
                     let offset = reader.read_u32();
@@ -641,7 +704,7 @@ impl<'vm> VM<'vm> {
                 Op::Copy => {
                     let offset = reader.read_u32();
                     let value = self.stack.peek(offset as usize).clone();
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 Op::Swap => {
                     let offset = reader.read_u32();
@@ -650,23 +713,99 @@ impl<'vm> VM<'vm> {
                 }
                 Op::Halt => {
                     let result = self.stack.pop();
-                    break result;
+                    break Ok(result);
+                }
+                Op::Abort => {
+                    let code = match self.stack.pop() {
+                        Value::I32(code) => code,
+                        value => panic!("invalid value {}", value.to_string()),
+                    };
+
+                    break Err(VMError::Abort(code));
+                }
+                Op::Panic => {
+                    let message = match self.stack.pop() {
+                        Value::Buffer(buf) => buf.as_str().to_string(),
+                        value => panic!("invalid value {}", value.to_string()),
+                    };
+
+                    break Err(VMError::Panic(message));
+                }
+                Op::AddWithOverflow => {
+                    let overflow_offset = reader.read_u32();
+
+                    let b = self.stack.pop();
+                    let a = self.stack.pop();
+
+                    let (result, overflowed) = match (&a, &b) {
+                        (Value::I8(a), Value::I8(b)) => overflowing(a.overflowing_add(*b), Value::I8),
+                        (Value::I16(a), Value::I16(b)) => overflowing(a.overflowing_add(*b), Value::I16),
+                        (Value::I32(a), Value::I32(b)) => overflowing(a.overflowing_add(*b), Value::I32),
+                        (Value::I64(a), Value::I64(b)) => overflowing(a.overflowing_add(*b), Value::I64),
+                        (Value::Int(a), Value::Int(b)) => overflowing(a.overflowing_add(*b), Value::Int),
+                        (Value::U8(a), Value::U8(b)) => overflowing(a.overflowing_add(*b), Value::U8),
+                        (Value::U16(a), Value::U16(b)) => overflowing(a.overflowing_add(*b), Value::U16),
+                        (Value::U32(a), Value::U32(b)) => overflowing(a.overflowing_add(*b), Value::U32),
+                        (Value::U64(a), Value::U64(b)) => overflowing(a.overflowing_add(*b), Value::U64),
+                        (Value::Uint(a), Value::Uint(b)) => overflowing(a.overflowing_add(*b), Value::Uint),
+                        _ => panic!(
+                            "invalid types in `add_with_overflow` : `{}` and `{}`",
+                            a.to_string(),
+                            b.to_string()
+                        ),
+                    };
+
+                    let buf = self.stack.peek_mut(0).as_buffer_mut();
+                    buf.bytes.offset_mut(0).put_value(&result);
+                    buf.bytes.offset_mut(overflow_offset as usize).put_value(&Value::Bool(overflowed));
+                }
+                Op::MemCmp => {
+                    let len = match self.stack.pop() {
+                        Value::Uint(len) => len,
+                        value => panic!("invalid value {}", value.to_string()),
+                    };
+
+                    let b = self.stack.pop();
+                    let a = self.stack.pop();
+
+                    let (a, b) = unsafe {
+                        (
+                            std::slice::from_raw_parts(pointer_bytes(a.as_pointer()), len),
+                            std::slice::from_raw_parts(pointer_bytes(b.as_pointer()), len),
+                        )
+                    };
+
+                    self.push(Value::I32(a.cmp(b) as i32))?;
                 }
             }
         }
     }
 
     #[inline]
-    pub fn push_frame(&mut self, function: *const Function) {
+    pub fn push_frame(&mut self, function: *const Function) -> VMResult<()> {
+        let func = unsafe { &*function };
         let stack_slot = self.stack.len();
 
-        for _ in 0..unsafe { &*function }.code.locals {
-            self.stack.push(Value::default());
+        for _ in 0..func.code.locals {
+            self.stack
+                .push(Value::default())
+                .map_err(|StackOverflow| VMError::StackOverflow(func.name))?;
         }
 
-        self.frames.push(StackFrame::<'vm>::new(function, stack_slot));
+        self.frames
+            .push(StackFrame::<'vm>::new(function, stack_slot))
+            .map_err(|StackOverflow| VMError::StackOverflow(func.name))?;
 
         self.frame = self.frames.last_mut() as _;
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: Value) -> VMResult<()> {
+        self.stack
+            .push(value)
+            .map_err(|StackOverflow| VMError::StackOverflow(self.frame().func().name))
     }
 
     #[inline]
@@ -696,50 +835,54 @@ impl<'vm> VM<'vm> {
     }
 
     #[inline]
-    fn index(&mut self, value: Value, index: usize) {
+    fn index(&mut self, value: Value, index: usize) -> VMResult<()> {
         match value {
             Value::Pointer(ref ptr) => match ptr {
                 Pointer::Buffer(buf) => {
                     let buf = unsafe { &**buf };
                     let value = buf.get_value_at_index(index);
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 _ => panic!("invalid value {}", value.to_string()),
             },
             Value::Buffer(buf) => {
                 let value = buf.get_value_at_index(index);
-                self.stack.push(value);
+                self.push(value)?;
             }
             _ => panic!("invalid value {}", value.to_string()),
         }
+
+        Ok(())
     }
 
     #[inline]
-    fn index_ptr(&mut self, value: Value, index: usize) {
+    fn index_ptr(&mut self, value: Value, index: usize) -> VMResult<()> {
         match value {
             Value::Pointer(ref ptr) => match ptr {
                 Pointer::Buffer(buf) => {
                     let buf = unsafe { &mut **buf };
                     let ptr = buf.bytes.offset_mut(index).as_mut_ptr();
                     let value = Value::Pointer(Pointer::from_type_and_ptr(buf.ty.element_type().unwrap(), ptr as _));
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 _ => panic!("invalid value {}", value.to_string()),
             },
-            Value::Buffer(_) => self.offset(value, index),
+            Value::Buffer(_) => self.offset(value, index)?,
             _ => panic!("invalid value {}", value.to_string()),
         }
+
+        Ok(())
     }
 
     #[inline]
-    fn offset(&mut self, value: Value, offset: usize) {
+    fn offset(&mut self, value: Value, offset: usize) -> VMResult<()> {
         match value {
             Value::Pointer(ptr) => match ptr {
                 Pointer::Buffer(buf) => {
                     let buf = unsafe { &mut *buf };
                     let ptr = buf.bytes.offset_mut(offset).as_mut_ptr();
                     let value = Value::Pointer(Pointer::from_type_and_ptr(&buf.ty, ptr as _));
-                    self.stack.push(value);
+                    self.push(value)?;
                 }
                 ptr => {
                     let ptr = if ptr.is_pointer() {
@@ -751,23 +894,24 @@ impl<'vm> VM<'vm> {
                     let raw = ptr.as_inner_raw();
                     let offset = unsafe { raw.add(offset) };
 
-                    self.stack
-                        .push(Value::Pointer(Pointer::from_kind_and_ptr(ptr.kind(), offset)))
+                    self.push(Value::Pointer(Pointer::from_kind_and_ptr(ptr.kind(), offset)))?;
                 }
             },
             Value::Buffer(buf) => {
                 let bytes = buf.bytes.offset(offset);
                 let ptr = &bytes[0];
-                self.stack.push(Value::Pointer(Pointer::from_type_and_ptr(
+                self.push(Value::Pointer(Pointer::from_type_and_ptr(
                     &buf.ty,
                     ptr as *const u8 as *mut u8 as _,
-                )));
+                )))?;
             }
             _ => panic!("invalid value {}", value.to_string()),
         }
+
+        Ok(())
     }
 
-    fn dispatch_intrinsic(&mut self, intrinsic: IntrinsicFunction) {
+    fn dispatch_intrinsic(&mut self, intrinsic: IntrinsicFunction) -> VMResult<()> {
         match intrinsic {
             IntrinsicFunction::StartWorkspace => {
                 let value = self.stack.pop();
@@ -785,25 +929,45 @@ impl<'vm> VM<'vm> {
 
                 let build_options = BuildOptions {
                     source_file,
+                    source_contents: None,
                     output_file: Some(output_file),
                     target_platform: match &workspace_value.build_options.target {
                         BuildTargetValue::Auto => TargetPlatform::current().unwrap(),
                         BuildTargetValue::Linux => TargetPlatform::LinuxAmd64,
                         BuildTargetValue::Windows => TargetPlatform::WindowsAmd64,
+                        BuildTargetValue::Macos => TargetPlatform::DarwinAmd64,
                     },
                     optimization_level: match &workspace_value.build_options.optimization_level {
                         OptimizationLevelValue::Debug => OptimizationLevel::Debug,
                         OptimizationLevelValue::Release => OptimizationLevel::Release,
                     },
                     emit_times: self.interp.build_options.emit_times,
+                    print_timings: false,
+                    emit_ast: self.interp.build_options.emit_ast,
                     emit_hir: self.interp.build_options.emit_hir,
+                    emit_hir_json: self.interp.build_options.emit_hir_json,
+                    emit_hir_dot: self.interp.build_options.emit_hir_dot,
                     emit_bytecode: self.interp.build_options.emit_bytecode,
+                    print_imports: self.interp.build_options.print_imports,
                     diagnostic_options: self.interp.build_options.diagnostic_options.clone(),
                     codegen_options: CodegenOptions::Codegen {
                         emit_llvm_ir: self.interp.build_options.codegen_options.emit_llvm_ir(),
+                        emit_asm: false,
+                        emit_obj: false,
+                        emit_map: false,
+                        print_stats: false,
                     },
                     include_paths: vec![],
+                    link_objects: vec![],
+                    link_libraries: vec![],
                     check_mode: false,
+                    no_entry: false,
+                    test_runner: false,
+                    max_type_var_depth: self.interp.build_options.max_type_var_depth,
+                    max_expr_depth: self.interp.build_options.max_expr_depth,
+                    default_int_type: self.interp.build_options.default_int_type,
+                    warn_default_int: self.interp.build_options.warn_default_int,
+                    warn_partial_unpack: self.interp.build_options.warn_partial_unpack,
                 };
 
                 let result = crate::driver::start_workspace(workspace_value.name.to_string(), build_options);
@@ -826,13 +990,15 @@ impl<'vm> VM<'vm> {
                     result_type,
                 ));
 
-                self.stack.push(result_value);
+                self.push(result_value)?;
             }
         }
+
+        Ok(())
     }
 
     #[inline]
-    fn cast_op(&mut self) {
+    fn cast_op(&mut self) -> VMResult<()> {
         let ty = self.stack.pop().into_type();
         let value = self.stack.pop();
 
@@ -877,7 +1043,9 @@ impl<'vm> VM<'vm> {
             _ => panic!("{:?}", ty),
         };
 
-        self.stack.push(new_value);
+        self.push(new_value)?;
+
+        Ok(())
     }
 
     #[allow(unused)]
@@ -925,3 +1093,142 @@ pub enum TraceLevel {
     Minimal,
     Full,
 }
+
+// Adds two values. Arrays of numbers are added element-wise
+fn add_values(a: &Value, b: &Value) -> Value {
+    match (a, b) {
+        (Value::I8(a), Value::I8(b)) => Value::I8(a + b),
+        (Value::I16(a), Value::I16(b)) => Value::I16(a + b),
+        (Value::I32(a), Value::I32(b)) => Value::I32(a + b),
+        (Value::I64(a), Value::I64(b)) => Value::I64(a + b),
+        (Value::Int(a), Value::Int(b)) => Value::Int(a + b),
+        (Value::U8(a), Value::U8(b)) => Value::U8(a + b),
+        (Value::U16(a), Value::U16(b)) => Value::U16(a + b),
+        (Value::U32(a), Value::U32(b)) => Value::U32(a + b),
+        (Value::U64(a), Value::U64(b)) => Value::U64(a + b),
+        (Value::Uint(a), Value::Uint(b)) => Value::Uint(a + b),
+        (Value::F32(a), Value::F32(b)) => Value::F32(a + b),
+        (Value::F64(a), Value::F64(b)) => Value::F64(a + b),
+        (Value::Pointer(a), Value::Int(b)) => Value::Pointer(unsafe { a.offset(*b) }),
+        (Value::Buffer(a), Value::Buffer(b)) => Value::Buffer(Buffer::from_values(
+            a.get_all_values()
+                .iter()
+                .zip(b.get_all_values().iter())
+                .map(|(a, b)| add_values(a, b)),
+            a.ty.clone(),
+        )),
+        _ => panic!(
+            "invalid types in binary operation `{}` : `{}` and `{}`",
+            stringify!(+),
+            a.to_string(),
+            b.to_string()
+        ),
+    }
+}
+
+// Subtracts two values. Arrays of numbers are subtracted element-wise
+fn sub_values(a: &Value, b: &Value) -> Value {
+    match (a, b) {
+        (Value::I8(a), Value::I8(b)) => Value::I8(a - b),
+        (Value::I16(a), Value::I16(b)) => Value::I16(a - b),
+        (Value::I32(a), Value::I32(b)) => Value::I32(a - b),
+        (Value::I64(a), Value::I64(b)) => Value::I64(a - b),
+        (Value::Int(a), Value::Int(b)) => Value::Int(a - b),
+        (Value::U8(a), Value::U8(b)) => Value::U8(a - b),
+        (Value::U16(a), Value::U16(b)) => Value::U16(a - b),
+        (Value::U32(a), Value::U32(b)) => Value::U32(a - b),
+        (Value::U64(a), Value::U64(b)) => Value::U64(a - b),
+        (Value::Uint(a), Value::Uint(b)) => Value::Uint(a - b),
+        (Value::F32(a), Value::F32(b)) => Value::F32(a - b),
+        (Value::F64(a), Value::F64(b)) => Value::F64(a - b),
+        (Value::Pointer(a), Value::Int(b)) => Value::Pointer(unsafe { a.offset(-*b) }),
+        (Value::Buffer(a), Value::Buffer(b)) => Value::Buffer(Buffer::from_values(
+            a.get_all_values()
+                .iter()
+                .zip(b.get_all_values().iter())
+                .map(|(a, b)| sub_values(a, b)),
+            a.ty.clone(),
+        )),
+        _ => panic!(
+            "invalid types in binary operation `{}` : `{}` and `{}`",
+            stringify!(-),
+            a.to_string(),
+            b.to_string()
+        ),
+    }
+}
+
+// Multiplies two values. Arrays of numbers are multiplied element-wise
+fn mul_values(a: &Value, b: &Value) -> Value {
+    match (a, b) {
+        (Value::I8(a), Value::I8(b)) => Value::I8(a * b),
+        (Value::I16(a), Value::I16(b)) => Value::I16(a * b),
+        (Value::I32(a), Value::I32(b)) => Value::I32(a * b),
+        (Value::I64(a), Value::I64(b)) => Value::I64(a * b),
+        (Value::Int(a), Value::Int(b)) => Value::Int(a * b),
+        (Value::U8(a), Value::U8(b)) => Value::U8(a * b),
+        (Value::U16(a), Value::U16(b)) => Value::U16(a * b),
+        (Value::U32(a), Value::U32(b)) => Value::U32(a * b),
+        (Value::U64(a), Value::U64(b)) => Value::U64(a * b),
+        (Value::Uint(a), Value::Uint(b)) => Value::Uint(a * b),
+        (Value::F32(a), Value::F32(b)) => Value::F32(a * b),
+        (Value::F64(a), Value::F64(b)) => Value::F64(a * b),
+        (Value::Buffer(a), Value::Buffer(b)) => Value::Buffer(Buffer::from_values(
+            a.get_all_values()
+                .iter()
+                .zip(b.get_all_values().iter())
+                .map(|(a, b)| mul_values(a, b)),
+            a.ty.clone(),
+        )),
+        _ => panic!(
+            "invalid types in binary operation `{}` : `{}` and `{}`",
+            stringify!(*),
+            a.to_string(),
+            b.to_string()
+        ),
+    }
+}
+
+// Integer division by zero is an error, while float division by zero follows IEEE semantics (inf/nan)
+fn is_int_zero(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::I8(0)
+            | Value::I16(0)
+            | Value::I32(0)
+            | Value::I64(0)
+            | Value::Int(0)
+            | Value::U8(0)
+            | Value::U16(0)
+            | Value::U32(0)
+            | Value::U64(0)
+            | Value::Uint(0)
+    )
+}
+
+// Dividing the minimum value of a signed integer by -1 overflows, since the result isn't representable
+fn is_signed_division_overflow(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::I8(a), Value::I8(b)) => a.checked_div(*b).is_none(),
+        (Value::I16(a), Value::I16(b)) => a.checked_div(*b).is_none(),
+        (Value::I32(a), Value::I32(b)) => a.checked_div(*b).is_none(),
+        (Value::I64(a), Value::I64(b)) => a.checked_div(*b).is_none(),
+        (Value::Int(a), Value::Int(b)) => a.checked_div(*b).is_none(),
+        _ => false,
+    }
+}
+
+// Returns the address of the bytes a pointer points to.
+// A pointer to a buffer points to its `Buffer` value, rather than to its bytes
+#[inline]
+fn pointer_bytes(ptr: &Pointer) -> *const u8 {
+    match ptr {
+        Pointer::Buffer(buf) => unsafe { (**buf).bytes.as_ptr() },
+        ptr => ptr.as_inner_raw() as *const u8,
+    }
+}
+
+#[inline]
+fn overflowing<T>((result, overflowed): (T, bool), f: impl FnOnce(T) -> Value) -> (Value, bool) {
+    (f(result), overflowed)
+}
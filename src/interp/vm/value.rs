@@ -550,6 +550,11 @@ impl Value {
         })
     }
 
+    pub fn zeroed(ty: &Type) -> Self {
+        let mut bytes = ByteSeq::new(ty.size_of(WORD_SIZE));
+        unsafe { Self::from_type_and_ptr(ty, bytes.as_mut_ptr() as RawPointer) }
+    }
+
     pub unsafe fn from_type_and_ptr(ty: &Type, ptr: RawPointer) -> Self {
         match ty {
             Type::Never | Type::Unit => Self::unit(),
@@ -85,6 +85,7 @@ pub enum BuildTargetValue {
     Auto = 0,
     Linux = 1,
     Windows = 2,
+    Macos = 3,
 }
 
 impl From<&Value> for BuildTargetValue {
@@ -93,6 +94,7 @@ impl From<&Value> for BuildTargetValue {
             0 => Self::Auto,
             1 => Self::Linux,
             2 => Self::Windows,
+            3 => Self::Macos,
             x => panic!("{}", x),
         }
     }
@@ -1,16 +1,21 @@
+mod redundant_cast;
 mod ref_access;
 mod type_limits;
+mod unreachable_code;
+mod unused_binding;
 
 use crate::{hir, infer::type_ctx::TypeCtx, workspace::Workspace};
 
 pub fn lint(workspace: &mut Workspace, tcx: &TypeCtx, cache: &hir::Cache) {
-    let mut sess = LintSess { workspace, tcx };
+    let mut sess = LintSess { workspace, tcx, cache };
     cache.lint(&mut sess);
+    sess.check_unused_bindings();
 }
 
 pub struct LintSess<'s> {
     pub workspace: &'s mut Workspace,
     pub tcx: &'s TypeCtx,
+    pub cache: &'s hir::Cache,
 }
 
 trait Lint {
@@ -87,6 +92,7 @@ impl Lint for hir::Function {
 impl Lint for hir::Sequence {
     fn lint(&self, sess: &mut LintSess) {
         self.statements.lint(sess);
+        sess.check_unreachable_code(self);
     }
 }
 
@@ -122,6 +128,7 @@ impl Lint for hir::Call {
 impl Lint for hir::Cast {
     fn lint(&self, sess: &mut LintSess) {
         self.value.lint(sess);
+        sess.check_redundant_cast(self);
     }
 }
 
@@ -151,6 +158,7 @@ impl Lint for hir::Builtin {
             | hir::Builtin::Mul(x)
             | hir::Builtin::Div(x)
             | hir::Builtin::Rem(x)
+            | hir::Builtin::AddWithOverflow(x)
             | hir::Builtin::Shl(x)
             | hir::Builtin::Shr(x)
             | hir::Builtin::And(x)
@@ -168,6 +176,13 @@ impl Lint for hir::Builtin {
             hir::Builtin::Ref(x) => x.lint(sess),
             hir::Builtin::Offset(x) => x.lint(sess),
             hir::Builtin::Slice(x) => x.lint(sess),
+            hir::Builtin::MemCmp(x) => x.lint(sess),
+            hir::Builtin::Asm(x) => x.lint(sess),
+            hir::Builtin::Assume(x) => x.lint(sess),
+            hir::Builtin::Breakpoint(_) => (),
+            hir::Builtin::Abort(x) => x.lint(sess),
+            hir::Builtin::Zeroed(_) => (),
+            hir::Builtin::Panic(x) => x.lint(sess),
         }
     }
 }
@@ -210,6 +225,22 @@ impl Lint for hir::Slice {
     }
 }
 
+impl Lint for hir::MemCmp {
+    fn lint(&self, sess: &mut LintSess) {
+        self.lhs.lint(sess);
+        self.rhs.lint(sess);
+        self.len.lint(sess);
+    }
+}
+
+impl Lint for hir::Asm {
+    fn lint(&self, sess: &mut LintSess) {
+        for input in self.inputs.iter() {
+            input.lint(sess);
+        }
+    }
+}
+
 impl Lint for hir::Literal {
     fn lint(&self, sess: &mut LintSess) {
         match self {
@@ -0,0 +1,66 @@
+use super::LintSess;
+use crate::{
+    error::diagnostic::{Diagnostic, Label},
+    hir,
+    infer::{display::DisplayType, normalize::Normalize},
+};
+
+impl<'s> LintSess<'s> {
+    pub fn check_redundant_cast(&mut self, cast: &hir::Cast) {
+        if cast.span.is_unknown() || self.is_redundant_cast_allowed(cast) {
+            return;
+        }
+
+        // Only the main library is linted, so redundant casts in the standard library don't generate noise
+        if !self.tcx.main_library_files.contains(&cast.span.file_id) {
+            return;
+        }
+
+        let from = cast.value.ty().normalize(self.tcx);
+        let to = cast.ty.normalize(self.tcx);
+
+        if from != to {
+            return;
+        }
+
+        // Implicit casts are inserted by coercions, and share their value's span
+        let is_autocast = cast.span == cast.value.span();
+
+        let diagnostic = if is_autocast {
+            Diagnostic::warning()
+                .with_message(format!(
+                    "redundant implicit cast from `{}` to `{}`",
+                    from.display(self.tcx),
+                    to.display(self.tcx)
+                ))
+                .with_label(Label::primary(
+                    cast.span,
+                    format!("value is implicitly cast to its own type `{}`", to.display(self.tcx)),
+                ))
+        } else {
+            Diagnostic::warning()
+                .with_message(format!(
+                    "redundant cast from `{}` to `{}`",
+                    from.display(self.tcx),
+                    to.display(self.tcx)
+                ))
+                .with_label(Label::primary(
+                    cast.span,
+                    format!("value is already of type `{}`", to.display(self.tcx)),
+                ))
+                .with_note("consider removing the cast")
+        };
+
+        self.workspace
+            .diagnostics
+            .push(diagnostic.with_note("this warning can be silenced with the `@allow_redundant_cast` attribute"));
+    }
+
+    fn is_redundant_cast_allowed(&self, cast: &hir::Cast) -> bool {
+        self.cache.redundant_cast_allowances.iter().any(|span| {
+            span.file_id == cast.span.file_id
+                && span.start.index <= cast.span.start.index
+                && cast.span.end.index <= span.end.index
+        })
+    }
+}
@@ -2,7 +2,7 @@ use super::LintSess;
 use crate::{
     error::diagnostic::{Diagnostic, Label},
     hir::{self, const_value::ConstValue},
-    infer::{display::DisplayType, normalize::Normalize},
+    infer::{display::DisplayType, normalize::Concrete},
     span::Span,
     types::{IntType, Type, UintType},
 };
@@ -10,19 +10,23 @@ use std::fmt::Display;
 
 impl<'s> LintSess<'s> {
     pub fn check_type_limits(&mut self, const_: &hir::Const) {
+        // `int` and `uint` are as wide as the target's word, which may differ from the host's
+        let word_size = self.workspace.build_options.target_platform.metrics().word_size;
+
+        // Integer literals whose type couldn't be inferred are checked against the type they fall back to
         match &const_.value {
-            &ConstValue::Int(value) => match &const_.ty.normalize(self.tcx) {
+            &ConstValue::Int(value) => match &const_.ty.concrete(self.tcx) {
                 Type::Int(int_type) => {
-                    let (min, max) = int_type_range(*int_type);
+                    let (min, max) = int_type_range(*int_type, word_size);
 
                     if value < min as i128 || value > max as i128 {
                         self.push_overflow_err(value, &const_.ty.display(self.tcx), min, max, const_.span);
                     }
                 }
                 Type::Uint(uint_type) => {
-                    let (_, max) = uint_type_range(*uint_type);
+                    let (_, max) = uint_type_range(*uint_type, word_size);
 
-                    if value < 0 || value as u64 > max {
+                    if value < 0 || value > max as i128 {
                         self.push_overflow_err(value, &const_.ty.display(self.tcx), 0, max, const_.span);
                     }
                 }
@@ -51,22 +55,48 @@ impl<'s> LintSess<'s> {
     }
 }
 
-fn int_type_range(int_ty: IntType) -> (i64, i64) {
+fn int_type_range(int_ty: IntType, word_size: usize) -> (i64, i64) {
     match int_ty {
         IntType::I8 => (i8::MIN as i64, i8::MAX as i64),
         IntType::I16 => (i16::MIN as i64, i16::MAX as i64),
         IntType::I32 => (i32::MIN as i64, i32::MAX as i64),
         IntType::I64 => (i64::MIN, i64::MAX),
-        IntType::Int => (isize::MIN as i64, isize::MAX as i64),
+        IntType::Int => match word_size {
+            4 => int_type_range(IntType::I32, word_size),
+            _ => int_type_range(IntType::I64, word_size),
+        },
     }
 }
 
-fn uint_type_range(uint_ty: UintType) -> (u64, u64) {
+fn uint_type_range(uint_ty: UintType, word_size: usize) -> (u64, u64) {
     match uint_ty {
         UintType::U8 => (u8::MIN as u64, u8::MAX as u64),
         UintType::U16 => (u16::MIN as u64, u16::MAX as u64),
         UintType::U32 => (u32::MIN as u64, u32::MAX as u64),
         UintType::U64 => (u64::MIN, u64::MAX),
-        UintType::Uint => (usize::MIN as u64, usize::MAX as u64),
+        UintType::Uint => match word_size {
+            4 => uint_type_range(UintType::U32, word_size),
+            _ => uint_type_range(UintType::U64, word_size),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{int_type_range, uint_type_range};
+    use crate::types::{IntType, UintType};
+
+    #[test]
+    fn word_sized_ranges_follow_the_target() {
+        assert_eq!(int_type_range(IntType::Int, 4), (i32::MIN as i64, i32::MAX as i64));
+        assert_eq!(int_type_range(IntType::Int, 8), (i64::MIN, i64::MAX));
+        assert_eq!(uint_type_range(UintType::Uint, 4), (0, u32::MAX as u64));
+        assert_eq!(uint_type_range(UintType::Uint, 8), (0, u64::MAX));
+    }
+
+    #[test]
+    fn fixed_ranges_ignore_the_target() {
+        assert_eq!(int_type_range(IntType::I16, 4), int_type_range(IntType::I16, 8));
+        assert_eq!(uint_type_range(UintType::U64, 4), (0, u64::MAX));
     }
 }
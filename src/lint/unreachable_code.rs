@@ -0,0 +1,43 @@
+use super::LintSess;
+use crate::{
+    error::diagnostic::{Diagnostic, Label},
+    hir,
+    infer::normalize::Normalize,
+};
+
+impl<'s> LintSess<'s> {
+    pub fn check_unreachable_code(&mut self, sequence: &hir::Sequence) {
+        // Only blocks written by the user are checked, since sequences generated by the compiler can't be fixed by it
+        if !sequence.is_scope {
+            return;
+        }
+
+        let diverging_index = sequence
+            .statements
+            .iter()
+            .position(|statement| statement.ty().normalize(self.tcx).is_never());
+
+        let (diverging, unreachable) = match diverging_index {
+            Some(index) => match sequence.statements.get(index + 1) {
+                Some(unreachable) => (&sequence.statements[index], unreachable),
+                // The diverging expression is the block's trailing expression
+                None => return,
+            },
+            None => return,
+        };
+
+        if unreachable.span().is_unknown() {
+            return;
+        }
+
+        self.workspace.diagnostics.push(
+            Diagnostic::warning()
+                .with_message("unreachable statement")
+                .with_label(Label::primary(unreachable.span(), "unreachable statement"))
+                .with_label(Label::secondary(
+                    diverging.span(),
+                    "any code following this expression is unreachable",
+                )),
+        );
+    }
+}
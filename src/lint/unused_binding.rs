@@ -0,0 +1,84 @@
+use super::LintSess;
+use crate::{
+    ast,
+    error::diagnostic::{Diagnostic, Label},
+    hir::const_value::ConstValue,
+    span::Span,
+    workspace::{BindingInfo, BindingInfoFlags, BindingInfoKind},
+};
+use ustr::Ustr;
+
+impl<'s> LintSess<'s> {
+    pub fn check_unused_bindings(&mut self) {
+        let unused_bindings: Vec<(&'static str, Ustr, Span)> = self
+            .workspace
+            .binding_infos
+            .iter()
+            .filter(|(_, binding_info)| self.is_unused_binding(binding_info))
+            .map(|(_, binding_info)| (binding_kind_name(&binding_info.kind), binding_info.name, binding_info.span))
+            .collect();
+
+        for (kind, name, span) in unused_bindings {
+            self.workspace.diagnostics.push(
+                Diagnostic::warning()
+                    .with_message(format!("unused {} `{}`", kind, name))
+                    .with_label(Label::primary(span, "never used"))
+                    .with_note(format!("if this is intentional, prefix it with an underscore: `_{}`", name)),
+            );
+        }
+    }
+
+    fn is_unused_binding(&self, binding_info: &BindingInfo) -> bool {
+        // Compiler-synthesized bindings (such as the implicit `it` parameter), bindings ignored with `_`,
+        // and public bindings are never reported
+        if !binding_info.flags.contains(BindingInfoFlags::IS_USER_DEFINED)
+            || binding_info
+                .flags
+                .intersects(BindingInfoFlags::IGNORE | BindingInfoFlags::IMPLICIT_IT_FUNCTION_PARAM)
+            || binding_info.vis == ast::Vis::Public
+            || !binding_info.uses.is_empty()
+            || binding_info.name.starts_with('_')
+            || binding_info.span.is_unknown()
+        {
+            return false;
+        }
+
+        // Only the main library is linted, so unused bindings in the standard library don't generate noise
+        let module_info = self.workspace.module_infos.get(binding_info.module_id).unwrap();
+
+        if module_info.library_id != self.workspace.main_library().id {
+            return false;
+        }
+
+        // The entry point is called by the runtime
+        if binding_info.module_id == self.workspace.root_module_id
+            && binding_info.scope_level.is_global()
+            && binding_info.name == "main"
+        {
+            return false;
+        }
+
+        // Exported bindings are used by foreign code
+        if self.cache.exported_bindings.iter().any(|exported| exported.id == binding_info.id) {
+            return false;
+        }
+
+        // Test functions are called by the test runner
+        if let Some(ConstValue::Function(function)) = &binding_info.const_value {
+            if self.cache.test_functions.contains(&function.id) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn binding_kind_name(kind: &BindingInfoKind) -> &'static str {
+    match kind {
+        BindingInfoKind::Function | BindingInfoKind::ExternFunction => "function",
+        BindingInfoKind::Type => "type",
+        BindingInfoKind::LetStatic | BindingInfoKind::ExternVariable => "static",
+        _ => "binding",
+    }
+}
@@ -5,6 +5,7 @@ mod check;
 mod common;
 mod driver;
 mod error;
+mod fmt;
 mod hir;
 mod ide;
 mod infer;
@@ -17,14 +18,20 @@ mod token;
 mod types;
 mod workspace;
 
-use crate::common::{
-    build_options::{BuildOptions, CodegenOptions, DiagnosticOptions, OptimizationLevel},
-    target::TargetPlatform,
+use crate::{
+    common::{
+        build_options::{BuildOptions, CodegenOptions, DefaultIntType, DiagnosticOptions, OptimizationLevel},
+        target::TargetPlatform,
+    },
+    error::emitter::ColorMode,
+    infer::type_ctx::DEFAULT_MAX_TYPE_VAR_DEPTH,
+    parse::DEFAULT_MAX_EXPR_DEPTH,
 };
 use clap::*;
 use colored::Colorize;
 use path_absolutize::Absolutize;
 use std::{
+    io::Read,
     path::{Path, PathBuf},
     process::Command,
 };
@@ -38,8 +45,12 @@ use std::{
     long_about = "Compiler for the Chili programming language"
 )]
 struct Args {
+    #[clap(subcommand)]
+    action: Option<Action>,
+
     /// The main action the compiler should take.
-    input: String,
+    /// Pass `-` to read the root file's source from stdin.
+    input: Option<String>,
 
     // Modes
     //
@@ -48,6 +59,10 @@ struct Args {
     #[clap(long, short)]
     run: bool,
 
+    /// Arguments passed to the program's entry point in Run mode, following `--`.
+    #[clap(last = true, requires = "run")]
+    program_args: Vec<String>,
+
     /// Enables Check mode - which only checks the input file, skipping code generation.
     /// Check mode also enables additional language support flags.
     #[clap(long, short)]
@@ -60,29 +75,106 @@ struct Args {
     #[clap(long)]
     emit_times: bool,
 
+    /// Print a breakdown of the time spent in each compiler phase, after compilation.
+    #[clap(long)]
+    print_timings: bool,
+
+    /// Write the untyped ast of every module into <output>.ast.txt.
+    #[clap(long)]
+    emit_ast: bool,
+
     /// Print trace information verbosely.
     #[clap(long)]
     emit_hir: bool,
 
+    /// Write the typed tree as JSON into <output>.hir.json, including each node's kind, type and span.
+    #[clap(long)]
+    emit_hir_json: bool,
+
+    /// Write the typed tree as a Graphviz graph into <output>.hir.dot.
+    #[clap(long)]
+    emit_hir_dot: bool,
+
     /// Print trace information verbosely.
     #[clap(long)]
     emit_bytecode: bool,
 
+    /// Print the resolved imports of every module.
+    #[clap(long)]
+    print_imports: bool,
+
     /// Emit LLVM IR file.
     #[clap(long)]
     emit_llvm_ir: bool,
 
+    /// Emit a target assembly file next to the output, and continue compiling. Reflects the current optimization level.
+    #[clap(long, conflicts_with = "check")]
+    emit_asm: bool,
+
+    /// Write a map of every emitted function and global, with its section and estimated size, next to the output.
+    #[clap(long, conflicts_with = "check")]
+    emit_map: bool,
+
+    /// Print the number of emitted functions, basic blocks and LLVM instructions, and the object file's size.
+    #[clap(long, conflicts_with = "check")]
+    stats: bool,
+
+    /// Compile into an object file without an entry point, exporting the root module's public bindings.
+    /// Use this to build a library, which doesn't define `main`. Executables built with `--run` still require `main`.
+    #[clap(long, visible_alias = "lib", conflicts_with = "run")]
+    no_entry: bool,
+
+    /// Emit an object file, without linking it into an executable.
+    #[clap(long, conflicts_with_all = &["run", "check"])]
+    emit_obj: bool,
+
+    /// The path of the output file. Defaults to the input file's path, with the platform's executable extension.
+    #[clap(long, short)]
+    output: Option<String>,
+
+    /// The optimization level. Defaults to `release` in Run mode, and to `debug` otherwise.
+    #[clap(long, arg_enum)]
+    opt_level: Option<OptimizationLevel>,
+
     // Misc options
     //
     //
     /// Omit colors from output.
-    #[clap(long)]
+    #[clap(long, global = true)]
     no_color: bool,
 
     /// Additional include paths, separated by ;.
-    #[clap(long)]
+    #[clap(long, global = true)]
     include_paths: Option<String>,
 
+    /// Additional object file to link into the executable. Can be specified multiple times.
+    #[clap(long = "link-obj")]
+    link_obj: Vec<String>,
+
+    /// Additional library to link into the executable. Can be specified multiple times.
+    #[clap(long = "link-lib")]
+    link_lib: Vec<String>,
+
+    /// The maximum number of bound type variables followed while unifying types, guarding against infinite types.
+    #[clap(long, global = true, default_value_t = DEFAULT_MAX_TYPE_VAR_DEPTH)]
+    max_type_var_depth: usize,
+
+    /// The maximum depth of nested expressions. Deeper expressions are rejected, instead of overflowing the stack.
+    #[clap(long, global = true, default_value_t = DEFAULT_MAX_EXPR_DEPTH)]
+    max_expr_depth: usize,
+
+    /// The type of integer literals in the main library whose type can't be inferred. Such float literals are always `f64`.
+    #[clap(long, global = true, arg_enum, default_value = "i32")]
+    default_int: DefaultIntType,
+
+    /// Warn about integer literals whose type can't be inferred, and fall back to the default integer type.
+    #[clap(long)]
+    warn_default_int: bool,
+
+    /// Warn about struct unpack patterns that leave some of the struct's fields unbound, and have no `*` glob.
+    #[clap(long)]
+    warn_partial_unpack: bool,
+
     // Check mode options
     //
     //
@@ -100,80 +192,384 @@ struct Args {
     /// Return the hover info for a given index, in the given input file.
     #[clap(long)]
     goto_def: Option<usize>,
+
+    /// Only available in Check mode.
+    /// Return a JSON index of every public top-level binding, in all modules.
+    #[clap(long)]
+    symbol_index: bool,
+
+    /// Only available in Check mode.
+    /// Re-check the input file whenever it, or any file imported by it, changes - printing its diagnostics each time.
+    #[clap(long)]
+    watch: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Action {
+    /// Check the input file and print its typed tree.
+    Ast(AstArgs),
+
+    /// Format the input file in place, normalizing its indentation and whitespace.
+    Fmt(FmtArgs),
+
+    /// Compile the input file into a test executable, which runs all of its functions annotated with `@test`.
+    Test(TestArgs),
+
+    /// Demangle a symbol name generated by the compiler.
+    Demangle {
+        /// The mangled symbol name.
+        symbol: String,
+    },
+
+    /// Print the compiler's version.
+    Version {
+        /// Also print the LLVM version, the host target and the supported targets.
+        #[clap(long, short)]
+        verbose: bool,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct FmtArgs {
+    /// The file to format.
+    input: String,
+
+    /// Don't rewrite the file. Instead, exit with a non-zero code if it isn't formatted.
+    #[clap(long)]
+    check: bool,
+}
+
+#[derive(Parser, Debug)]
+struct TestArgs {
+    /// The file to test.
+    input: String,
+}
+
+#[derive(Parser, Debug)]
+struct AstArgs {
+    /// The file to print the tree of.
+    input: String,
+
+    /// Print the parsed ast, without type checking it.
+    #[clap(long)]
+    untyped: bool,
+
+    /// Only print the tree of the module with the given qualified name.
+    #[clap(long)]
+    module: Option<String>,
+
+    /// Print the source location of every node in the typed tree, as `@file:start..end`.
+    #[clap(long)]
+    spans: bool,
 }
 
 fn cli() {
     let args = Args::parse();
 
-    match get_file_path(&args.input) {
-        Ok(source_file) => {
+    match (&args.action, &args.input) {
+        (Some(Action::Ast(ast_args)), _) => print_ast(&args, ast_args),
+        (Some(Action::Fmt(fmt_args)), _) => format(&args, fmt_args),
+        (Some(Action::Test(test_args)), _) => test(&args, test_args),
+        (Some(Action::Demangle { symbol }), _) => match backend::llvm::mangle::demangle(symbol) {
+            Some(name) => println!("{}", name),
+            None => print_err(&format!("`{}` is not a valid symbol name", symbol)),
+        },
+        (Some(Action::Version { verbose }), _) => print_version(*verbose),
+        (None, Some(input)) => build(&args, input),
+        (None, None) => print_err("no input file was specified"),
+    }
+}
+
+fn print_version(verbose: bool) {
+    println!("chili {}", env!("CARGO_PKG_VERSION"));
+
+    if verbose {
+        println!("llvm: {}", backend::llvm::LLVM_VERSION);
+        println!("host: {}", backend::llvm::host_target_triple());
+        println!("targets:");
+
+        for target in TargetPlatform::supported() {
+            println!("  {:?} ({})", target, target.metrics().target_triplet);
+        }
+    }
+}
+
+fn print_ast(args: &Args, ast_args: &AstArgs) {
+    match get_input(&ast_args.input) {
+        Ok((source_file, source_contents)) => {
+            let name = get_workspace_name(&source_file);
+
+            let build_options = BuildOptions {
+                source_file,
+                source_contents,
+                output_file: None,
+                target_platform: current_target_platform(),
+                optimization_level: OptimizationLevel::Debug,
+                emit_times: false,
+                print_timings: false,
+                emit_ast: false,
+                emit_hir: false,
+                emit_hir_json: false,
+                emit_hir_dot: false,
+                emit_bytecode: false,
+                print_imports: false,
+                diagnostic_options: DiagnosticOptions::Emit {
+                    no_color: args.no_color,
+                },
+                codegen_options: CodegenOptions::Skip { emit_llvm_ir: false },
+                include_paths: get_include_paths(&args.include_paths),
+                link_objects: vec![],
+                link_libraries: vec![],
+                check_mode: false,
+                no_entry: false,
+                test_runner: false,
+                max_type_var_depth: args.max_type_var_depth,
+                max_expr_depth: args.max_expr_depth,
+                default_int_type: args.default_int,
+                warn_default_int: false,
+                warn_partial_unpack: false,
+            };
+
+            driver::print_ast(name, build_options, ast_args.untyped, ast_args.module.as_deref(), ast_args.spans);
+        }
+        Err(e) => print_err(&e),
+    }
+}
+
+fn test(args: &Args, test_args: &TestArgs) {
+    match get_input(&test_args.input) {
+        Ok((source_file, source_contents)) => {
+            let name = get_workspace_name(&source_file);
+
+            // tests are compiled into an executable of their own, next to the input file
+            let output_file = source_file.with_file_name(format!(
+                "{}_test",
+                source_file.file_stem().unwrap().to_str().unwrap()
+            ));
+
+            let build_options = BuildOptions {
+                source_file,
+                source_contents,
+                output_file: Some(output_file),
+                target_platform: current_target_platform(),
+                optimization_level: OptimizationLevel::Debug,
+                emit_times: false,
+                print_timings: args.print_timings,
+                emit_ast: false,
+                emit_hir: false,
+                emit_hir_json: false,
+                emit_hir_dot: false,
+                emit_bytecode: false,
+                print_imports: false,
+                diagnostic_options: DiagnosticOptions::Emit {
+                    no_color: args.no_color,
+                },
+                codegen_options: CodegenOptions::Codegen {
+                    emit_llvm_ir: false,
+                    emit_asm: false,
+                    emit_obj: false,
+                    emit_map: false,
+                    print_stats: false,
+                },
+                include_paths: get_include_paths(&args.include_paths),
+                link_objects: vec![],
+                link_libraries: vec![],
+                check_mode: false,
+                no_entry: false,
+                test_runner: true,
+                max_type_var_depth: args.max_type_var_depth,
+                max_expr_depth: args.max_expr_depth,
+                default_int_type: args.default_int,
+                warn_default_int: args.warn_default_int,
+                warn_partial_unpack: args.warn_partial_unpack,
+            };
+
+            if !driver::test(name, build_options) {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => print_err(&e),
+    }
+}
+
+fn format(args: &Args, fmt_args: &FmtArgs) {
+    let path = match get_file_path(&fmt_args.input) {
+        Ok(path) => path,
+        Err(e) => return print_err(&e),
+    };
+
+    let color_mode = if args.no_color { ColorMode::Never } else { ColorMode::Always };
+
+    match fmt::format_file(&path, fmt_args.check, color_mode) {
+        fmt::FormatOutcome::Unchanged => (),
+        fmt::FormatOutcome::Changed => {
+            if fmt_args.check {
+                print_err(&format!("`{}` is not formatted", fmt_args.input));
+                std::process::exit(1);
+            }
+        }
+        fmt::FormatOutcome::Failed => {
+            print_err(&format!("failed to format `{}`", fmt_args.input));
+            std::process::exit(1);
+        }
+        fmt::FormatOutcome::Io(error) => {
+            print_err(&format!("failed to format `{}`: {}", path.display(), error));
+            std::process::exit(1);
+        }
+    }
+}
+
+fn build(args: &Args, input: &str) {
+    let output_file = match get_output_file(&args.output) {
+        Ok(output_file) => output_file,
+        Err(e) => return print_err(&e),
+    };
+
+    match get_input(input) {
+        Ok((source_file, source_contents)) => {
             let name = get_workspace_name(&source_file);
 
             if args.run {
                 let build_options = BuildOptions {
                     source_file,
-                    output_file: None,
+                    source_contents,
+                    output_file,
                     target_platform: current_target_platform(),
-                    optimization_level: OptimizationLevel::Release,
+                    optimization_level: args.opt_level.unwrap_or(OptimizationLevel::Release),
                     emit_times: args.emit_times,
+                    print_timings: args.print_timings,
+                    emit_ast: args.emit_ast,
                     emit_hir: args.emit_hir,
+                    emit_hir_json: args.emit_hir_json,
+                    emit_hir_dot: args.emit_hir_dot,
                     emit_bytecode: args.emit_bytecode,
+                    print_imports: args.print_imports,
                     diagnostic_options: DiagnosticOptions::Emit {
                         no_color: args.no_color,
                     },
                     codegen_options: CodegenOptions::Codegen {
                         emit_llvm_ir: args.emit_llvm_ir,
+                        emit_asm: args.emit_asm,
+                        emit_obj: false,
+                        emit_map: args.emit_map,
+                        print_stats: args.stats,
                     },
                     include_paths: get_include_paths(&args.include_paths),
+                    link_objects: get_link_objects(&args.link_obj),
+                    link_libraries: args.link_lib.clone(),
                     check_mode: false,
+                    no_entry: false,
+                    test_runner: false,
+                    max_type_var_depth: args.max_type_var_depth,
+                    max_expr_depth: args.max_expr_depth,
+                    default_int_type: args.default_int,
+                    warn_default_int: args.warn_default_int,
+                    warn_partial_unpack: args.warn_partial_unpack,
                 };
 
                 let result = driver::start_workspace(name, build_options);
 
                 if let Some(output_file) = &result.output_file {
-                    let _ = Command::new(output_file).spawn();
+                    let _ = Command::new(output_file).args(&args.program_args).spawn();
                 }
             } else if args.check {
                 let build_options = BuildOptions {
                     source_file,
+                    source_contents,
                     output_file: None,
                     target_platform: current_target_platform(),
-                    optimization_level: OptimizationLevel::Debug,
+                    optimization_level: args.opt_level.unwrap_or(OptimizationLevel::Debug),
                     emit_times: false,
+                    print_timings: args.print_timings,
+                    emit_ast: args.emit_ast,
                     emit_hir: false,
+                    emit_hir_json: args.emit_hir_json,
+                    emit_hir_dot: args.emit_hir_dot,
                     emit_bytecode: false,
+                    print_imports: false,
                     diagnostic_options: DiagnosticOptions::DontEmit,
                     codegen_options: CodegenOptions::Skip { emit_llvm_ir: false },
                     include_paths: get_include_paths(&args.include_paths),
+                    link_objects: vec![],
+                    link_libraries: vec![],
                     check_mode: true,
+                    no_entry: false,
+                    test_runner: false,
+                    max_type_var_depth: args.max_type_var_depth,
+                    max_expr_depth: args.max_expr_depth,
+                    default_int_type: args.default_int,
+                    warn_default_int: args.warn_default_int,
+                    warn_partial_unpack: args.warn_partial_unpack,
                 };
 
-                let result = driver::start_workspace(name, build_options);
+                if args.watch {
+                    if build_options.source_contents.is_some() {
+                        return print_err("cannot watch source read from stdin");
+                    }
+
+                    return driver::watch(name, build_options);
+                }
+
+                // all requested queries are answered against the same check result, in order
+                let session = driver::IdeSession::start(name, build_options);
 
                 if args.diagnostics {
-                    ide::diagnostics(&result.workspace, result.tcx.as_ref(), result.cache.as_ref());
-                } else if let Some(offset) = args.hover_info {
-                    ide::hover_info(&result.workspace, result.tcx.as_ref(), offset);
-                } else if let Some(offset) = args.goto_def {
-                    ide::goto_definition(&result.workspace, result.tcx.as_ref(), offset);
+                    session.diagnostics();
+                }
+
+                if let Some(offset) = args.hover_info {
+                    session.hover_info(offset);
+                }
+
+                if let Some(offset) = args.goto_def {
+                    session.goto_definition(offset);
+                }
+
+                if args.symbol_index {
+                    session.symbol_index();
                 }
             } else {
                 let build_options = BuildOptions {
                     source_file,
-                    output_file: None,
+                    source_contents,
+                    output_file,
                     target_platform: current_target_platform(),
-                    optimization_level: OptimizationLevel::Debug,
+                    optimization_level: args.opt_level.unwrap_or(OptimizationLevel::Debug),
                     emit_times: args.emit_times,
+                    print_timings: args.print_timings,
+                    emit_ast: args.emit_ast,
                     emit_hir: args.emit_hir,
+                    emit_hir_json: args.emit_hir_json,
+                    emit_hir_dot: args.emit_hir_dot,
                     emit_bytecode: args.emit_bytecode,
+                    print_imports: args.print_imports,
                     diagnostic_options: DiagnosticOptions::Emit {
                         no_color: args.no_color,
                     },
-                    codegen_options: CodegenOptions::Skip {
-                        emit_llvm_ir: args.emit_llvm_ir,
+                    codegen_options: if args.no_entry || args.emit_obj || args.emit_asm || args.emit_map || args.stats {
+                        CodegenOptions::Codegen {
+                            emit_llvm_ir: args.emit_llvm_ir,
+                            emit_asm: args.emit_asm,
+                            emit_obj: args.emit_obj,
+                            emit_map: args.emit_map,
+                            print_stats: args.stats,
+                        }
+                    } else {
+                        CodegenOptions::Skip {
+                            emit_llvm_ir: args.emit_llvm_ir,
+                        }
                     },
                     include_paths: get_include_paths(&args.include_paths),
+                    link_objects: get_link_objects(&args.link_obj),
+                    link_libraries: args.link_lib.clone(),
                     check_mode: false,
+                    no_entry: args.no_entry,
+                    test_runner: false,
+                    max_type_var_depth: args.max_type_var_depth,
+                    max_expr_depth: args.max_expr_depth,
+                    default_int_type: args.default_int,
+                    warn_default_int: args.warn_default_int,
+                    warn_partial_unpack: args.warn_partial_unpack,
                 };
 
                 driver::start_workspace(name, build_options);
@@ -193,6 +589,28 @@ fn get_workspace_name(source_file: &Path) -> String {
         .to_string()
 }
 
+// The name given to source piped from stdin. It is placed in the current directory, so that imports are resolved relative to it
+const STDIN_FILE_NAME: &str = "stdin.chl";
+
+// Returns the root file's path, and its contents when they're read from stdin, which is denoted by an input of `-`
+fn get_input(input_file: &str) -> Result<(PathBuf, Option<String>), String> {
+    if input_file == "-" {
+        let mut source = String::new();
+
+        std::io::stdin()
+            .read_to_string(&mut source)
+            .map_err(|e| format!("failed to read from stdin: {}", e))?;
+
+        let path = std::env::current_dir()
+            .map_err(|e| format!("failed to get the current directory: {}", e))?
+            .join(STDIN_FILE_NAME);
+
+        Ok((path, Some(source)))
+    } else {
+        get_file_path(input_file).map(|path| (path, None))
+    }
+}
+
 fn get_file_path(input_file: &str) -> Result<PathBuf, String> {
     let path = Path::new(input_file).absolutize().unwrap();
 
@@ -205,6 +623,23 @@ fn get_file_path(input_file: &str) -> Result<PathBuf, String> {
     }
 }
 
+fn get_output_file(output_file: &Option<String>) -> Result<Option<PathBuf>, String> {
+    let output_file = match output_file {
+        Some(output_file) => output_file,
+        None => return Ok(None),
+    };
+
+    let path = Path::new(output_file).absolutize().unwrap();
+
+    if path.is_dir() {
+        Err(format!("output file `{}` is a directory", output_file))
+    } else if !path.parent().map_or(false, |parent| parent.is_dir()) {
+        Err(format!("the directory of output file `{}` doesn't exist", output_file))
+    } else {
+        Ok(Some(path.to_path_buf()))
+    }
+}
+
 fn current_target_platform() -> TargetPlatform {
     match TargetPlatform::current() {
         Ok(t) => t,
@@ -225,6 +660,13 @@ fn get_include_paths(include_paths: &Option<String>) -> Vec<PathBuf> {
         .map_or_else(|| vec![], |i| i.split(';').map(|s| PathBuf::from(s)).collect())
 }
 
+fn get_link_objects(link_objects: &[String]) -> Vec<PathBuf> {
+    link_objects
+        .iter()
+        .map(|path| Path::new(path).absolutize().unwrap().to_path_buf())
+        .collect()
+}
+
 fn main() {
     std::thread::Builder::new()
         .name("main".to_string())
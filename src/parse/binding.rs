@@ -1,6 +1,7 @@
 use super::*;
 use crate::{
     ast::pat::{GlobPat, NamePat, Pat, StructPat, StructSubPat},
+    error::diagnostic::{Diagnostic, Label},
     types::FunctionTypeKind,
     workspace::BindingId,
 };
@@ -83,6 +84,14 @@ impl Parser {
 
         let (sig, _) = self.parse_function_sig(Some(name), FunctionTypeKind::Orphan, true)?;
 
+        // a function signature without a body is a declaration, which is only valid for extern functions
+        if !is!(self, Eq) {
+            return Err(Diagnostic::error()
+                .with_message(format!("function `{}` is declared but never defined", name))
+                .with_label(Label::primary(start_span.to(self.previous_span()), "declared here"))
+                .with_note("to declare a function that is defined elsewhere, use `extern fn`"));
+        }
+
         require!(self, Eq, "=")?;
 
         let body = Box::new(self.parse_expression(false, false)?);
@@ -50,6 +50,14 @@ impl Parser {
     }
 
     fn parse_expression_inner(&mut self, allow_assignments: bool, allow_newlines: bool) -> DiagnosticResult<Ast> {
+        // every operator in a chain of binary expressions adds a level to the resulting tree
+        let depth = self.expr_depth;
+        let result = self.parse_binary_expression(allow_assignments, allow_newlines);
+        self.expr_depth = depth;
+        result
+    }
+
+    fn parse_binary_expression(&mut self, allow_assignments: bool, allow_newlines: bool) -> DiagnosticResult<Ast> {
         let mut expr_stack: Vec<Ast> = vec![];
         let mut op_stack: Vec<ast::BinaryOp> = vec![];
         let mut last_precedence = 1000000;
@@ -73,6 +81,7 @@ impl Parser {
                     }
 
                     self.bump();
+                    self.enter_expr()?;
 
                     op
                 }
@@ -262,8 +271,32 @@ impl Parser {
 
     pub fn parse_operand(&mut self) -> DiagnosticResult<Ast> {
         self.skip_newlines();
-        let expr = self.parse_operand_base()?;
-        self.parse_operand_postfix_operator(expr)
+
+        let depth = self.expr_depth;
+        self.enter_expr()?;
+
+        let result = self
+            .parse_operand_base()
+            .and_then(|expr| self.parse_operand_postfix_operator(expr));
+
+        self.expr_depth = depth;
+        result
+    }
+
+    fn enter_expr(&mut self) -> DiagnosticResult<()> {
+        self.expr_depth += 1;
+
+        if self.expr_depth > self.max_expr_depth {
+            Err(Diagnostic::error()
+                .with_message("expression nesting too deep")
+                .with_label(Label::primary(
+                    self.span(),
+                    format!("exceeds the maximum nesting depth of {}", self.max_expr_depth),
+                ))
+                .with_note("the maximum depth can be raised with `--max-expr-depth`"))
+        } else {
+            Ok(())
+        }
     }
 
     pub fn parse_operand_base(&mut self) -> DiagnosticResult<Ast> {
@@ -356,7 +389,7 @@ impl Parser {
             self.parse_array_type_or_literal()
         } else if eat!(self, Break | Continue | Return) {
             self.parse_terminator()
-        } else if eat!(self, Nil | True | False | Int(_) | Float(_) | Str(_) | Char(_)) {
+        } else if eat!(self, Nil | Null | True | False | Int(_) | Float(_) | Str(_) | Char(_)) {
             self.parse_literal()
         } else if eat!(self, OpenParen) {
             let start_span = self.previous_span();
@@ -486,6 +519,12 @@ impl Parser {
             CloseCurly,
             Comma,
             {
+                let vis = if eat!(self, Pub) {
+                    ast::Vis::Public
+                } else {
+                    ast::Vis::Private
+                };
+
                 let id = self.require_ident()?;
                 let name = id.name();
 
@@ -503,6 +542,7 @@ impl Parser {
                 ast::StructTypeField {
                     name,
                     ty,
+                    vis,
                     span: id.span,
                 }
             },
@@ -518,6 +558,83 @@ impl Parser {
         let kind = match name.as_str() {
             "size_of" => ast::BuiltinKind::SizeOf(Box::new(self.parse_expression(false, true)?)),
             "align_of" => ast::BuiltinKind::AlignOf(Box::new(self.parse_expression(false, true)?)),
+            "size_of_val" => ast::BuiltinKind::SizeOfVal(Box::new(self.parse_expression(false, true)?)),
+            "align_of_val" => ast::BuiltinKind::AlignOfVal(Box::new(self.parse_expression(false, true)?)),
+            "type_id" => ast::BuiltinKind::TypeId(Box::new(self.parse_expression(false, true)?)),
+            "type_eq" => {
+                let a = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let b = self.parse_expression(false, true)?;
+                ast::BuiltinKind::TypeEq(Box::new(a), Box::new(b))
+            }
+            "field_type" => {
+                let ty = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let field = self.parse_expression(false, true)?;
+                ast::BuiltinKind::FieldType(Box::new(ty), Box::new(field))
+            }
+            "field_count" => ast::BuiltinKind::FieldCount(Box::new(self.parse_expression(false, true)?)),
+            "include_str" => ast::BuiltinKind::IncludeStr(Box::new(self.parse_expression(false, true)?)),
+            "is_aligned" => {
+                let ptr = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let align = self.parse_expression(false, true)?;
+                ast::BuiltinKind::IsAligned(Box::new(ptr), Box::new(align))
+            }
+            "assume" => ast::BuiltinKind::Assume(Box::new(self.parse_expression(false, true)?)),
+            "breakpoint" => ast::BuiltinKind::Breakpoint,
+            "src" => ast::BuiltinKind::Src,
+            "abort" => ast::BuiltinKind::Abort(Box::new(self.parse_expression(false, true)?)),
+            "zeroed" => ast::BuiltinKind::Zeroed(Box::new(self.parse_expression(false, true)?)),
+            "panic" => ast::BuiltinKind::Panic(Box::new(self.parse_expression(false, true)?)),
+            "ref" => {
+                let is_mutable = eat!(self, Mut);
+                ast::BuiltinKind::Ref(Box::new(self.parse_expression(false, true)?), is_mutable)
+            }
+            "deref" => ast::BuiltinKind::Deref(Box::new(self.parse_expression(false, true)?)),
+            "add_with_overflow" => {
+                let lhs = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let rhs = self.parse_expression(false, true)?;
+                ast::BuiltinKind::AddWithOverflow(Box::new(lhs), Box::new(rhs))
+            }
+            "memcmp" => {
+                let lhs = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let rhs = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let len = self.parse_expression(false, true)?;
+                ast::BuiltinKind::MemCmp(Box::new(lhs), Box::new(rhs), Box::new(len))
+            }
+            "splat" => {
+                let ty = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let len = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let value = self.parse_expression(false, true)?;
+                ast::BuiltinKind::Splat(Box::new(ty), Box::new(len), Box::new(value))
+            }
+            "asm" => {
+                let ty = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let template = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let constraints = self.parse_expression(false, true)?;
+
+                let mut inputs = vec![];
+
+                while eat!(self, Comma) {
+                    inputs.push(self.parse_expression(false, true)?);
+                }
+
+                ast::BuiltinKind::Asm(Box::new(ty), Box::new(template), Box::new(constraints), inputs)
+            }
+            "call" => {
+                let callee = self.parse_expression(false, true)?;
+                require!(self, Comma, ",")?;
+                let args = self.parse_expression(false, true)?;
+                ast::BuiltinKind::Call(Box::new(callee), Box::new(args))
+            }
             name => {
                 return Err(Diagnostic::error()
                     .with_message(format!("unknown builtin function `{}`", name))
@@ -607,13 +724,27 @@ impl Parser {
         }))
     }
 
+    fn parse_loop_control(&mut self, span: Span) -> ast::LoopControl {
+        match self.peek().kind {
+            Int(depth) => {
+                self.bump();
+
+                ast::LoopControl {
+                    depth: Some(usize::try_from(depth).unwrap_or(usize::MAX)),
+                    span: span.to(self.previous_span()),
+                }
+            }
+            _ => ast::LoopControl { depth: None, span },
+        }
+    }
+
     pub fn parse_terminator(&mut self) -> DiagnosticResult<Ast> {
         let token = self.previous();
         let span = token.span;
 
         match token.kind {
-            Break => Ok(Ast::Break(ast::Empty { span })),
-            Continue => Ok(Ast::Continue(ast::Empty { span })),
+            Break => Ok(Ast::Break(self.parse_loop_control(span))),
+            Continue => Ok(Ast::Continue(self.parse_loop_control(span))),
             Return => {
                 self.skip_newlines();
 
@@ -13,6 +13,7 @@ impl Parser {
 
         let kind = match &token.kind {
             Nil => LiteralKind::Nil,
+            Null => LiteralKind::Null,
             True => LiteralKind::Bool(true),
             False => LiteralKind::Bool(false),
             Int(value) => LiteralKind::Int(*value as i128),
@@ -133,8 +133,15 @@ pub struct Parser {
     module_info: ModuleInfo,
     module_path: ModulePath,
     restrictions: Restrictions,
+    // The depth of the expression currently being parsed, and its maximum allowed depth
+    expr_depth: usize,
+    max_expr_depth: usize,
 }
 
+// The default maximum depth of nested expressions.
+// Deeper expressions are rejected, since parsing and checking them recursively could overflow the stack
+pub const DEFAULT_MAX_EXPR_DEPTH: usize = 256;
+
 #[derive(Debug)]
 pub struct ParserCache {
     pub module_infos: IdCache<ModuleId, ModuleInfo>,
@@ -143,6 +150,9 @@ pub struct ParserCache {
     pub diagnostics: Diagnostics,
     pub parsed_files: HashSet<Ustr>,
     pub total_lines: u32,
+    pub max_expr_depth: usize,
+    // The root file's path and contents, when its contents aren't read from disk
+    pub root_source: Option<(Ustr, String)>,
 }
 
 pub enum ParserResult {
@@ -160,6 +170,8 @@ impl Parser {
         module_path: ModulePath,
         module_info: ModuleInfo,
     ) -> Self {
+        let max_expr_depth = cache.lock().max_expr_depth;
+
         Self {
             cache,
             thread_pool,
@@ -169,6 +181,8 @@ impl Parser {
             module_info,
             module_path,
             restrictions: Restrictions::empty(),
+            expr_depth: 0,
+            max_expr_depth,
         }
     }
 
@@ -184,7 +198,12 @@ impl Parser {
             if !cache.parsed_files.insert(self.module_info.file_path) {
                 return ParserResult::AlreadyParsed;
             } else {
-                match std::fs::read_to_string(self.module_info.file_path.as_str()) {
+                let source = match &cache.root_source {
+                    Some((path, source)) if *path == self.module_info.file_path => Ok(source.clone()),
+                    _ => std::fs::read_to_string(self.module_info.file_path.as_str()),
+                };
+
+                match source {
                     Ok(source) => {
                         cache.total_lines += source.lines().count() as u32;
 
@@ -1,9 +1,14 @@
 use super::*;
 use crate::{
-    ast,
+    ast::{
+        self,
+        pat::{NamePat, Pat},
+    },
     error::{diagnostic::Label, SyntaxError},
     span::FileId,
+    workspace::BindingId,
 };
+use std::ops::Range;
 
 impl Parser {
     pub fn parse_module(&mut self, file_id: FileId) -> ParserResult {
@@ -22,6 +27,11 @@ impl Parser {
     }
 
     pub fn parse_top_level(&mut self, module: &mut ast::Module) -> DiagnosticResult<()> {
+        if eat!(self, Static) {
+            require!(self, If, "if")?;
+            return self.parse_static_if(module);
+        }
+
         let attrs = self.parse_attrs()?;
         let has_attrs = !attrs.is_empty();
 
@@ -31,6 +41,10 @@ impl Parser {
             ast::Vis::Private
         };
 
+        if eat!(self, Const) {
+            return self.parse_const_block(module, attrs, vis);
+        }
+
         match self.try_parse_any_binding(attrs, vis, true)? {
             Some(binding) => {
                 module.bindings.push(binding?);
@@ -58,4 +72,133 @@ impl Parser {
             }
         }
     }
+
+    // Parses a `const { A = 1; B = 2 }` block, which is sugar for declaring each constant with its own `let`.
+    // The block's attributes and visibility apply to all of its constants.
+    fn parse_const_block(
+        &mut self,
+        module: &mut ast::Module,
+        attrs: Vec<ast::Attr>,
+        vis: ast::Vis,
+    ) -> DiagnosticResult<()> {
+        require!(self, OpenCurly, "{")?;
+
+        let mut defined_names = UstrMap::<Span>::default();
+
+        loop {
+            self.skip_newlines();
+
+            if eat!(self, CloseCurly) {
+                break;
+            }
+
+            let id = self.require_ident()?;
+            let name = id.name();
+
+            if let Some(already_defined_span) = defined_names.insert(name, id.span) {
+                return Err(SyntaxError::duplicate_binding(name, id.span, already_defined_span));
+            }
+
+            let type_expr = if eat!(self, Colon) {
+                Some(Box::new(self.parse_expression(false, false)?))
+            } else {
+                None
+            };
+
+            require!(self, Eq, "=")?;
+
+            let mut value = self.parse_expression(false, false)?;
+            Self::assign_expr_name_if_needed(&mut value, name);
+
+            module.bindings.push(ast::Binding {
+                attrs: attrs.clone(),
+                vis,
+                kind: ast::BindingKind::Let {
+                    pat: Pat::Name(NamePat {
+                        id: BindingId::unknown(),
+                        name,
+                        span: id.span,
+                        is_mutable: false,
+                        ignore: false,
+                    }),
+                    type_expr,
+                    value: Box::new(value),
+                },
+                span: id.span.to(self.previous_span()),
+            });
+
+            if !eat!(self, Semicolon | Newline) {
+                require!(self, CloseCurly, "; or }")?;
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_static_if(&mut self, module: &mut ast::Module) -> DiagnosticResult<()> {
+        let start_span = self.previous_span();
+
+        self.skip_newlines();
+
+        let condition = self.parse_expression_res(Restrictions::NO_STRUCT_LITERAL, false, true)?;
+
+        self.skip_newlines();
+
+        let then_bindings = self.parse_static_if_branch(module)?;
+
+        let last_index = self.current;
+
+        self.skip_newlines();
+
+        let else_bindings = if eat!(self, Else) {
+            self.skip_newlines();
+
+            if eat!(self, If) {
+                let start = module.bindings.len();
+                self.parse_static_if(module)?;
+                start..module.bindings.len()
+            } else {
+                self.parse_static_if_branch(module)?
+            }
+        } else {
+            self.current = last_index;
+            let end = module.bindings.len();
+            end..end
+        };
+
+        module.static_ifs.push(ast::StaticIf {
+            condition: Box::new(condition),
+            then_bindings,
+            else_bindings,
+            span: start_span.to(self.previous_span()),
+        });
+
+        Ok(())
+    }
+
+    fn parse_static_if_branch(&mut self, module: &mut ast::Module) -> DiagnosticResult<Range<usize>> {
+        require!(self, OpenCurly, "{")?;
+
+        let start = module.bindings.len();
+        let comptime_blocks_len = module.comptime_blocks.len();
+
+        while !eat!(self, CloseCurly) {
+            if self.eof() {
+                return Err(SyntaxError::expected(self.span(), "}"));
+            }
+
+            self.parse_top_level(module)?;
+        }
+
+        if module.comptime_blocks.len() != comptime_blocks_len {
+            let span = module.comptime_blocks.last().unwrap().span;
+
+            return Err(Diagnostic::error()
+                .with_message("comptime blocks are not allowed inside a `static if`")
+                .with_label(Label::primary(span, "")));
+        }
+
+        Ok(start..module.bindings.len())
+    }
 }
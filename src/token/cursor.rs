@@ -55,6 +55,11 @@ impl Cursor {
         Span::new(self.file_id, self.start, EndPosition { index: self.end.index })
     }
 
+    // The span of the character right after the cursor's end
+    pub fn next_char_span(&self) -> Span {
+        Span::new(self.file_id, self.end, EndPosition { index: self.end.index + 1 })
+    }
+
     pub fn end_span(&self) -> Span {
         Span::new(
             self.file_id,
@@ -276,7 +276,8 @@ impl<'lx> Lexer<'lx> {
 
     fn eat_char(&mut self) -> DiagnosticResult<TokenKind> {
         while self.peek() != SINGLE_QUOTE && !self.is_eof() {
-            if self.peek() == '\\' && self.peek_next() == '"' {
+            // an escaped character can't terminate the literal, i.e: '\''
+            if self.peek() == '\\' {
                 self.bump();
                 self.bump();
             } else {
@@ -301,12 +302,7 @@ impl<'lx> Lexer<'lx> {
 
         let contents = chars.as_str().to_string();
 
-        if contents.len() != 1 {
-            return Err(Diagnostic::error()
-                .with_message("character literal must be one character long")
-                .with_label(Label::primary(self.cursor.span(), "not one character long")));
-        }
-
+        // escape sequences are unescaped first, so that i.e '\n' counts as one character
         let contents = unescape(&contents, self.cursor.span()).map_err(|e| match e {
             UnescapeError::InvalidEscapeSequence(span) => {
                 let message = "unknown escape sequence";
@@ -316,7 +312,28 @@ impl<'lx> Lexer<'lx> {
             }
         })?;
 
-        Ok(Char(contents.chars().next().unwrap()))
+        let mut chars = contents.chars();
+
+        let char = match (chars.next(), chars.next()) {
+            (Some(char), None) => char,
+            _ => {
+                return Err(Diagnostic::error()
+                    .with_message("character literal must be one character long")
+                    .with_label(Label::primary(self.cursor.span(), "not one character long")))
+            }
+        };
+
+        // character literals are typed as `u8`, and only ASCII characters are encoded as a single byte in UTF-8.
+        // this is consistent with `\xNN` escapes, which are ASCII only
+        if !char.is_ascii() {
+            return Err(Diagnostic::error()
+                .with_message(format!("character literal `{}` is not ASCII", char))
+                .with_label(Label::primary(self.cursor.span(), "not an ASCII character"))
+                .with_note("character literals are typed as `u8`, so they can only contain ASCII characters")
+                .with_note("use an integer literal for bytes above `0x7F`"));
+        }
+
+        Ok(Char(char))
     }
 
     fn eat_line(&mut self) {
@@ -328,23 +345,20 @@ impl<'lx> Lexer<'lx> {
     fn eat_number(&mut self) -> DiagnosticResult<TokenKind> {
         if self.peek_previous() == '0' {
             match self.peek() {
-                'x' | 'X' => return self.eat_number_hex(),
-                'o' | 'O' => return self.eat_number_octal(),
-                'b' | 'B' => return self.eat_number_binary(),
+                'x' | 'X' => return self.eat_number_radix(16, "hexadecimal"),
+                'o' | 'O' => return self.eat_number_radix(8, "octal"),
+                'b' | 'B' => return self.eat_number_radix(2, "binary"),
                 _ => (),
             }
         }
 
-        while self.peek().is_ascii_digit() || self.peek() == '_' {
-            self.bump();
-        }
+        // the first digit was already eaten
+        self.eat_digits(10, 1)?;
 
         if self.peek() == '.' && self.peek_next().is_ascii_digit() {
             self.bump();
 
-            while self.peek().is_ascii_digit() || self.peek() == '_' {
-                self.bump();
-            }
+            self.eat_digits(10, 0)?;
 
             let literal = self.source.range(self.cursor);
             let float = literal.replace('_', "").parse::<f64>().unwrap();
@@ -359,88 +373,64 @@ impl<'lx> Lexer<'lx> {
         }
     }
 
-    fn eat_number_hex(&mut self) -> DiagnosticResult<TokenKind> {
-        let mut hex_value = String::from("");
-
+    // Eats the digits of an integer literal with a `0x`, `0o` or `0b` prefix.
+    // The digits continue up to the first character that can't continue an identifier,
+    // so that `0b102` is reported as an invalid digit, instead of being split into `0b10` and `2`
+    fn eat_number_radix(&mut self, radix: u32, radix_name: &str) -> DiagnosticResult<TokenKind> {
+        // eat the radix's letter
         self.bump();
 
-        while !self.is_eof() {
-            let char = self.peek();
+        let digit_count = self.eat_digits(radix, 0)?;
 
-            if ('0'..='9').contains(&char) || ('A'..='F').contains(&char) || ('a'..='f').contains(&char) {
-                hex_value.push(char);
-                self.bump();
-            } else {
-                break;
-            }
+        if Self::is_id_continue(self.peek()) {
+            return Err(LexerError::invalid_digit(
+                self.cursor.next_char_span(),
+                self.peek(),
+                radix_name,
+            ));
         }
 
-        match u128::from_str_radix(&hex_value, 16) {
-            Ok(n) => Ok(Int(n)),
-            Err(_) => Err(LexerError::integer_too_large(self.cursor.span())),
+        if digit_count == 0 {
+            return Err(LexerError::missing_digits(self.cursor.span(), radix_name));
         }
-    }
 
-    fn eat_number_octal(&mut self) -> DiagnosticResult<TokenKind> {
-        let mut octal_value = String::from("");
+        // skip the `0x`, `0o` or `0b` prefix
+        let digits = self.source.range(self.cursor)[2..].replace('_', "");
 
-        self.bump();
-
-        while !self.is_eof() {
-            let char = self.peek();
-
-            if ('0'..='7').contains(&char) {
-                octal_value.push(char);
-                self.bump();
-            } else {
-                break;
-            }
-        }
-
-        let mut base: u128 = 1;
-        let mut decimal_value: u128 = 0;
-
-        for char in octal_value.chars().rev() {
-            let digit = char.to_digit(10).unwrap();
-            decimal_value = match decimal_value.checked_add(digit as u128 * base) {
-                Some(v) => v,
-                None => return Err(LexerError::integer_too_large(self.cursor.span())),
-            };
-            base *= 8;
+        match u128::from_str_radix(&digits, radix) {
+            Ok(n) => Ok(Int(n)),
+            Err(_) => Err(LexerError::integer_too_large(self.cursor.span())),
         }
-
-        Ok(Int(decimal_value))
     }
 
-    fn eat_number_binary(&mut self) -> DiagnosticResult<TokenKind> {
-        let mut binary_value = String::from("");
-
-        self.bump();
+    // Eats a run of digits, which may be separated by single underscores, and returns the number of digits eaten.
+    // `digit_count` is the number of digits that were already eaten, since an underscore can't lead the run
+    fn eat_digits(&mut self, radix: u32, mut digit_count: usize) -> DiagnosticResult<usize> {
+        let mut last_underscore_span = None;
 
-        while !self.is_eof() {
+        loop {
             let char = self.peek();
 
-            if char == '0' || char == '1' {
-                binary_value.push(char);
-                self.bump();
+            if char == '_' {
+                if digit_count == 0 || last_underscore_span.is_some() {
+                    return Err(LexerError::misplaced_underscore(self.cursor.next_char_span()));
+                }
+
+                last_underscore_span = Some(self.cursor.next_char_span());
+            } else if char.is_digit(radix) {
+                digit_count += 1;
+                last_underscore_span = None;
             } else {
                 break;
             }
-        }
 
-        let mut base: u128 = 1;
-        let mut decimal_value: u128 = 0;
-
-        for char in binary_value.chars().rev() {
-            let digit = char.to_digit(10).unwrap();
-            decimal_value = match decimal_value.checked_add(digit as u128 * base) {
-                Some(v) => v,
-                None => return Err(LexerError::integer_too_large(self.cursor.span())),
-            };
-            base *= 2;
+            self.bump();
         }
 
-        Ok(Int(decimal_value))
+        match last_underscore_span {
+            Some(span) => Err(LexerError::misplaced_underscore(span)),
+            None => Ok(digit_count),
+        }
     }
 
     #[inline]
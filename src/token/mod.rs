@@ -106,6 +106,8 @@ pub enum TokenKind {
     Union,
     Match,
     Comptime,
+    Static,
+    Const,
 
     // Accessors
     Placeholder,
@@ -113,6 +115,7 @@ pub enum TokenKind {
 
     // Literals
     Nil,
+    Null,
     True,
     False,
     Int(u128),
@@ -127,6 +130,7 @@ impl From<&str> for TokenKind {
 
         match s {
             "nil" => Nil,
+            "null" => Null,
             "true" => True,
             "false" => False,
             "if" => If,
@@ -150,6 +154,8 @@ impl From<&str> for TokenKind {
             "union" => Union,
             "match" => Match,
             "comptime" => Comptime,
+            "static" => Static,
+            "const" => Const,
             "_" => Placeholder,
             s => Ident(ustr(s)),
         }
@@ -227,11 +233,14 @@ impl TokenKind {
             As => "as",
             Struct => "struct",
             Comptime => "comptime",
+            Static => "static",
+            Const => "const",
             Union => "union",
             Match => "match",
             Placeholder => "_",
             Ident(_) => "identifier",
             Nil => "nil",
+            Null => "null",
             True => "true",
             False => "false",
             Int(_) => "{integer}",
@@ -271,6 +280,7 @@ impl TokenKind {
                 | Placeholder
                 | Ident(_)
                 | Nil
+                | Null
                 | True
                 | False
                 | Int(_)
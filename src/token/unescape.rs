@@ -15,11 +15,15 @@ pub fn unescape(s: &str, start_span: Span) -> Result<String, UnescapeError> {
         if c != '\\' {
             s.push(c);
         } else {
+            let escape_start = processed;
+
             let c = match chars.next() {
                 Some(c) => c,
                 None => break,
             };
 
+            processed += 1;
+
             match c {
                 'b' => s.push('\u{0008}'),
                 'f' => s.push('\u{000C}'),
@@ -30,17 +34,29 @@ pub fn unescape(s: &str, start_span: Span) -> Result<String, UnescapeError> {
                 '\'' => s.push('\''),
                 '"' => s.push('"'),
                 '\\' => s.push('\\'),
+                // `\xNN` is an ascii character, given by exactly two hex digits
+                'x' => {
+                    let digits: String = chars.by_ref().take(2).collect();
+                    processed += digits.chars().count();
+
+                    let is_valid = digits.len() == 2 && digits.chars().all(|c| c.is_ascii_hexdigit());
+
+                    match u8::from_str_radix(&digits, 16) {
+                        Ok(n) if is_valid && n.is_ascii() => s.push(n as char),
+                        _ => {
+                            return Err(UnescapeError::InvalidEscapeSequence(escape_span(
+                                start_span,
+                                escape_start,
+                                processed,
+                            )))
+                        }
+                    }
+                }
                 _ => {
-                    return Err(UnescapeError::InvalidEscapeSequence(Span::new(
-                        start_span.file_id,
-                        Position {
-                            index: start_span.start.index + processed,
-                            line: start_span.start.line,
-                            column: start_span.start.column + processed as u32,
-                        },
-                        EndPosition {
-                            index: start_span.start.index + processed + 1,
-                        },
+                    return Err(UnescapeError::InvalidEscapeSequence(escape_span(
+                        start_span,
+                        escape_start,
+                        processed,
                     )))
                 }
             };
@@ -49,3 +65,18 @@ pub fn unescape(s: &str, start_span: Span) -> Result<String, UnescapeError> {
 
     Ok(s)
 }
+
+// `start` and `end` are offsets from the literal's opening quote
+fn escape_span(literal_span: Span, start: usize, end: usize) -> Span {
+    Span::new(
+        literal_span.file_id,
+        Position {
+            index: literal_span.start.index + start,
+            line: literal_span.start.line,
+            column: literal_span.start.column + start as u32,
+        },
+        EndPosition {
+            index: literal_span.start.index + end + 1,
+        },
+    )
+}
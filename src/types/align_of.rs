@@ -64,7 +64,7 @@ impl AlignOf for FloatType {
 
 impl AlignOf for StructType {
     fn align_of(&self, word_size: usize) -> usize {
-        match self.kind {
+        let natural_align = match self.kind {
             StructTypeKind::Struct | StructTypeKind::Union => {
                 let mut max_align: usize = 1;
                 for field in self.fields.iter() {
@@ -74,6 +74,8 @@ impl AlignOf for StructType {
                 max_align
             }
             StructTypeKind::PackedStruct => 1,
-        }
+        };
+
+        natural_align.max(self.align.unwrap_or(1))
     }
 }
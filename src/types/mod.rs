@@ -4,6 +4,7 @@ pub mod offset_of;
 pub mod size_of;
 
 use crate::{
+    ast::Vis,
     define_id_type,
     hir::const_value::ConstValue,
     span::Span,
@@ -147,6 +148,8 @@ pub struct StructType {
     pub id: Option<BindingId>,
     pub fields: Vec<StructTypeField>,
     pub kind: StructTypeKind,
+    // a minimum alignment, set with the `@align` attribute
+    pub align: Option<usize>,
 }
 
 impl StructType {
@@ -179,6 +182,7 @@ impl StructType {
             id,
             fields: vec![],
             kind,
+            align: None,
         }
     }
 
@@ -188,10 +192,10 @@ impl StructType {
             id: None,
             fields,
             kind,
+            align: None,
         }
     }
 
-    #[allow(unused)]
     pub fn is_struct(&self) -> bool {
         matches!(self.kind, StructTypeKind::Struct)
     }
@@ -221,6 +225,7 @@ pub enum StructTypeKind {
 pub struct StructTypeField {
     pub name: Ustr,
     pub ty: Type,
+    pub vis: Vis,
     pub span: Span,
 }
 
@@ -229,6 +234,7 @@ impl StructTypeField {
         Self {
             name: ustr(""),
             ty,
+            vis: Vis::Public,
             span: Span::unknown(),
         }
     }
@@ -283,7 +289,6 @@ impl Type {
         matches!(self, Type::Module(_))
     }
 
-    #[allow(unused)]
     pub fn is_number(&self) -> bool {
         self.is_any_integer() || self.is_float()
     }
@@ -300,7 +305,6 @@ impl Type {
         matches!(self, Type::Infer(_, InferType::AnyInt))
     }
 
-    #[allow(unused)]
     pub fn is_anyfloat(&self) -> bool {
         matches!(self, Type::Infer(_, InferType::AnyFloat))
     }
@@ -313,7 +317,6 @@ impl Type {
         matches!(self, Type::Uint(_))
     }
 
-    #[allow(unused)]
     pub fn is_float(&self) -> bool {
         matches!(self, Type::Float(_))
     }
@@ -545,4 +548,19 @@ impl Type {
             _ => false,
         }
     }
+
+    // Whether two values of this type are equal if and only if their bytes are equal.
+    // Floats are excluded, since `0.0 == -0.0` and `nan != nan`, and so are types which may contain padding
+    pub fn is_bytewise_comparable(&self) -> bool {
+        match self {
+            Type::Int(_) | Type::Uint(_) | Type::Bool | Type::Pointer(..) => !self.is_fat_pointer(),
+            Type::Array(inner, _) => inner.is_bytewise_comparable(),
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_aggregate(&self) -> bool {
+        matches!(self, Type::Array(..) | Type::Tuple(_) | Type::Struct(_))
+    }
 }
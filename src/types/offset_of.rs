@@ -46,7 +46,11 @@ impl OffsetOf for StructType {
                     offset += field.ty.size_of(word_size);
                 });
 
-                offset
+                // the field itself starts at the next offset that satisfies its alignment
+                match self.fields.get(index) {
+                    Some(field) => calculate_align_from_offset(offset, field.ty.align_of(word_size)),
+                    None => offset,
+                }
             }
             StructTypeKind::PackedStruct => self.fields.iter().take(index).map(|f| f.ty.size_of(word_size)).sum(),
             StructTypeKind::Union => 0,
@@ -15,6 +15,7 @@ impl SizeOf for Type {
             Type::Int(ty) => ty.size_of(word_size),
             Type::Uint(ty) => ty.size_of(word_size),
             Type::Float(ty) => ty.size_of(word_size),
+            // the pointee is never descended into, which is what gives self-referential structs a size
             Type::Pointer(ty, _) => match ty.as_ref() {
                 Type::Slice(_) | Type::Str(_) => word_size * 2,
                 _ => word_size,
@@ -85,13 +86,13 @@ impl SizeOf for StructType {
 
                 offset
             }
-            StructTypeKind::PackedStruct => self.fields.iter().map(|f| f.ty.size_of(word_size)).sum(),
+            StructTypeKind::PackedStruct => {
+                let size = self.fields.iter().map(|f| f.ty.size_of(word_size)).sum();
+                calculate_align_from_offset(size, self.align_of(word_size))
+            }
             StructTypeKind::Union => {
                 let max_size = self.fields.iter().map(|f| f.ty.size_of(word_size)).max().unwrap_or(0);
-
-                let align = self.align_of(word_size);
-
-                (max_size / align) * align
+                calculate_align_from_offset(max_size, self.align_of(word_size))
             }
         }
     }
@@ -0,0 +1,35 @@
+// Exits the process with a given code using `abort!`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+#[test]
+fn exits_with_code() {
+    let sandbox = Sandbox::new("abort_exit");
+    let path = sandbox.file("program.chl", "fn main() = {\n    abort!(3)\n}\n");
+    let exe = sandbox.path("program");
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg(&exe));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let output = run(&mut Command::new(&exe));
+
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn rejects_non_integer_code() {
+    let sandbox = Sandbox::new("abort_reject");
+    let path = sandbox.file("program.chl", "fn main() = {\n    abort!(true)\n}\n");
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.stderr.contains("mismatched types"),
+        "expected the code to be rejected, got: {}",
+        output.stderr
+    );
+}
@@ -0,0 +1,49 @@
+// Rejects array types whose size doesn't fit in the target's address space
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, array_type: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file(
+        "arrays.chl",
+        &format!("fn main() = {{\n    let array: *{} = null\n}}\n", array_type),
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    output.stderr
+}
+
+#[test]
+fn accepts_array_within_limit() {
+    let stderr = check("array_size_accept", "[1024]u64");
+
+    assert!(
+        !stderr.contains("error"),
+        "expected the array type to check, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn rejects_array_too_large() {
+    // 2^60 elements of 8 bytes are one byte more than a 64-bit target's maximum object size
+    let stderr = check("array_size_reject", "[1152921504606846976]u64");
+
+    assert!(
+        stderr.contains(
+            "array too large: `[1152921504606846976]u64` has a size of 9223372036854775808 bytes, \
+             exceeding the target's maximum of 9223372036854775807 bytes"
+        ),
+        "expected an array size diagnostic, got: {}",
+        stderr
+    );
+}
@@ -0,0 +1,126 @@
+// Prints the typed tree of a program with `chili ast`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+const SOURCE: &str = "fn main() = {
+    let answer = 42
+}
+";
+
+#[test]
+fn prints_module_and_binding() {
+    let sandbox = Sandbox::new("ast_typed");
+    let path = sandbox.file("tree.chl", SOURCE);
+    let module = sandbox.module_name("tree");
+
+    let output = run(sandbox.chili().arg("ast").arg(&path).arg("--module").arg(&module));
+
+    assert!(output.status.success(), "failed to print the tree: {}", output.stderr);
+    assert!(
+        output.stdout.starts_with(&format!("# {} (", module)),
+        "expected the tree to start with its module, got: {}",
+        output.stdout
+    );
+    assert!(
+        output.stdout.contains(".main("),
+        "expected `main`, got: {}",
+        output.stdout
+    );
+    assert!(
+        output.stdout.contains("let answer = 42"),
+        "expected `answer`, got: {}",
+        output.stdout
+    );
+    assert!(
+        !output.stdout.contains("# std"),
+        "expected only the root module, got: {}",
+        output.stdout
+    );
+}
+
+#[test]
+fn prints_spans() {
+    let sandbox = Sandbox::new("ast_spans");
+    let path = sandbox.file("tree.chl", SOURCE);
+    let module = sandbox.module_name("tree");
+
+    let start = SOURCE.find("42").unwrap();
+    let literal_span = format!("tree.chl:{}..{}", start, start + 2);
+
+    let output = run(sandbox
+        .chili()
+        .arg("ast")
+        .arg(&path)
+        .arg("--module")
+        .arg(&module)
+        .arg("--spans"));
+
+    assert!(output.status.success(), "failed to print the tree: {}", output.stderr);
+    assert!(
+        output.stdout.contains(&literal_span),
+        "expected the literal's span `{}`, got: {}",
+        literal_span,
+        output.stdout
+    );
+
+    let output = run(sandbox.chili().arg("ast").arg(&path).arg("--module").arg(&module));
+
+    assert!(output.status.success(), "failed to print the tree: {}", output.stderr);
+    assert!(
+        !output.stdout.contains(" @"),
+        "expected no spans without `--spans`, got: {}",
+        output.stdout
+    );
+}
+
+#[test]
+fn prints_loop_control_depth() {
+    let sandbox = Sandbox::new("ast_loop_depth");
+    let path = sandbox.file(
+        "tree.chl",
+        "fn main() = {
+    for x in 0..2 {
+        for y in 0..3 {
+            break 2
+        }
+    }
+}
+",
+    );
+    let module = sandbox.module_name("tree");
+
+    let output = run(sandbox.chili().arg("ast").arg(&path).arg("--module").arg(&module));
+
+    assert!(output.status.success(), "failed to print the tree: {}", output.stderr);
+    assert!(
+        output.stdout.contains("break 2"),
+        "expected the break's depth, got: {}",
+        output.stdout
+    );
+}
+
+#[test]
+fn rejects_loop_control_depth_out_of_range() {
+    let sandbox = Sandbox::new("ast_loop_depth_reject");
+    let path = sandbox.file(
+        "tree.chl",
+        "fn main() = {
+    for x in 0..2 {
+        break 2
+    }
+}
+",
+    );
+    let module = sandbox.module_name("tree");
+
+    let output = run(sandbox.chili().arg("ast").arg(&path).arg("--module").arg(&module));
+
+    assert!(
+        output.stderr.contains("`break 2` targets a loop that doesn't exist"),
+        "expected the depth to be rejected, got: {}",
+        output.stderr
+    );
+}
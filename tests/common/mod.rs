@@ -0,0 +1,79 @@
+// Helpers shared by the integration tests, which drive the compiled `chili` executable
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+pub fn root_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+// A temporary directory holding a copy of the compiler, next to a link to the standard library.
+// It is removed when dropped
+pub struct Sandbox {
+    pub dir: PathBuf,
+}
+
+impl Sandbox {
+    // `name` must be unique across the tests of a test binary, since they run in parallel
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("chili_{}_{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        // the standard library is looked up next to the compiler's executable
+        fs::copy(env!("CARGO_BIN_EXE_chili"), dir.join("chili")).unwrap();
+        std::os::unix::fs::symlink(root_dir().join("stdlib"), dir.join("stdlib")).unwrap();
+
+        Self { dir }
+    }
+
+    // Writes a source file into the sandbox, and returns its path
+    pub fn file(&self, name: &str, source: &str) -> PathBuf {
+        let path = self.dir.join(name);
+        fs::write(&path, source).unwrap();
+        path
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    // The qualified name of a root module in the sandbox, which is prefixed by the sandbox's name
+    pub fn module_name(&self, file_stem: &str) -> String {
+        format!("{}.{}", self.dir.file_name().unwrap().to_str().unwrap(), file_stem)
+    }
+
+    // A command running the sandboxed compiler from the sandbox, with colors disabled
+    pub fn chili(&self) -> Command {
+        let mut command = Command::new(self.dir.join("chili"));
+        command.current_dir(&self.dir).arg("--no-color");
+        command
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub struct Run {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub fn run(command: &mut Command) -> Run {
+    let output = command.output().unwrap();
+
+    Run {
+        status: output.status,
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    }
+}
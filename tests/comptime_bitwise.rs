@@ -0,0 +1,57 @@
+// Evaluates bitwise operators on integers at compile-time
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+#[test]
+fn evaluates_integer_masks() {
+    let sandbox = Sandbox::new("comptime_bitwise");
+    // the operands are mutable, so the operators are evaluated by the interpreter instead of being folded
+    let path = sandbox.file(
+        "bitwise.chl",
+        "fn main() = {
+    let and = comptime {
+        let mut a: i32 = 12
+        a & 10
+    }
+    let or = comptime {
+        let mut a: i32 = 12
+        a | 10
+    }
+    let xor = comptime {
+        let mut a: i32 = 12
+        a ^ 10
+    }
+    std.c.printf(\"%d %d %d\\n\".ptr, and, or, xor)
+}
+",
+    );
+    let exe = sandbox.path("bitwise");
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg(&exe));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let output = run(&mut Command::new(&exe));
+
+    assert_eq!(output.stdout, "8 14 6\n");
+}
+
+#[test]
+fn rejects_bool_operands() {
+    let sandbox = Sandbox::new("comptime_bitwise_bool");
+    let path = sandbox.file(
+        "bitwise.chl",
+        "fn main() = {\n    let both = comptime { true & false }\n}\n",
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.stderr.contains("mismatched types"),
+        "expected bool operands to be rejected, got: {}",
+        output.stderr
+    );
+}
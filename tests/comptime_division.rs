@@ -0,0 +1,77 @@
+// Evaluates integer division at compile-time, and reports division by zero and signed overflow
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+// the divisor is mutable, so the division is evaluated by the interpreter instead of being folded
+fn division(dividend: &str, op: &str, divisor: &str) -> String {
+    format!(
+        "fn main() = {{
+    let result = comptime {{
+        let mut divisor: i8 = {}
+        ({} as i8) {} divisor
+    }}
+    std.c.printf(\"%d\\n\".ptr, result as i32)
+}}
+",
+        divisor, dividend, op
+    )
+}
+
+fn check(name: &str, source: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("division.chl", source);
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn evaluates_division() {
+    let sandbox = Sandbox::new("comptime_division");
+    let path = sandbox.file("division.chl", &division("-128", "/", "2"));
+    let exe = sandbox.path("division");
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg(&exe));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let output = run(&mut Command::new(&exe));
+
+    assert_eq!(output.stdout, "-64\n");
+}
+
+#[test]
+fn reports_division_by_zero() {
+    let stderr = check("comptime_division_zero", &division("7", "/", "0"));
+    assert!(
+        stderr.contains("compile-time evaluation failed: attempted to divide by zero"),
+        "expected the division by zero to be reported, got: {}",
+        stderr
+    );
+
+    let stderr = check("comptime_remainder_zero", &division("7", "%", "0"));
+    assert!(
+        stderr.contains("attempted to calculate the remainder with a divisor of zero"),
+        "expected the remainder by zero to be reported, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn reports_signed_division_overflow() {
+    let stderr = check("comptime_division_overflow", &division("-128", "/", "-1"));
+    assert!(
+        stderr.contains("compile-time evaluation failed: attempted to divide with overflow"),
+        "expected the overflow to be reported, got: {}",
+        stderr
+    );
+
+    let stderr = check("comptime_remainder_overflow", &division("-128", "%", "-1"));
+    assert!(
+        stderr.contains("attempted to calculate the remainder with overflow"),
+        "expected the overflow to be reported, got: {}",
+        stderr
+    );
+}
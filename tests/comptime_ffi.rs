@@ -0,0 +1,71 @@
+// Calls foreign functions at compile-time, and reports libraries and symbols that can't be loaded
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+fn foreign_call(lib: &str, name: &str) -> String {
+    format!(
+        "@lib(\"{}\")
+extern fn {}(x: i32) -> i32
+
+fn main() = {{
+    let result = comptime {{ {}(-5) }}
+    std.c.printf(\"%d\\n\".ptr, result)
+}}
+",
+        lib, name, name
+    )
+}
+
+fn check(name: &str, source: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("ffi.chl", source);
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    output.stderr
+}
+
+#[test]
+fn calls_libc_function() {
+    let sandbox = Sandbox::new("comptime_ffi");
+    let path = sandbox.file("ffi.chl", &foreign_call("c", "abs"));
+    let exe = sandbox.path("ffi");
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg(&exe));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let output = run(&mut Command::new(&exe));
+
+    assert_eq!(output.stdout, "5\n");
+}
+
+#[test]
+fn reports_missing_library() {
+    let stderr = check("comptime_ffi_library", &foreign_call("chili_missing_library", "abs"));
+
+    assert!(
+        stderr.contains("failed to load library `chili_missing_library`"),
+        "expected the library to be reported, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn reports_missing_symbol() {
+    let stderr = check("comptime_ffi_symbol", &foreign_call("c", "chili_missing_symbol"));
+
+    assert!(
+        stderr.contains("couldn't find symbol `chili_missing_symbol`"),
+        "expected the symbol to be reported, got: {}",
+        stderr
+    );
+}
@@ -0,0 +1,49 @@
+// Reports compile-time evaluation that recurses too deeply, instead of overflowing the interpreter's stack
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn recursion(depth: usize) -> String {
+    format!(
+        "fn depth(n: int) -> int = if n == 0 {{ 0 }} else {{ depth(n - 1) + 1 }}\n\n\
+         fn main() = {{\n    let d = comptime {{ depth({}) }}\n}}\n",
+        depth
+    )
+}
+
+#[test]
+fn reports_deep_recursion() {
+    let sandbox = Sandbox::new("comptime_recursion_deep");
+    let path = sandbox.file("deep.chl", &recursion(100_000));
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    assert!(
+        output
+            .stderr
+            .contains("compile-time evaluation exceeded maximum recursion depth while calling `"),
+        "expected a recursion depth diagnostic, got: {}",
+        output.stderr
+    );
+}
+
+#[test]
+fn evaluates_shallow_recursion() {
+    let sandbox = Sandbox::new("comptime_recursion_shallow");
+    let path = sandbox.file("shallow.chl", &recursion(10));
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        !output.stderr.contains("error"),
+        "expected the recursion to be evaluated, got: {}",
+        output.stderr
+    );
+}
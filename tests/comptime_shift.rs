@@ -0,0 +1,59 @@
+// Evaluates shifts at compile-time, and reports shifting by a negative amount
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+#[test]
+fn evaluates_shifts() {
+    let sandbox = Sandbox::new("comptime_shift");
+    // the amounts are mutable, so the shifts are evaluated by the interpreter instead of being folded
+    let path = sandbox.file(
+        "shift.chl",
+        "fn main() = {
+    let left = comptime {
+        let mut amount: i32 = 3
+        1 << amount
+    }
+    let right = comptime {
+        let mut amount: i32 = 2
+        -16 >> amount
+    }
+    std.c.printf(\"%d %d\\n\".ptr, left, right)
+}
+",
+    );
+    let exe = sandbox.path("shift");
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg(&exe));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let output = run(&mut Command::new(&exe));
+
+    assert_eq!(output.stdout, "8 -4\n");
+}
+
+#[test]
+fn reports_negative_shift_amount() {
+    let sandbox = Sandbox::new("comptime_shift_negative");
+    let path = sandbox.file(
+        "shift.chl",
+        "fn main() = {
+    let shifted = comptime {
+        let mut amount = -1
+        1 << amount
+    }
+}
+",
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.stderr.contains("attempted to shift by a negative amount `-1`"),
+        "expected the negative amount to be reported, got: {}",
+        output.stderr
+    );
+}
@@ -0,0 +1,61 @@
+// Chooses the type of uninferred integer literals with `--default-int`, and warns about them with `--warn-default-int`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn diagnostics(name: &str, source: &str, default_int: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("ints.chl", source);
+
+    let output = run(sandbox
+        .chili()
+        .arg(&path)
+        .arg("--check")
+        .arg("--diagnostics")
+        .arg("--default-int")
+        .arg(default_int)
+        .arg("--warn-default-int"));
+
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+    output.stdout
+}
+
+#[test]
+fn warns_about_defaulted_literal() {
+    let diagnostics = diagnostics("default_int_warn", "fn main() = {\n    let x = 42\n}\n", "i64");
+
+    assert!(
+        diagnostics.contains("integer type defaults to `i64`"),
+        "expected a default int warning, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn annotated_literal_is_quiet() {
+    // literals in the standard library aren't reported either
+    let diagnostics = diagnostics(
+        "default_int_annotated",
+        "fn main() = {\n    let x: i32 = 42\n}\n",
+        "i64",
+    );
+
+    assert!(
+        !diagnostics.contains("integer type defaults to"),
+        "expected no default int warnings, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn checks_defaulted_literal_limits() {
+    let diagnostics = diagnostics("default_int_limits", "fn main() = {\n    let x = 300\n}\n", "i8");
+
+    assert!(
+        diagnostics.contains("must be between -128 and 127, found 300"),
+        "expected the literal to overflow `i8`, got: {}",
+        diagnostics
+    );
+}
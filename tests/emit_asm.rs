@@ -0,0 +1,64 @@
+// Writes a target assembly file of a library with `--emit-asm`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+const SOURCE: &str = "pub fn add(a: i32, b: i32) -> i32 = a + b\n";
+
+#[test]
+fn writes_assembly_file() {
+    let sandbox = Sandbox::new("emit_asm");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--lib").arg("--emit-asm"));
+
+    assert!(
+        output.status.success(),
+        "failed to build the library: {}",
+        output.stderr
+    );
+
+    let asm = fs::read_to_string(sandbox.path("add.s")).expect("the assembly file wasn't written");
+
+    // the label is prefixed with an underscore on macOS
+    assert!(
+        asm.lines().any(|line| line == "add:" || line == "_add:"),
+        "expected a label for `add`, got: {}",
+        asm
+    );
+    assert!(
+        sandbox.path("add.o").exists(),
+        "compilation should continue after the assembly file"
+    );
+}
+
+#[test]
+fn reports_unwritable_assembly_file() {
+    let sandbox = Sandbox::new("emit_asm_unwritable");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    // a directory in place of the assembly file can't be written to
+    fs::create_dir(sandbox.path("add.s")).unwrap();
+
+    let output = run(sandbox.chili().arg(&path).arg("--lib").arg("--emit-asm"));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    assert!(
+        output
+            .stderr
+            .contains(&format!("couldn't write `{}`", sandbox.path("add.s").display())),
+        "expected a diagnostic naming the assembly file, got: {}",
+        output.stderr
+    );
+    assert!(
+        !sandbox.path("add.o").exists(),
+        "the build should stop at the assembly file"
+    );
+}
@@ -0,0 +1,49 @@
+// Writes the typed tree as a Graphviz graph next to the output with `--emit-hir-dot`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+const SOURCE: &str = "fn main() = {
+    let answer = 42
+}
+";
+
+#[test]
+fn writes_typed_tree_graph() {
+    let sandbox = Sandbox::new("emit_hir_dot");
+    let path = sandbox.file("program.chl", SOURCE);
+    let main = format!("Function {}.main\\n", sandbox.module_name("program"));
+
+    let output = run(sandbox.chili().arg(&path).arg("--emit-hir-dot"));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let dot = fs::read_to_string(sandbox.path("program.hir.dot")).expect("the dot file wasn't written");
+
+    assert!(dot.starts_with("digraph hir {\n"), "expected a digraph, got: {}", dot);
+    assert!(dot.ends_with("}\n"), "expected the graph to be closed, got: {}", dot);
+    assert!(dot.contains(&main), "expected a node for `main`, got: {}", dot);
+    assert!(dot.contains(" -> "), "expected edges to children, got: {}", dot);
+}
+
+#[test]
+fn reports_unwritable_dot_file() {
+    let sandbox = Sandbox::new("emit_hir_dot_unwritable");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    // a directory in place of the dot file can't be written to
+    fs::create_dir(sandbox.path("program.hir.dot")).unwrap();
+
+    let output = run(sandbox.chili().arg(&path).arg("--emit-hir-dot"));
+
+    assert!(
+        output.stderr.contains(&format!(
+            "couldn't write `{}`",
+            sandbox.path("program.hir.dot").display()
+        )),
+        "expected a diagnostic naming the dot file, got: {}",
+        output.stderr
+    );
+}
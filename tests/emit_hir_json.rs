@@ -0,0 +1,60 @@
+// Writes the typed tree as JSON next to the output with `--emit-hir-json`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+const SOURCE: &str = "fn main() = {
+    let answer = 42
+}
+";
+
+#[test]
+fn writes_typed_tree() {
+    let sandbox = Sandbox::new("emit_hir_json");
+    let path = sandbox.file("program.chl", SOURCE);
+    let main = format!("{}.main", sandbox.module_name("program"));
+
+    let output = run(sandbox.chili().arg(&path).arg("--emit-hir-json"));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let json = fs::read_to_string(sandbox.path("program.hir.json")).expect("the json file wasn't written");
+    let document: serde_json::Value = serde_json::from_str(&json).expect("the json file isn't valid json");
+
+    let functions = document["functions"].as_array().expect("expected a `functions` array");
+    let main = functions
+        .iter()
+        .find(|function| function["name"] == main.as_str())
+        .unwrap_or_else(|| panic!("expected `{}`, got: {}", main, json));
+
+    assert_eq!(main["kind"], "Function");
+    assert!(
+        main["span"]["file"]
+            .as_str()
+            .map_or(false, |file| file.ends_with("program.chl")),
+        "expected the span to point at the source file, got: {}",
+        main["span"]
+    );
+}
+
+#[test]
+fn reports_unwritable_json_file() {
+    let sandbox = Sandbox::new("emit_hir_json_unwritable");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    // a directory in place of the json file can't be written to
+    fs::create_dir(sandbox.path("program.hir.json")).unwrap();
+
+    let output = run(sandbox.chili().arg(&path).arg("--emit-hir-json"));
+
+    assert!(
+        output.stderr.contains(&format!(
+            "couldn't write `{}`",
+            sandbox.path("program.hir.json").display()
+        )),
+        "expected a diagnostic naming the json file, got: {}",
+        output.stderr
+    );
+}
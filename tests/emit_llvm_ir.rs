@@ -0,0 +1,70 @@
+// Checks the llvm ir that builtins lower to, by writing it next to the output with `--emit-llvm-ir`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+fn emit_llvm_ir(name: &str, source: &str, opt_level: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("program.chl", source);
+
+    let output = run(sandbox
+        .chili()
+        .arg(&path)
+        .arg("--emit-llvm-ir")
+        .arg("--opt-level")
+        .arg(opt_level));
+
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+    assert!(!output.stderr.contains("error"), "failed to build: {}", output.stderr);
+
+    fs::read_to_string(sandbox.path("program.ll")).expect("the llvm ir wasn't written")
+}
+
+const BREAKPOINT: &str = "fn main() = {\n    breakpoint!()\n}\n";
+
+#[test]
+fn breakpoint_traps_in_debug() {
+    let ir = emit_llvm_ir("emit_llvm_ir_breakpoint_debug", BREAKPOINT, "debug");
+    assert!(
+        ir.contains("call void @llvm.debugtrap()"),
+        "expected a debug trap, got:\n{}",
+        ir
+    );
+}
+
+#[test]
+fn breakpoint_is_removed_in_release() {
+    let ir = emit_llvm_ir("emit_llvm_ir_breakpoint_release", BREAKPOINT, "release");
+    assert!(!ir.contains("llvm.debugtrap"), "expected no debug trap, got:\n{}", ir);
+}
+
+#[test]
+fn assume_lowers_to_llvm_assume() {
+    let ir = emit_llvm_ir(
+        "emit_llvm_ir_assume",
+        "fn main() = {\n    let x = 5\n    assume!(x > 0)\n}\n",
+        "debug",
+    );
+    assert!(
+        ir.contains("call void @llvm.assume(i1"),
+        "expected an assumption, got:\n{}",
+        ir
+    );
+}
+
+#[test]
+fn assume_rejects_non_bool_condition() {
+    let sandbox = Sandbox::new("emit_llvm_ir_assume_reject");
+    let path = sandbox.file("program.chl", "fn main() = {\n    assume!(1)\n}\n");
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.stderr.contains("mismatched types"),
+        "expected the condition to be rejected, got: {}",
+        output.stderr
+    );
+}
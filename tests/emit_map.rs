@@ -0,0 +1,59 @@
+// Writes a map file of a library's symbols with `--emit-map`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+const SOURCE: &str = "pub let mut calls: i32 = 0
+
+pub fn add(a: i32, b: i32) -> i32 = {
+    calls += 1
+    a + b
+}
+";
+
+#[test]
+fn lists_defined_symbols() {
+    let sandbox = Sandbox::new("emit_map");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--lib").arg("--emit-map"));
+
+    assert!(
+        output.status.success(),
+        "failed to build the library: {}",
+        output.stderr
+    );
+
+    let map = fs::read_to_string(sandbox.path("add.map")).expect("the map file wasn't written");
+    let has_symbol = |section: &str, name: &str| {
+        map.lines()
+            .any(|line| line.starts_with(section) && line.split_whitespace().last() == Some(name))
+    };
+
+    assert!(has_symbol(".text", "add"), "expected `add` in .text, got: {}", map);
+    assert!(has_symbol(".data", "calls"), "expected `calls` in .data, got: {}", map);
+    assert!(map.contains("total code: "), "expected the totals, got: {}", map);
+}
+
+#[test]
+fn reports_unwritable_map_file() {
+    let sandbox = Sandbox::new("emit_map_unwritable");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    // a directory in place of the map file can't be written to
+    fs::create_dir(sandbox.path("add.map")).unwrap();
+
+    let output = run(sandbox.chili().arg(&path).arg("--lib").arg("--emit-map"));
+
+    assert!(
+        output
+            .stderr
+            .contains(&format!("couldn't write `{}`", sandbox.path("add.map").display())),
+        "expected a diagnostic naming the map file, got: {}",
+        output.stderr
+    );
+    assert!(!sandbox.path("add.o").exists(), "the build should stop at the map file");
+}
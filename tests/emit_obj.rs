@@ -0,0 +1,59 @@
+// Writes an object file without linking it with `--emit-obj`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+const SOURCE: &str = "fn main() = {
+    let answer = 42
+}
+";
+
+#[test]
+fn writes_object_file_without_linking() {
+    let sandbox = Sandbox::new("emit_obj");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--emit-obj"));
+
+    assert!(
+        output.status.success(),
+        "failed to build the object file: {}",
+        output.stderr
+    );
+    assert!(!sandbox.path("program").exists(), "the object file shouldn't be linked");
+
+    let object = fs::read(sandbox.path("program.o")).expect("the object file wasn't written");
+
+    let is_elf = object.starts_with(b"\x7fELF");
+    let is_mach_o = object.starts_with(&[0xcf, 0xfa, 0xed, 0xfe]);
+    assert!(is_elf || is_mach_o, "`program.o` isn't an object file");
+
+    // the entry point is exported under its platform's symbol name
+    let symbol: &[u8] = if is_mach_o { b"_main\0" } else { b"\0main\0" };
+    assert!(
+        object.windows(symbol.len()).any(|window| window == symbol),
+        "expected `program.o` to define `main`"
+    );
+}
+
+#[test]
+fn rejects_emit_obj_when_running() {
+    let sandbox = Sandbox::new("emit_obj_run");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--emit-obj").arg("--run"));
+
+    assert!(
+        !output.status.success(),
+        "expected `--emit-obj` to conflict with `--run`"
+    );
+    assert!(
+        output.stderr.contains("--emit-obj"),
+        "expected the conflict to be reported, got: {}",
+        output.stderr
+    );
+    assert!(!sandbox.path("program.o").exists(), "no object file should be written");
+}
@@ -0,0 +1,38 @@
+// Builds a library with `--lib`, and links its object file into a C program
+#![cfg(unix)]
+
+mod common;
+
+use common::{root_dir, run, Sandbox};
+use std::process::Command;
+
+#[test]
+fn exported_bindings_link_from_c() {
+    let fixtures_dir = root_dir().join("tests").join("export_to_c");
+    let sandbox = Sandbox::new("export_to_c");
+
+    let status = sandbox
+        .chili()
+        .arg(fixtures_dir.join("add.chl"))
+        .arg("--lib")
+        .arg("--output")
+        .arg(sandbox.path("add"))
+        .status()
+        .unwrap();
+
+    assert!(status.success(), "failed to build the library");
+
+    let status = Command::new("cc")
+        .arg(fixtures_dir.join("main.c"))
+        .arg(sandbox.path("add.o"))
+        .arg("-o")
+        .arg(sandbox.path("main"))
+        .status()
+        .unwrap();
+
+    assert!(status.success(), "failed to link the library's object file from C");
+
+    let output = run(&mut Command::new(sandbox.path("main")));
+
+    assert_eq!(output.stdout, "add(3, 4) = 7\ncalls = 1\n");
+}
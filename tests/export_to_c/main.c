@@ -0,0 +1,11 @@
+#include <stdint.h>
+#include <stdio.h>
+
+extern int32_t add(int32_t a, int32_t b);
+extern int32_t calls;
+
+int main(void) {
+    printf("add(3, 4) = %d\n", add(3, 4));
+    printf("calls = %d\n", calls);
+    return 0;
+}
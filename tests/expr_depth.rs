@@ -0,0 +1,71 @@
+// Rejects pathologically nested expressions with a diagnostic, instead of overflowing the stack
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+// `levels` blocks, each nested in a `let` binding of the enclosing block.
+// The checker descends through both the binding and its block, so it nests twice as deep as the parser
+fn nested_bindings(levels: usize) -> String {
+    format!(
+        "fn main() = {{\n{}1\n{}}}\n",
+        "let a = {\n".repeat(levels),
+        "}\n".repeat(levels)
+    )
+}
+
+#[test]
+fn parser_rejects_long_binary_chain() {
+    let sandbox = Sandbox::new("expr_depth_parse");
+    let path = sandbox.file(
+        "chain.chl",
+        &format!("fn main() = {{\n    let x = 1{}\n}}\n", " + 1".repeat(20_000)),
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    assert!(
+        output.stderr.contains("expression nesting too deep"),
+        "expected a nesting diagnostic, got: {}",
+        output.stderr
+    );
+}
+
+#[test]
+fn checker_rejects_deeply_nested_bindings() {
+    let sandbox = Sandbox::new("expr_depth_check");
+    let path = sandbox.file("nested.chl", &nested_bindings(40));
+
+    let output = run(sandbox.chili().arg(&path).arg("--max-expr-depth").arg("64"));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    assert!(
+        output.stderr.contains("expression nesting too deep"),
+        "expected the checker to report the nesting, got: {}",
+        output.stderr
+    );
+}
+
+#[test]
+fn accepts_nesting_within_the_limit() {
+    let sandbox = Sandbox::new("expr_depth_accept");
+    let path = sandbox.file("nested.chl", &nested_bindings(40));
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        !output.stderr.contains("error"),
+        "expected the default depth to allow the nesting, got: {}",
+        output.stderr
+    );
+}
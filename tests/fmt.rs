@@ -0,0 +1,97 @@
+// Formats source files with `chili fmt`, and checks them with `chili fmt --check`
+#![cfg(unix)]
+
+mod common;
+
+use common::{root_dir, run, Sandbox};
+use std::fs;
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(root_dir().join("tests").join("fmt").join(name)).unwrap()
+}
+
+#[test]
+fn formats_to_golden_output() {
+    let sandbox = Sandbox::new("fmt_golden");
+    let path = sandbox.file("module.chl", &fixture("unformatted.chl"));
+
+    let output = run(sandbox.chili().arg("fmt").arg(&path));
+
+    assert!(output.status.success(), "failed to format: {}", output.stdout);
+    assert_eq!(fs::read_to_string(&path).unwrap(), fixture("formatted.chl"));
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    let sandbox = Sandbox::new("fmt_idempotent");
+
+    for (name, source) in [
+        ("golden.chl", fixture("formatted.chl")),
+        (
+            "demo.chl",
+            fs::read_to_string(root_dir().join("examples").join("demo").join("demo.chl")).unwrap(),
+        ),
+    ] {
+        let path = sandbox.file(name, &source);
+
+        let output = run(sandbox.chili().arg("fmt").arg(&path));
+        assert!(
+            output.status.success(),
+            "failed to format `{}`: {}",
+            name,
+            output.stdout
+        );
+
+        let formatted = fs::read_to_string(&path).unwrap();
+
+        let output = run(sandbox.chili().arg("fmt").arg(&path));
+        assert!(
+            output.status.success(),
+            "failed to format `{}` again: {}",
+            name,
+            output.stdout
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            formatted,
+            "formatting `{}` twice changed it",
+            name
+        );
+
+        let output = run(sandbox.chili().arg("fmt").arg("--check").arg(&path));
+        assert!(
+            output.status.success(),
+            "`{}` isn't formatted after formatting it",
+            name
+        );
+    }
+}
+
+#[test]
+fn check_rejects_unformatted_file() {
+    let sandbox = Sandbox::new("fmt_check");
+    let source = fixture("unformatted.chl");
+    let path = sandbox.file("module.chl", &source);
+
+    let output = run(sandbox.chili().arg("fmt").arg("--check").arg(&path));
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.contains("is not formatted"), "got: {}", output.stdout);
+    assert_eq!(fs::read_to_string(&path).unwrap(), source, "checking rewrote the file");
+}
+
+#[test]
+fn reports_unreadable_file() {
+    let sandbox = Sandbox::new("fmt_unreadable");
+    let path = sandbox.path("invalid.chl");
+    fs::write(&path, [0xff, 0xfe, 0xfd]).unwrap();
+
+    let output = run(sandbox.chili().arg("fmt").arg(&path));
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        output.stdout.contains(&path.display().to_string()) && output.stdout.contains("valid UTF-8"),
+        "expected the path and the io error, got: {}",
+        output.stdout
+    );
+}
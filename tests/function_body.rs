@@ -0,0 +1,43 @@
+// Reports functions that are declared without a body
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, source: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("functions.chl", source);
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn accepts_defined_and_extern_functions() {
+    let stderr = check(
+        "function_body_accept",
+        "extern fn abs(x: i32) -> i32\n\nfn double(x: i32) -> i32 = x * 2\n\nfn main() = {\n    let x = double(abs(-4))\n}\n",
+    );
+
+    assert!(
+        !stderr.contains("error"),
+        "expected the functions to check, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn rejects_function_without_body() {
+    let stderr = check("function_body_reject", "fn double(x: i32) -> i32\n\nfn main() = {}\n");
+
+    assert!(
+        stderr.contains("function `double` is declared but never defined"),
+        "expected the missing body to be reported, got: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("use `extern fn`"),
+        "expected a note about `extern fn`, got: {}",
+        stderr
+    );
+}
@@ -0,0 +1,38 @@
+// Answers two IDE queries in a single `--check` invocation, against the same check result
+#![cfg(unix)]
+
+mod common;
+
+use common::{root_dir, run, Sandbox};
+use std::fs;
+
+#[test]
+fn queries_share_one_check_result() {
+    let source_file = root_dir().join("tests").join("ide_session").join("main.chl");
+    let sandbox = Sandbox::new("ide_session");
+
+    let source = fs::read_to_string(&source_file).unwrap();
+    let definition_offset = source.find("answer =").unwrap();
+    let use_offset = source.rfind("answer").unwrap();
+
+    let output = run(sandbox
+        .chili()
+        .arg(&source_file)
+        .arg("--check")
+        .arg("--hover-info")
+        .arg(definition_offset.to_string())
+        .arg("--goto-def")
+        .arg(use_offset.to_string()));
+
+    assert!(output.status.success(), "failed to check the file");
+
+    let lines = output.stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 2, "expected one answer per query, got: {}", output.stdout);
+    assert_eq!(lines[0], r#"{"contents":"i32"}"#);
+    assert!(
+        lines[1].contains(&format!(r#""start":{}"#, definition_offset)),
+        "expected the use to lead to the definition, got: {}",
+        lines[1]
+    );
+}
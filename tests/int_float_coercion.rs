@@ -0,0 +1,54 @@
+// Coerces integer literals used with floats by casting them
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+#[test]
+fn casts_int_literal_to_float() {
+    let sandbox = Sandbox::new("int_float_coercion");
+    let path = sandbox.file(
+        "coercion.chl",
+        "fn main() = {
+    let x: f32 = 1 + 2.0
+}
+",
+    );
+    let module = sandbox.module_name("coercion");
+
+    let output = run(sandbox.chili().arg("ast").arg(&path).arg("--module").arg(&module));
+
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+    assert!(
+        !output.stderr.contains("error"),
+        "expected no errors, got: {}",
+        output.stderr
+    );
+    assert!(
+        output.stdout.contains("let x = 1 as f32 + 2"),
+        "expected the integer operand to be cast, got: {}",
+        output.stdout
+    );
+}
+
+#[test]
+fn rejects_int_value_with_float() {
+    let sandbox = Sandbox::new("int_float_coercion_value");
+    let path = sandbox.file(
+        "coercion.chl",
+        "fn main() = {
+    let i: i32 = 1
+    let x = i + 2.0
+}
+",
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.stderr.contains("mismatched types"),
+        "expected an integer value not to coerce to a float, got: {}",
+        output.stderr
+    );
+}
@@ -0,0 +1,60 @@
+// Links native object files into the executable with `--link-obj`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+#[test]
+fn links_object_file() {
+    let sandbox = Sandbox::new("link_obj");
+    let c_file = sandbox.file("triple.c", "int triple(int x) { return x * 3; }\n");
+    let object = sandbox.path("triple.o");
+
+    let status = Command::new("cc")
+        .arg("-c")
+        .arg(&c_file)
+        .arg("-o")
+        .arg(&object)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to compile the object file");
+
+    let path = sandbox.file(
+        "program.chl",
+        "extern fn triple(x: i32) -> i32\n\nfn main() = {\n    std.c.printf(\"%d\\n\".ptr, triple(14))\n}\n",
+    );
+    let exe = sandbox.path("program");
+
+    let output = run(sandbox
+        .chili()
+        .arg(&path)
+        .arg("--link-obj")
+        .arg(&object)
+        .arg("--output")
+        .arg(&exe));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    let output = run(&mut Command::new(&exe));
+
+    assert_eq!(output.stdout, "42\n");
+}
+
+#[test]
+fn rejects_missing_object_file() {
+    let sandbox = Sandbox::new("link_obj_missing");
+    let path = sandbox.file("program.chl", "fn main() = {}\n");
+    let object = sandbox.path("missing.o");
+
+    let output = run(sandbox.chili().arg(&path).arg("--link-obj").arg(&object));
+
+    assert!(
+        output
+            .stderr
+            .contains(&format!("object file `{}` doesn't exist", object.display())),
+        "expected the missing object file to be reported, got: {}",
+        output.stderr
+    );
+    assert!(!sandbox.path("program").exists(), "the program shouldn't be linked");
+}
@@ -0,0 +1,44 @@
+// Applies `!` to booleans and integers, and rejects it for floats
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, operand: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file(
+        "not.chl",
+        &format!(
+            "fn main() = {{\n    let value = {}\n    let not = !value\n}}\n",
+            operand
+        ),
+    );
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn accepts_bool_and_int_operands() {
+    for (name, operand) in [("not_bool", "true"), ("not_int", "5 as u8")] {
+        let stderr = check(name, operand);
+
+        assert!(
+            !stderr.contains("error"),
+            "expected `!` to apply to `{}`, got: {}",
+            operand,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn rejects_float_operand() {
+    let stderr = check("not_float", "1.5 as f32");
+
+    assert!(
+        stderr.contains("operator `!` cannot be applied to type `f32`"),
+        "expected the float operand to be rejected, got: {}",
+        stderr
+    );
+}
@@ -0,0 +1,59 @@
+// Selects the optimization level with `--opt-level`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::process::Command;
+
+const SOURCE: &str = "fn main() = {
+    let mut sum = 0
+    for i in 0..10 {
+        sum += i
+    }
+    std.c.printf(\"%d\\n\".ptr, sum)
+}
+";
+
+#[test]
+fn builds_at_every_level() {
+    let sandbox = Sandbox::new("opt_level");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    for opt_level in ["debug", "release"] {
+        let exe = sandbox.path(&format!("program_{}", opt_level));
+
+        let output = run(sandbox
+            .chili()
+            .arg(&path)
+            .arg("--opt-level")
+            .arg(opt_level)
+            .arg("--output")
+            .arg(&exe));
+        assert!(
+            output.status.success(),
+            "failed to build in {}: {}",
+            opt_level,
+            output.stderr
+        );
+
+        let output = run(&mut Command::new(&exe));
+
+        assert_eq!(output.stdout, "45\n", "wrong output in {}", opt_level);
+    }
+}
+
+#[test]
+fn rejects_unknown_level() {
+    let sandbox = Sandbox::new("opt_level_unknown");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--opt-level").arg("fast"));
+
+    assert!(!output.status.success(), "expected `fast` to be rejected");
+    assert!(
+        output.stderr.contains("fast"),
+        "expected the invalid level to be reported, got: {}",
+        output.stderr
+    );
+}
@@ -0,0 +1,50 @@
+// Chooses the executable's path with `-o`/`--output`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::{fs, process::Command};
+
+const SOURCE: &str = "fn main() = std.c.printf(\"built\\n\".ptr)\n";
+
+#[test]
+fn writes_executable_to_output() {
+    let sandbox = Sandbox::new("output");
+    let path = sandbox.file("program.chl", SOURCE);
+    fs::create_dir(sandbox.path("bin")).unwrap();
+
+    let output = run(sandbox.chili().arg(&path).arg("-o").arg("bin/renamed"));
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+    assert!(
+        !sandbox.path("program").exists(),
+        "the default output shouldn't be written"
+    );
+
+    let output = run(&mut Command::new(sandbox.path("bin").join("renamed")));
+
+    assert_eq!(output.stdout, "built\n");
+}
+
+#[test]
+fn rejects_invalid_output() {
+    let sandbox = Sandbox::new("output_invalid");
+    let path = sandbox.file("program.chl", SOURCE);
+    fs::create_dir(sandbox.path("bin")).unwrap();
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg("bin"));
+    assert!(
+        output.stdout.contains("output file `bin` is a directory"),
+        "expected the directory to be rejected, got: {}",
+        output.stdout
+    );
+
+    let output = run(sandbox.chili().arg(&path).arg("--output").arg("missing/program"));
+    assert!(
+        output
+            .stdout
+            .contains("the directory of output file `missing/program` doesn't exist"),
+        "expected the missing directory to be rejected, got: {}",
+        output.stdout
+    );
+}
@@ -0,0 +1,56 @@
+// Warns about struct unpack patterns that leave fields unbound with `--warn-partial-unpack`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn diagnostics(name: &str, pattern: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file(
+        "unpack.chl",
+        &format!(
+            "type Point = struct {{ x: i32, y: i32, z: i32 }}\n\n\
+             fn main() = {{\n    let point: Point = {{ x: 1, y: 2, z: 3 }}\n    let {} = point\n}}\n",
+            pattern
+        ),
+    );
+
+    let output = run(sandbox
+        .chili()
+        .arg(&path)
+        .arg("--check")
+        .arg("--diagnostics")
+        .arg("--warn-partial-unpack"));
+
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+    output.stdout
+}
+
+#[test]
+fn warns_about_unbound_fields() {
+    let diagnostics = diagnostics("partial_unpack", "{ x }");
+
+    assert!(
+        diagnostics.contains("doesn't bind fields `y`, `z`"),
+        "expected a partial unpack warning, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn full_and_glob_unpacks_are_quiet() {
+    for (name, pattern) in [
+        ("partial_unpack_full", "{ x, y, z }"),
+        ("partial_unpack_glob", "{ x, * }"),
+    ] {
+        let diagnostics = diagnostics(name, pattern);
+
+        assert!(
+            !diagnostics.contains("struct unpack"),
+            "expected `{}` to bind every field, got: {}",
+            pattern,
+            diagnostics
+        );
+    }
+}
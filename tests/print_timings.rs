@@ -0,0 +1,48 @@
+// Prints the time spent in each compiler phase with `--print-timings`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+const SOURCE: &str = "fn main() = {
+    let answer = 42
+}
+";
+
+#[test]
+fn prints_every_phase() {
+    let sandbox = Sandbox::new("print_timings");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--print-timings"));
+
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+
+    for phase in ["parse", "check", "lint", "codegen", "total"] {
+        assert!(
+            output
+                .stderr
+                .lines()
+                .any(|line| line.contains(phase) && line.trim_end().contains("ms")),
+            "expected a timing for `{}`, got: {}",
+            phase,
+            output.stderr
+        );
+    }
+}
+
+#[test]
+fn prints_nothing_by_default() {
+    let sandbox = Sandbox::new("print_timings_disabled");
+    let path = sandbox.file("program.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(output.status.success(), "failed to build: {}", output.stderr);
+    assert!(
+        !output.stderr.contains("total"),
+        "expected no timings, got: {}",
+        output.stderr
+    );
+}
@@ -0,0 +1,53 @@
+// Warns about casts of values to their own type
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn diagnostics(name: &str, source: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("casts.chl", source);
+
+    let output = run(sandbox.chili().arg(&path).arg("--check").arg("--diagnostics"));
+
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+    output.stdout
+}
+
+#[test]
+fn warns_about_redundant_cast() {
+    let diagnostics = diagnostics(
+        "redundant_cast",
+        "fn main() = {
+    let small: i32 = 42
+    let same = small as i32
+}
+",
+    );
+
+    assert!(
+        diagnostics.contains("redundant cast from `i32` to `i32`"),
+        "expected a redundant cast warning, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn needed_cast_is_quiet() {
+    // the standard library's own redundant casts aren't reported either
+    let diagnostics = diagnostics(
+        "redundant_cast_needed",
+        "fn main() = {
+    let small: i32 = 42
+    let big = small as i64
+}
+",
+    );
+
+    assert!(
+        !diagnostics.contains("redundant"),
+        "expected no redundant cast warnings, got: {}",
+        diagnostics
+    );
+}
@@ -0,0 +1,43 @@
+// Traces errors in required top level bindings back to the bindings that required them
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, a: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file(
+        "chain.chl",
+        &format!("let a: i32 = {}\n\nlet b = a\n\nfn main() = {{\n    let c = b\n}}\n", a),
+    );
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn checks_required_chain() {
+    let stderr = check("required_by_accept", "1");
+
+    assert!(
+        !stderr.contains("error"),
+        "expected the chain to check, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn notes_required_chain() {
+    let stderr = check("required_by_reject", "true");
+
+    assert!(
+        stderr.contains("mismatched types"),
+        "expected the error in `a`, got: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("`a` is required by `b`, at "),
+        "expected a note tracing `a` back to `b`, got: {}",
+        stderr
+    );
+}
@@ -0,0 +1,45 @@
+// Builds arrays with `splat!`, which are limited to the target's maximum object size like array types
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+#[test]
+fn splats_array() {
+    let sandbox = Sandbox::new("splat_accept");
+    let path = sandbox.file(
+        "splat.chl",
+        "fn main() = {\n    let scale = splat!(f32, 4, 2.0)\n    let halves = scale * splat!(f32, 4, 0.5)\n}\n",
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        !output.stderr.contains("error"),
+        "expected the splats to check, got: {}",
+        output.stderr
+    );
+}
+
+#[test]
+fn rejects_array_too_large() {
+    let sandbox = Sandbox::new("splat_too_large");
+    let path = sandbox.file(
+        "splat.chl",
+        "fn main() = {\n    let huge = splat!(i64, 4611686018427387904, 0)\n}\n",
+    );
+
+    let output = run(sandbox.chili().arg(&path));
+
+    assert!(
+        output.status.code().is_some(),
+        "the compiler crashed: {:?}",
+        output.status
+    );
+    assert!(
+        output.stderr.contains("array too large: `[4611686018427387904]i64`"),
+        "expected an array size diagnostic, got: {}",
+        output.stderr
+    );
+}
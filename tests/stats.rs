@@ -0,0 +1,85 @@
+// Prints code size metrics after codegen with `--stats`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::fs;
+
+const SOURCE: &str = "pub fn add(a: i32, b: i32) -> i32 = a + b\n";
+
+fn stat(stderr: &str, name: &str) -> u64 {
+    let line = stderr
+        .lines()
+        .find(|line| line.starts_with(name))
+        .unwrap_or_else(|| panic!("expected a `{}` line, got: {}", name, stderr));
+
+    line[name.len()..]
+        .split_whitespace()
+        .next()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| panic!("expected a number in `{}`", line))
+}
+
+#[test]
+fn prints_stats_after_codegen() {
+    let sandbox = Sandbox::new("stats");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--lib").arg("--stats"));
+
+    assert!(
+        output.status.success(),
+        "failed to build the library: {}",
+        output.stderr
+    );
+
+    assert!(stat(&output.stderr, "functions") >= 1);
+    assert!(stat(&output.stderr, "basic blocks") >= 1);
+    assert!(stat(&output.stderr, "instructions") >= 1);
+    stat(&output.stderr, "globals");
+    stat(&output.stderr, "data size");
+
+    let object_size = fs::metadata(sandbox.path("add.o"))
+        .expect("the object file wasn't kept")
+        .len();
+
+    assert_eq!(stat(&output.stderr, "object size"), object_size);
+}
+
+#[test]
+fn prints_no_stats_by_default() {
+    let sandbox = Sandbox::new("stats_default");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--lib"));
+
+    assert!(
+        output.status.success(),
+        "failed to build the library: {}",
+        output.stderr
+    );
+    assert!(
+        !output.stderr.contains("instructions"),
+        "expected no stats, got: {}",
+        output.stderr
+    );
+}
+
+#[test]
+fn rejects_stats_when_checking() {
+    let sandbox = Sandbox::new("stats_check");
+    let path = sandbox.file("add.chl", SOURCE);
+
+    let output = run(sandbox.chili().arg(&path).arg("--check").arg("--stats"));
+
+    assert!(
+        !output.status.success(),
+        "expected `--stats` to conflict with `--check`"
+    );
+    assert!(
+        output.stderr.contains("--stats"),
+        "expected the conflict to be reported, got: {}",
+        output.stderr
+    );
+}
@@ -0,0 +1,60 @@
+// Reads the root file's source from stdin when the input is `-`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+fn run_with_stdin(command: &mut Command, source: &str) -> (bool, String, String) {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(source.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn builds_source_from_stdin() {
+    let sandbox = Sandbox::new("stdin");
+
+    let (success, _, stderr) = run_with_stdin(
+        sandbox.chili().arg("-").arg("--output").arg(sandbox.path("piped")),
+        "fn main() = std.c.printf(\"piped\\n\".ptr)\n",
+    );
+    assert!(success, "failed to build: {}", stderr);
+
+    let output = run(&mut Command::new(sandbox.path("piped")));
+
+    assert_eq!(output.stdout, "piped\n");
+}
+
+#[test]
+fn refuses_to_watch_stdin() {
+    let sandbox = Sandbox::new("stdin_watch");
+
+    let (_, stdout, _) = run_with_stdin(
+        sandbox.chili().arg("-").arg("--check").arg("--watch"),
+        "fn main() = {}\n",
+    );
+
+    assert!(
+        stdout.contains("cannot watch source read from stdin"),
+        "expected watching stdin to be rejected, got: {}",
+        stdout
+    );
+}
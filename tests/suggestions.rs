@@ -0,0 +1,79 @@
+// Suggests similarly named bindings for names that can't be found
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, source: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("names.chl", source);
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn suggests_top_level_name() {
+    let stderr = check(
+        "suggestions_top_level",
+        "let counter: i32 = 0\n\nfn main() = {\n    let x = countr\n}\n",
+    );
+
+    assert!(
+        stderr.contains("did you mean `counter`?"),
+        "expected `counter` to be suggested, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn ignores_distant_top_level_name() {
+    let stderr = check(
+        "suggestions_top_level_distant",
+        "let counter: i32 = 0\n\nfn main() = {\n    let x = qqqq\n}\n",
+    );
+
+    assert!(
+        stderr.contains("error"),
+        "expected `qqqq` to be missing, got: {}",
+        stderr
+    );
+    assert!(
+        !stderr.contains("did you mean"),
+        "expected no suggestion, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn suggests_local_name() {
+    let stderr = check(
+        "suggestions_local",
+        "fn main() = {\n    let total = 1\n    let x = totl\n}\n",
+    );
+
+    assert!(
+        stderr.contains("did you mean `total`?"),
+        "expected `total` to be suggested, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn ignores_local_name_out_of_scope() {
+    let stderr = check(
+        "suggestions_local_out_of_scope",
+        "fn main() = {\n    {\n        let total = 1\n    }\n    let x = totl\n}\n",
+    );
+
+    assert!(
+        stderr.contains("error"),
+        "expected `totl` to be missing, got: {}",
+        stderr
+    );
+    assert!(
+        !stderr.contains("did you mean `total`?"),
+        "expected no suggestion for a binding out of scope, got: {}",
+        stderr
+    );
+}
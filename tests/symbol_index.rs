@@ -0,0 +1,37 @@
+// Indexes the public top-level bindings of a workspace with `--check --symbol-index`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+#[test]
+fn indexes_public_bindings_only() {
+    let sandbox = Sandbox::new("symbol_index");
+    let path = sandbox.file(
+        "symbols.chl",
+        "pub fn add(a: i32, b: i32) -> i32 = a + b
+
+fn hidden() = {}
+
+fn main() = {}
+",
+    );
+    let module = sandbox.module_name("symbols");
+
+    let output = run(sandbox.chili().arg(&path).arg("--check").arg("--symbol-index"));
+
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+    assert!(
+        output
+            .stdout
+            .contains(&format!(r#""name":"add","module":"{}","kind":"function""#, module)),
+        "expected `add` to be indexed, got: {}",
+        output.stdout
+    );
+    assert!(
+        !output.stdout.contains(r#""name":"hidden""#),
+        "expected private bindings to be left out, got: {}",
+        output.stdout
+    );
+}
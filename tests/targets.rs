@@ -0,0 +1,38 @@
+// Lists the platforms the compiler can target with `chili version --verbose`
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+#[test]
+fn lists_supported_targets() {
+    let sandbox = Sandbox::new("targets");
+
+    let output = run(sandbox.chili().arg("version").arg("--verbose"));
+
+    assert!(
+        output.status.success(),
+        "failed to print the version: {}",
+        output.stderr
+    );
+
+    for target in [
+        "LinuxAmd64 (x86_64-pc-linux-gnu)",
+        "WindowsAmd64 (x86_64-pc-windows-msvc)",
+        "DarwinAmd64 (x86_64-apple-darwin)",
+    ] {
+        assert!(
+            output.stdout.contains(target),
+            "expected `{}` to be supported, got: {}",
+            target,
+            output.stdout
+        );
+    }
+
+    assert!(
+        !output.stdout.contains("Wasm"),
+        "expected wasm targets to be unsupported, got: {}",
+        output.stdout
+    );
+}
@@ -0,0 +1,43 @@
+// Runs `chili test` on a file with passing and failing tests, and checks each one is run and reported
+#![cfg(unix)]
+
+mod common;
+
+use common::{root_dir, run, Sandbox};
+
+#[test]
+fn reports_every_test() {
+    let sandbox = Sandbox::new("test_runner");
+    let source = std::fs::read_to_string(root_dir().join("tests").join("test_runner").join("tests.chl")).unwrap();
+    let source_file = sandbox.file("tests.chl", &source);
+
+    let output = run(sandbox.chili().arg("test").arg(&source_file));
+
+    assert_eq!(output.status.code(), Some(1), "a failing test should fail the run");
+
+    let lines = output.stdout.lines().collect::<Vec<_>>();
+    let has_line = |suffix: &str| lines.iter().any(|line| line.ends_with(suffix));
+
+    assert!(lines.contains(&"running 5 test(s)"), "{}", output.stdout);
+
+    for passing in ["adds", "calls_into_c", "runs_inline_assembly", "runs_after_a_failure"] {
+        assert!(
+            has_line(&format!("{} ... ok", passing)),
+            "`{}` should pass: {}",
+            passing,
+            output.stdout
+        );
+    }
+
+    assert!(has_line("fails ... FAILED"), "`fails` should fail: {}", output.stdout);
+    assert!(output.stdout.contains("this test always fails"), "{}", output.stdout);
+    assert!(output.stdout.contains("still running"), "{}", output.stdout);
+    assert!(
+        lines.contains(&"test result: FAILED. 4 passed; 1 failed"),
+        "{}",
+        output.stdout
+    );
+
+    // the test executable is removed after it runs
+    assert!(!sandbox.path("tests_test").exists());
+}
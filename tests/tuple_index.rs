@@ -0,0 +1,46 @@
+// Accesses tuple elements by index, and reports indices past the tuple's arity
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, index: usize) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file(
+        "tuple.chl",
+        &format!(
+            "fn main() = {{\n    let point: (i32, i32) = (5, 10)\n    let element = point.{}\n}}\n",
+            index
+        ),
+    );
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn accepts_index_within_arity() {
+    let stderr = check("tuple_index_accept", 1);
+
+    assert!(
+        !stderr.contains("error"),
+        "expected the last element to be accessible, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn reports_index_and_arity() {
+    let stderr = check("tuple_index_reject", 2);
+
+    assert!(
+        stderr.contains("tuple index `2` is out of bounds for type `(i32, i32)`, which has 2 elements"),
+        "expected the index and the arity, got: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("valid indices are 0 to 1"),
+        "expected the valid indices, got: {}",
+        stderr
+    );
+}
@@ -0,0 +1,36 @@
+// Rejects integer literals that don't fit in their type
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn check(name: &str, binding: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("limits.chl", &format!("fn main() = {{\n    {}\n}}\n", binding));
+
+    run(sandbox.chili().arg(&path)).stderr
+}
+
+#[test]
+fn accepts_literal_within_word_size() {
+    // the integration tests run on 64-bit hosts, which are also the default target
+    let stderr = check("type_limits_accept", "let x: int = 5000000000");
+
+    assert!(
+        !stderr.contains("error"),
+        "expected the literal to fit `int`, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn rejects_literal_overflow() {
+    let stderr = check("type_limits_reject", "let x: i32 = 5000000000");
+
+    assert!(
+        stderr.contains("integer literal of type `i32` must be between -2147483648 and 2147483647, found 5000000000"),
+        "expected the literal to overflow `i32`, got: {}",
+        stderr
+    );
+}
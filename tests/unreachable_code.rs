@@ -0,0 +1,50 @@
+// Warns about statements following an expression that never finishes
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+// The starting offsets of the unreachable statement warnings in the checked file
+fn unreachable_statements(name: &str, source: &str) -> Vec<u64> {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("code.chl", source);
+
+    let output = run(sandbox.chili().arg(&path).arg("--check").arg("--diagnostics"));
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+
+    let objects: Vec<serde_json::Value> = serde_json::from_str(&output.stdout).expect("expected a json array");
+
+    objects
+        .iter()
+        .filter(|object| {
+            object["type"] == "Diagnostic"
+                && object["message"]
+                    .as_str()
+                    .map_or(false, |message| message.starts_with("unreachable statement"))
+                && object["span"]["file"]
+                    .as_str()
+                    .map_or(false, |file| file.ends_with("code.chl"))
+        })
+        .map(|object| object["span"]["start"].as_u64().unwrap())
+        .collect()
+}
+
+#[test]
+fn warns_about_statement_after_abort() {
+    let source = "fn main() = {\n    abort!(1)\n    let after = 2\n}\n";
+
+    let statements = unreachable_statements("unreachable_code", source);
+
+    assert_eq!(statements, vec![source.find("let after").unwrap() as u64]);
+}
+
+#[test]
+fn trailing_diverging_expression_is_quiet() {
+    let statements = unreachable_statements(
+        "unreachable_code_trailing",
+        "fn main() = {\n    let before = 2\n    abort!(1)\n}\n",
+    );
+
+    assert!(statements.is_empty(), "expected no warnings, got: {:?}", statements);
+}
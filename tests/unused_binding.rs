@@ -0,0 +1,63 @@
+// Warns about bindings that are never used
+#![cfg(unix)]
+
+mod common;
+
+use common::{run, Sandbox};
+
+fn diagnostics(name: &str, source: &str) -> String {
+    let sandbox = Sandbox::new(name);
+    let path = sandbox.file("bindings.chl", source);
+
+    let output = run(sandbox.chili().arg(&path).arg("--check").arg("--diagnostics"));
+
+    assert!(output.status.success(), "failed to check: {}", output.stderr);
+    output.stdout
+}
+
+#[test]
+fn warns_about_unused_bindings() {
+    let diagnostics = diagnostics(
+        "unused_binding",
+        "fn helper() = {}
+
+fn main() = {
+    let unused = 1
+}
+",
+    );
+
+    assert!(
+        diagnostics.contains("unused binding `unused`"),
+        "expected an unused binding warning, got: {}",
+        diagnostics
+    );
+    assert!(
+        diagnostics.contains("unused function `helper`"),
+        "expected an unused function warning, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn used_and_ignored_bindings_are_quiet() {
+    // `main`, public bindings and bindings prefixed with an underscore are never reported
+    let diagnostics = diagnostics(
+        "unused_binding_quiet",
+        "pub fn api() = {}
+
+fn helper() -> i32 = 1
+
+fn main() = {
+    let used = helper()
+    let _ignored = used
+}
+",
+    );
+
+    assert!(
+        !diagnostics.contains("unused"),
+        "expected no unused warnings, got: {}",
+        diagnostics
+    );
+}
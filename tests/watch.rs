@@ -0,0 +1,80 @@
+// Re-checks a file whenever it changes with `--check --watch`
+#![cfg(unix)]
+
+mod common;
+
+use common::Sandbox;
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    process::{Child, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+const VALID: &str = "fn main() = {\n    let x: i32 = 1\n}\n";
+const INVALID: &str = "fn main() = {\n    let x: i32 = true\n}\n";
+
+// Watching never finishes on its own, so the process is killed when the test ends, even if it fails
+struct Watcher(Child);
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn rechecks_on_change() {
+    let sandbox = Sandbox::new("watch");
+    let path = sandbox.file("watched.chl", VALID);
+
+    let mut watcher = Watcher(
+        sandbox
+            .chili()
+            .arg(&path)
+            .arg("--check")
+            .arg("--watch")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap(),
+    );
+
+    // every check prints a single line of diagnostics
+    let (sender, receiver) = mpsc::channel();
+    let stdout = watcher.0.stdout.take().unwrap();
+
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let next_check = || {
+        receiver
+            .recv_timeout(Duration::from_secs(60))
+            .expect("the file wasn't checked")
+    };
+
+    let diagnostics = next_check();
+    assert!(
+        !diagnostics.contains("mismatched types"),
+        "expected the valid file to check, got: {}",
+        diagnostics
+    );
+
+    // make sure the modification time changes, even on file systems with a coarse resolution
+    thread::sleep(Duration::from_secs(1));
+    fs::write(&path, INVALID).unwrap();
+
+    let diagnostics = next_check();
+    assert!(
+        diagnostics.contains("mismatched types"),
+        "expected the change to be re-checked, got: {}",
+        diagnostics
+    );
+}